@@ -1,4 +1,4 @@
-use std::error::Error as StdError;
+use std::{error::Error as StdError, net::AddrParseError};
 
 use thiserror::Error;
 
@@ -19,12 +19,68 @@ pub enum ParseError {
     #[error("found equalsign with no key or value around it")]
     UnexpectedEqualsign,
 
-    #[error("key `{key}` is of unexpected type")]
-    UnexpectedKeyType { key: String },
+    #[error("key `{key}` is not a valid `{ty}`")]
+    UnexpectedKeyType { key: String, ty: String },
+
+    #[error("override `{arg}` is not of the form `KEY=VALUE`")]
+    InvalidOverride { arg: String },
+
+    #[error("key `{key}` is not read by any field")]
+    UnknownKey { key: String },
 
     #[error("value `{value}` is of unexpected type")]
     UnexpectedValueType { value: String },
 
+    #[error("value of `{field}` does not match the pattern `{pattern}`")]
+    PatternMismatch { field: String, pattern: String },
+
+    #[error("no capture group was matched for `{field}`")]
+    MissingCapture { field: String },
+
+    #[error("`{value}` is not a valid interval, expected e.g. `[0,100)`")]
+    InvalidInterval { value: String },
+
+    #[error("`{value}` is not a valid duration, expected e.g. `1h 30m`")]
+    InvalidDuration { value: String },
+
+    #[error(
+        "`{value}` is not a valid RFC 3339 date-time, expected e.g. `2024-05-01T12:00:00Z`: \
+         {reason}"
+    )]
+    InvalidDateTime { value: String, reason: String },
+
+    #[error("`{value}` is not a valid unix timestamp: {reason}")]
+    InvalidTimestamp { value: String, reason: String },
+
+    #[error("no parser returning `{ty}` is registered for `{key}`")]
+    UnknownParser { key: String, ty: String },
+
+    #[error("`{value}` is not a valid range, expected e.g. `8000-8100`")]
+    InvalidRange { value: String },
+
+    #[error("range `{value}` is reversed, the start cannot be greater than the end")]
+    ReversedRange { value: String },
+
+    #[error("`{value}` does not fit in the integer type")]
+    IntegerOverflow { value: String },
+
+    #[error("`{value}` is not a valid network address: {err}")]
+    InvalidAddress {
+        value: String,
+        #[source]
+        err: AddrParseError,
+    },
+
+    #[error("found {len} elements but at most {capacity} are allowed")]
+    CapacityExceeded { len: usize, capacity: usize },
+
+    #[error("element at index {index} is invalid: {err}")]
+    InvalidElement {
+        index: usize,
+        #[source]
+        err: Box<ParseError>,
+    },
+
     #[error("parsing failed for `{field}`: {err}")]
     Failed {
         field: String,
@@ -33,6 +89,73 @@ pub enum ParseError {
     },
 }
 
+impl ParseError {
+    /// Wraps the error of parsing the value of `field`, keeping it as the
+    /// source of the returned error
+    pub fn failed(field: impl ToString, err: impl Into<BoxError>) -> Self {
+        Self::Failed {
+            field: field.to_string(),
+            err: err.into(),
+        }
+    }
+
+    /// Replaces any loaded value in the error with `"[redacted]"`, e.g., for
+    /// fields containing secrets. Errors from custom parse functions are
+    /// replaced entirely as their message may include the value
+    pub fn redacted(self) -> Self {
+        const REDACTED: &str = "[redacted]";
+
+        match self {
+            Self::UnexpectedKeyType { ty, .. } => Self::UnexpectedKeyType {
+                key: REDACTED.to_string(),
+                ty,
+            },
+            Self::UnexpectedValueType { .. } => Self::UnexpectedValueType {
+                value: REDACTED.to_string(),
+            },
+            Self::InvalidInterval { .. } => Self::InvalidInterval {
+                value: REDACTED.to_string(),
+            },
+            Self::InvalidDuration { .. } => Self::InvalidDuration {
+                value: REDACTED.to_string(),
+            },
+            Self::InvalidDateTime { reason, .. } => Self::InvalidDateTime {
+                value: REDACTED.to_string(),
+                reason,
+            },
+            Self::InvalidTimestamp { reason, .. } => Self::InvalidTimestamp {
+                value: REDACTED.to_string(),
+                reason,
+            },
+            Self::InvalidRange { .. } => Self::InvalidRange {
+                value: REDACTED.to_string(),
+            },
+            Self::ReversedRange { .. } => Self::ReversedRange {
+                value: REDACTED.to_string(),
+            },
+            Self::IntegerOverflow { .. } => Self::IntegerOverflow {
+                value: REDACTED.to_string(),
+            },
+            Self::InvalidAddress { err, .. } => Self::InvalidAddress {
+                value: REDACTED.to_string(),
+                err,
+            },
+            Self::InvalidElement { index, err } => Self::InvalidElement {
+                index,
+                err: Box::new(err.redacted()),
+            },
+            Self::Failed { field, err } => Self::Failed {
+                field,
+                err: match err.downcast::<ParseError>() {
+                    Ok(err) => Box::new(err.redacted()),
+                    Err(_) => REDACTED.into(),
+                },
+            },
+            other => other,
+        }
+    }
+}
+
 #[derive(Debug, Error, strum::EnumIs)]
 pub enum RetrieveError {
     #[error("none of the environment variables ({keys}) was found")]
@@ -41,8 +164,31 @@ pub enum RetrieveError {
     #[error("environment variable `{key}` contains invalid Unicode")]
     InvalidUnicode { key: String },
 
+    #[error("file `{path}` could not be read: {err}")]
+    ReadFile {
+        path: String,
+        #[source]
+        err: std::io::Error,
+    },
+
+    #[error("environment variable `{key}` has been removed{}", .note.as_deref().map(|note| format!(", {note}")).unwrap_or_default())]
+    Removed { key: String, note: Option<String> },
+
+    #[error("hydration hook failed: {err}")]
+    Hydrate {
+        #[source]
+        err: BoxError,
+    },
+
     #[error("fatal error occurred")]
     Fatal,
+
+    #[error("retrieval failed for `{field}`: {err}")]
+    Failed {
+        field: String,
+        #[source]
+        err: Box<RetrieveError>,
+    },
 }
 
 #[derive(Debug, Error)]
@@ -59,6 +205,85 @@ pub enum ValidationError {
         #[source]
         err: BoxError,
     },
+
+    #[error("`{field}` cannot be set together with `{other}`")]
+    Conflict { field: String, other: String },
+
+    #[error("`{field}` must be one of {}", .allowed.iter().map(|v| format!("`{v}`")).collect::<Vec<_>>().join(", "))]
+    NotOneOf { field: String, allowed: Vec<String> },
+
+    #[error("`{field}` must be a multiple of {step}")]
+    NotMultipleOf { field: String, step: String },
+
+    #[error("`{field}` has the scheme `{scheme}`, expected one of {}", .allowed.iter().map(|v| format!("`{v}`")).collect::<Vec<_>>().join(", "))]
+    InvalidScheme {
+        field: String,
+        scheme: String,
+        allowed: Vec<String>,
+    },
+
+    #[error("`{field}` contains the element at index {index} more than once")]
+    Duplicate { field: String, index: usize },
+
+    #[error("environment variables {} are not read by any field", .keys.iter().map(|k| format!("`{k}`")).collect::<Vec<_>>().join(", "))]
+    UnknownVariables { keys: Vec<String> },
+
+    #[error(
+        "environment variable `{name}` would be collected as `{key}`, which is read by another \
+         field"
+    )]
+    RestOverlap { name: String, key: String },
+
+    #[error("`{field}` is required when `{other}` is `{value}`")]
+    Required {
+        field: String,
+        other: String,
+        value: String,
+    },
+
+    #[error("schema `{path}` could not be loaded: {err}")]
+    InvalidSchema {
+        path: String,
+        #[source]
+        err: BoxError,
+    },
+
+    #[error("value could not be serialized for schema `{path}`: {err}")]
+    Unserializable {
+        path: String,
+        #[source]
+        err: BoxError,
+    },
+
+    #[error("schema validation failed for `{instance_path}` at `{schema_path}`: {message}")]
+    Schema {
+        instance_path: String,
+        schema_path: String,
+        message: String,
+    },
+}
+
+/// The stage of loading an [`Error`] occurred in, e.g., to map a failed field
+/// to a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Reading the value, e.g., a missing environment variable
+    Retrieve,
+
+    /// Parsing the value into the type of the field
+    Parse,
+
+    /// Validating the parsed value
+    Validation,
+
+    /// Selecting the variant of an enum
+    Enum,
+
+    /// Converting a default value into the type of the field
+    Convert,
+
+    /// Serializing a loaded value, e.g., to JSON
+    Serialize,
 }
 
 #[derive(Debug, Error, strum::EnumIs)]
@@ -77,4 +302,176 @@ pub enum Error {
 
     #[error("Failed to convert field `{field}` to expected type `{ty}`")]
     ConvertError { field: String, ty: String },
+
+    #[error("Failed to serialize field `{field}`: {err}")]
+    SerializeError {
+        field: String,
+        #[source]
+        err: BoxError,
+    },
+}
+
+impl Error {
+    /// Returns the name of the field which failed to load, if the error is
+    /// tied to a single field, e.g., to report it to the caller together with
+    /// the [`category`](Self::category) of the error. Retrieve and parse
+    /// errors of a field are wrapped in `RetrieveError::Failed` and
+    /// `ParseError::Failed` to name it
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            Self::RetrieveError(RetrieveError::Failed { field, .. }) => Some(field),
+            Self::ParseError(
+                ParseError::Failed { field, .. }
+                | ParseError::PatternMismatch { field, .. }
+                | ParseError::MissingCapture { field },
+            ) => Some(field),
+            Self::ValidationError(
+                ValidationError::Failed { field, .. }
+                | ValidationError::Conflict { field, .. }
+                | ValidationError::NotOneOf { field, .. }
+                | ValidationError::NotMultipleOf { field, .. }
+                | ValidationError::InvalidScheme { field, .. }
+                | ValidationError::Duplicate { field, .. }
+                | ValidationError::Required { field, .. },
+            ) => Some(field),
+            Self::ConvertError { field, .. } | Self::SerializeError { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Returns the stage of loading the error occurred in
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::RetrieveError(_) => ErrorCategory::Retrieve,
+            Self::ParseError(_) => ErrorCategory::Parse,
+            Self::ValidationError(_) => ErrorCategory::Validation,
+            Self::EnumError(_) => ErrorCategory::Enum,
+            Self::ConvertError { .. } => ErrorCategory::Convert,
+            Self::SerializeError { .. } => ErrorCategory::Serialize,
+        }
+    }
+
+    /// Attaches `field` to a retrieve or parse error which does not name a
+    /// field yet, by wrapping it in `RetrieveError::Failed` or
+    /// `ParseError::Failed`. Errors naming a field, e.g., those of a nested
+    /// struct, are returned as is
+    #[doc(hidden)]
+    pub fn in_field(self, field: &str) -> Self {
+        if self.field().is_some() {
+            return self;
+        }
+
+        match self {
+            Self::RetrieveError(err) => RetrieveError::Failed {
+                field: field.to_string(),
+                err: Box::new(err),
+            }
+            .into(),
+            Self::ParseError(err) => ParseError::failed(field, err).into(),
+            other => other,
+        }
+    }
+
+    /// Replaces any loaded value in a parse error with `"[redacted]"`. Other
+    /// errors are returned as is
+    pub fn redacted(self) -> Self {
+        match self {
+            Self::ParseError(err) => Self::ParseError(err.redacted()),
+            other => other,
+        }
+    }
+}
+
+/// All errors of loading a struct with
+/// [`Envoke::try_envoke_all`](crate::Envoke::try_envoke_all), one per field
+/// which failed to load, including those of nested fields. Errors which are
+/// not tied to a field, e.g., a missing dotenv file, end the load and are
+/// returned on their own. A returned value always holds at least one error
+#[derive(Debug, Default)]
+pub struct Errors(Vec<Error>);
+
+impl Errors {
+    /// Returns an iterator over the errors, each of which is a single
+    /// failure, e.g., to report every failed field with its
+    /// [`field`](Error::field) and [`category`](Error::category)
+    pub fn iter(&self) -> std::slice::Iter<'_, Error> {
+        self.0.iter()
+    }
+
+    /// Returns the number of errors
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no errors
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[doc(hidden)]
+    pub fn extend(&mut self, other: Errors) {
+        self.0.extend(other.0);
+    }
+
+    /// Attaches `field` to each error, see [`Error::in_field`]
+    #[doc(hidden)]
+    pub fn in_field(self, field: &str) -> Self {
+        Self(self.0.into_iter().map(|err| err.in_field(field)).collect())
+    }
+
+    /// Returns the first error, which is the only one unless all errors were
+    /// collected
+    #[doc(hidden)]
+    pub fn into_first(self) -> Error {
+        self.0
+            .into_iter()
+            .next()
+            .expect("there is at least one error")
+    }
 }
+
+impl std::fmt::Display for Errors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, err) in self.0.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{err}")?;
+        }
+        Ok(())
+    }
+}
+
+impl StdError for Errors {}
+
+impl IntoIterator for Errors {
+    type Item = Error;
+    type IntoIter = std::vec::IntoIter<Error>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Errors {
+    type Item = &'a Error;
+    type IntoIter = std::slice::Iter<'a, Error>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+macro_rules! impl_from_for_errors {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Errors {
+                fn from(err: $ty) -> Self {
+                    Self(vec![err.into()])
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_errors!(Error, RetrieveError, ParseError, ValidationError, EnumError);
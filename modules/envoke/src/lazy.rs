@@ -0,0 +1,72 @@
+use std::{convert::Infallible, fmt, str::FromStr, sync::OnceLock};
+
+use crate::{
+    errors::{ParseError, Result},
+    utils::parse_str,
+};
+
+/// A field value which is parsed on first access instead of when the struct
+/// is loaded, so a malformed value only results in an error if it is used.
+/// Loaded by fields with the `lazy_parse` attribute
+pub struct Lazy<T> {
+    field: &'static str,
+    raw: String,
+    sensitive: bool,
+    value: OnceLock<T>,
+}
+
+impl<T: FromStr> Lazy<T> {
+    /// Returns the parsed value, parsing it on the first access. A value
+    /// which cannot be parsed is parsed again on the next access
+    pub fn get(&self) -> Result<&T> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let value = parse_str(&self.raw).map_err(|e| {
+            let err = ParseError::failed(self.field, e);
+            match self.sensitive {
+                true => err.redacted(),
+                false => err,
+            }
+        })?;
+
+        Ok(self.value.get_or_init(|| value))
+    }
+}
+
+impl<T> Lazy<T> {
+    /// Returns the value as it was loaded from the environment
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let raw = match self.sensitive {
+            true => "***",
+            false => &self.raw,
+        };
+
+        f.debug_struct("Lazy")
+            .field("raw", &raw)
+            .field("value", &self.value.get())
+            .finish()
+    }
+}
+
+/// Returns the function wrapping the loaded value of `field` in a [`Lazy`]
+pub fn parse_lazy<T>(
+    field: &'static str,
+    sensitive: bool,
+) -> impl Fn(String) -> std::result::Result<Lazy<T>, Infallible> {
+    move |raw| {
+        Ok(Lazy {
+            field,
+            raw,
+            sensitive,
+            value: OnceLock::new(),
+        })
+    }
+}
@@ -26,23 +26,43 @@
 //! | Screaming snake case | `SCREAMING_SNAKE_CASE` | Converts names to uppercase and uses underscores `_` to separate words                                             |
 //! | Kebab case           | `kebab-case`           | Converts names to lowercase and uses hyphens `-` to separate words                                                 |
 //! | Screaming kebab case | `SCREAMING-KEBAB-CASE` | Converts names to uppercase and uses hyphens `-` to separate words                                                 |
+//! | Train case           | `Train-Case`           | Capitalizes the first letter of each word and uses hyphens `-` to separate words                                   |
+//! | Dot case             | `dot.case`             | Converts names to lowercase and uses dots `.` to separate words                                                    |
+//! | Flat case            | `flatcase`             | Converts names to lowercase and joins words without any separator                                                  |
 //!
 //! </br>
 //!
 //! ### Structs
 //!
+//! Both structs with named fields and tuple structs are supported. Fields of
+//! tuple structs have no name to use as the environment variable, so it must
+//! be given explicitly, e.g., `struct Port(#[fill(env = "PORT")] u16);`.
+//!
 //! **Container**
 //!
 //! Below are the current implemented container attributes. This list will be
 //! updated as more are added or changed.
 //!
-//! | Attribute    | Default | Description                                                                                                                                                                                                                                                                                                                                                                                  |
-//! | ------------ | ------- | -------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
-//! | `prefix`     | None    | Set a custom prefix which will be prepended infront of environment variables before fetching                                                                                                                                                                                                                                                                                                 |
-//! | `suffix`     | None    | Set a custom prefix which will be appended infront of environment variables before fetching                                                                                                                                                                                                                                                                                                  |
-//! | `delimiter`  | None    | Set a customer delimiter used for separated prefix, environment variable, and suffix. **NB!** If you are using the `rename_all` attribute as well it will take priority over the delimiter. It can still be useful to include the delimiter to ensure the prefix, environment variable, and suffix are separated before renaming occurs otherwise they will be interpreted as a single word! |
-//! | `rename_all` | None    | Rename all environment variables to a different naming case. See [name cases](#name-cases) for a full list and description of the different options.                                                                                                                                                                                                                                         |
-//! | `dotenv`     | None    | Set a dotenv file to use when loading environment variables into structs/enums. Note that environment variables in the process's environment have a higher priority than those found in the dotenv file.                                                                                                                                                                                     |
+//! | Attribute      | Default | Description                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                          |
+//! | -------------- | ------- | ---------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+//! | `prefix`       | None    | Set a custom prefix which will be prepended infront of environment variables before fetching                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                         |
+//! | `suffix`       | None    | Set a custom prefix which will be appended infront of environment variables before fetching                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                          |
+//! | `delimiter`    | Empty   | Set a custom delimiter used to separate the prefix, environment variable, and suffix. Without it they are joined as is, e.g., `prefix = "APP"` loads `APPport`, so either include the separator in the prefix, e.g., `prefix = "APP_"`, or set `delimiter = "_"`. **NB!** If you are using the `rename_all` attribute as well it will take priority over the delimiter. It can still be useful to include the delimiter to ensure the prefix, environment variable, and suffix are separated before renaming occurs otherwise they will be interpreted as a single word!                             |
+//! | `rename_all`   | None    | Rename all environment variables to a different naming case. See [name cases](#name-cases) for a full list and description of the different options.                                                                                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | `dotenv`       | None    | Set a dotenv file to use when loading environment variables into structs/enums. Note that environment variables in the process's environment have a higher priority than those found in the dotenv file.                                                                                                                                                                                                                                                                                                                                                                                             |
+//! | `json`         | False   | Generate `to_json_redacted` and `to_json_value_redacted` methods which serialize the loaded struct to JSON, e.g., for logging the effective configuration at startup. Fields marked `sensitive` are replaced with `"***"` and `nested` fields use the nested type's method, so it must also have this attribute. If a value cannot be serialized, e.g., a map with non-string keys, a serialize error naming the field is returned. Requires the `serde` feature.                                                                                                                                    |
+//! | `schema`       | None    | Validate the loaded struct against a JSON Schema file after all fields are loaded. The path is relative to the directory of the crate's `Cargo.toml` and the file is embedded at compile time, so loading does not depend on the working directory. The struct must implement `serde::Serialize`. On failure a validation error containing the path of the invalid value and of the failing schema keyword is returned. Requires the `schema` feature.                                                                                                                                               |
+//! | `config_json`  | None    | Environment variable containing the whole struct as JSON, which is deserialized with `serde` if it is set. The name gets the container's prefix, suffix and case like any other variable. A field is taken from the JSON unless one of its own environment variables is set, which always takes priority. Fields without environment variables, e.g., `nested` fields, are taken from the JSON as is. If the variable is not set, fields are loaded as usual, while other errors, e.g., invalid Unicode, are returned. The struct must implement `serde::Deserialize`. Requires the `serde` feature. |
+//! | `from_map`     | False   | Generate an `impl TryFrom<HashMap<String, String>>` which loads the struct from the given map instead of the process environment, e.g., for configuration read from another source or for tests. The map is also used by `nested` fields. A `dotenv` file is still used as a fallback.                                                                                                                                                                                                                                                                                                               |
+//! | `env_line`     | False   | Generate an `env_line(field)` method which returns the `KEY=value` line of the named field using its resolved environment variable name, e.g., for emitting or diffing individual settings. Sequences and maps are formatted with the field's delimiter and fields marked `sensitive` are replaced with `***`. Returns `None` for unknown fields and unset optional fields. Other names are looked up in `nested` fields, so the nested type must also have this attribute. Values, or their elements, must implement `Display`.                                                                     |
+//! | `diff`         | False   | Generate a `diff(other)` method which returns an `envoke::FieldDiff` for each field whose value differs from `other`, e.g., for logging what changed when reloading. Values are formatted like `env_line`, so fields marked `sensitive` are replaced with `***`. Changes in `nested` fields are reported with the field path, e.g., `database.url`, so the nested type must also have this attribute. Fields marked `ignore` or `map_prefix` are skipped. Values, or their elements, must implement `Display` and `PartialEq`.                                                                       |
+//! | `reload`       | False   | Generate a `reload()` method which loads a new instance from the current environment like `try_envoke`, but keeps the values of fields marked `ignore` from `self` instead of resetting them, e.g., for runtime state such as connection pools when reloading the config. Ignored fields must implement `Clone`. Ignored fields of `nested` fields are not kept.                                                                                                                                                                                                                                     |
+//! | `env_priority` | `first` | Which environment variable of a field is used if several of its `env` names are set. `first` uses the first one listed and `last` the last one, e.g., with `#[fill(env = "PORT", env = "APP_PORT")]` and `last`, `APP_PORT` overrides `PORT`. Names from `deprecated_env` are only used if none of the `env` names are set either way.                                                                                                                                                                                                                                                               |
+//! | `global`       | False   | Generate a `global()` method returning a `&'static` instance which is loaded with `envoke()` on first access and shared afterwards, e.g., `Config::global().port`. Panics on first access if the struct cannot be loaded and on every access after that, call `try_envoke()` at startup first to handle errors. Changes to the environment after the first access are not picked up, so tests changing variables should load the struct directly. Cannot be used on generic structs.                                                                                                                 |
+//! | `hydrate`      | None    | Call the given function if loading fails with a `RetrieveError`, e.g., because a variable is missing, and retry loading once, e.g., `#[fill(hydrate = bootstrap)]` where `bootstrap` populates the environment from a remote source. The function must be a `fn() -> Result<(), E>` where `E` converts into a boxed error. If it fails a `RetrieveError::Hydrate` is returned, and if loading fails again that error is returned.                                                                                                                                                                    |
+//! | `deny_unknown` | False   | Fail with a `ValidationError` listing the variables if any variable starting with `prefix`, followed by `delimiter` if set, is set in the process environment without being read by any field, including those of nested fields, e.g., to catch a typo such as `APP_PROT` instead of `APP_PORT`. The prefix is matched as written, so it should be written in the same case as the variables. Variables in the dotenv file are not checked. Only makes sense with a prefix, so `prefix` must be set. Cannot be used on structs with a `map_prefix` field.                                            |
+//! | `fuzzy_names`  | False   | Read a variable which is not set from one whose name only differs in case and separators, e.g., `APP_PORT` is read from `app-port` or `App.Port` if `APP_PORT` itself is not set. The separators `_`, `-`, and `.` are treated as equal. If several variables match, the first one in sorted order is used. The environment is only scanned for a variable which is not set, and nested fields are looked up the same way. Variables in the dotenv file are not matched. Cannot be used together with `deny_unknown`.                                                                                |
+//! | `removed`      | None    | Fail with a `RetrieveError` if a variable which has been removed is set, e.g., `removed(env = "OLD_VAR", note = "use NEW_VAR")`, so a stale deployment fails loudly instead of its setting being silently ignored. The name is matched as written, without the prefix, suffix, or `rename_all` applied. The optional `note` is added to the error to guide the operator to the replacement. Can be given several times, once per removed variable. Variables in the dotenv file are not checked.                                                                                                     |
 //!
 //! </br>
 //!
@@ -51,19 +71,65 @@
 //! Below are the current implemented field attributes. This list will be
 //! updated as more are added or changed.
 //!
-//! | Attribute      | Default    | Description                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                           |
-//! | -------------- | ---------- | ------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
-//! | `env`          | field name | Environment variable name to load the field value from. Can be chained multiple times to allow for fallbacks. The macro follows a first come, first serve basis meaning it attempts to load the variables in the order they are listed. Once an value is found it will try to parse it into the specified type. If it fails it will return an error and wont try the remaining ones in the list. This behavior might change in the future. Optionally, you can supply your own parsing function. See `parse_fn` for more information! |
-//! | `default`      | None       | Use the default value if the environment variable is not found. Optionally to statically assign a value to the field `env` can be omitted.                                                                                                                                                                                                                                                                                                                                                                                            |
-//! | `parse_fn`     | None       | Set a custom parsing function for parsing the retrieved value before assigning it to the field. This can be useful when the fields type does not implement the `FromStr` trait. Requires `arg_type` to be set. Cannot be used together with `try_parse_fn`.                                                                                                                                                                                                                                                                           |
-//! | `try_parse_fn` | None       | Similar to `parse_fn` except it can fail. Useful if the parse function cannot always succeed, e.g., parsing a string to an UUID. Requires `arg_type` to be set. Cannot be used together with `parse_fn`.                                                                                                                                                                                                                                                                                                                              |
-//! | `arg_type`     | None       | Specify the argument type which the `parse_fn` function requires. As I don't know if it is possible to find the type automatically this argument is required such that the environment variable value can be parsed into the expected type first before being set as the argument in the function call.                                                                                                                                                                                                                               |
-//! | `validate_fn`  | None       | Set a custom validation function for ensuring the loaded value meets expectations. Note `validate_fn` supports both direct assignment and parentheses assignments. See [example](#validating-a-loaded-value)                                                                                                                                                                                                                                                                                                                          |
-//! | `delimiter`    | Comma (,)  | Used when parsing environment variable which is a stringified map or set. The delimiter specifies the boundary between values.                                                                                                                                                                                                                                                                                                                                                                                                        |
-//! | `no_prefix`    | False      | Disable adding the global prefix to this environment variable. This will also remove the delimiter that wouldn't normally be between the environment variable and prefix                                                                                                                                                                                                                                                                                                                                                              |
-//! | `no_suffix`    | False      | Disable adding the global suffix to this environment variable. This will also remove the delimiter that wouldn't normally be between the environment variable and suffix                                                                                                                                                                                                                                                                                                                                                              |
-//! | `nested`       | False      | Indicate that the field is a struct. Required when the field type is another struct                                                                                                                                                                                                                                                                                                                                                                                                                                                   |
-//! | `ignore`       | False      | Indicate that the derive macro should ignore this field when parsing. Note that this only works on optional fields.                                                                                                                                                                                                                                                                                                                                                                                                                   |
+//! | Attribute          | Default        | Description                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                |
+//! | ------------------ | -------------- | ---------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+//! | `env`              | field name     | Environment variable name to load the field value from. Can be chained multiple times to allow for fallbacks. The macro follows a first come, first serve basis meaning it attempts to load the variables in the order they are listed. Once an value is found it will try to parse it into the specified type. If it fails it will return an error and wont try the remaining ones in the list. The same applies to optional fields, which are only `None` if none of the variables are set. The container attribute `env_priority = "last"` reverses the order so the last variable which is set is used instead. Optionally, you can supply your own parsing function. See `parse_fn` for more information! A name may contain placeholders filled with the value of another field, e.g., `env = "SERVICE_{region}_ENDPOINT"` reads `SERVICE_EU_ENDPOINT` if `region` is `EU`. The referenced fields are loaded first, in dependency order, and their values are inserted as is after the prefix, suffix and case are applied to the rest of the name. A placeholder must name a non-optional field which is not `sensitive`, `nested` or `split_into`, and fields cannot refer back to themselves, directly or through other fields. Templated names are not listed by `env_keys`.                     |
+//! | `alias`            | None           | Additional environment variable name to load the field value from. Can be chained multiple times. Aliases are checked after the names given with `env`, or the field name if none are given, and behave like them otherwise, e.g., `#[fill(env = "DATABASE_URL", alias = "DB_URL")]`. Use it to accept alternative names while keeping `env` as the canonical one, which is used by e.g. `env_line`. Cannot be used on nested or ignored fields.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                           |
+//! | `file`             | None           | Path of a file to read the value from if none of the environment variables are set, e.g., `#[fill(env = "DB_PASSWORD", file = "/run/secrets/db_password")]` for secrets mounted by Docker or Kubernetes. The contents are trimmed and parsed like the value of the variable. A missing file is treated like an unset variable, so a `default` is used if given, while a file which cannot be read is an error.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                             |
+//! | `deprecated_env`   | None           | Deprecated environment variable name to load the field value from. Can be chained multiple times. Deprecated names are only checked after the names given by `env`, and loading a value from one of them emits a warning pointing to the first `env` name. If both a current and a deprecated name are set, the current one is used without a warning. Warnings are logged with `log::warn!` or `tracing::warn!` if the `log` or `tracing` feature is enabled and dropped otherwise, unless a handler is set with `envoke::set_warning_handler`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                           |
+//! | `deprecated`       | None           | Hint to show in the deprecation warning instead of pointing to the first `env` name, e.g., `deprecated = "use DATABASE_URL instead"`. Requires `deprecated_env`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                           |
+//! | `default`          | None           | Use the default value if the environment variable is not found. Optionally to statically assign a value to the field `env` can be omitted. A default function can take `env_name` or `env_names` as an argument to receive the first or all environment variable names the field is loaded from, with prefix and suffix applied, e.g., `default = fallback(env_name)`. The default is only evaluated if no value could be loaded, so a default function is never called when the variable is set and valid. For optional fields the default is only used if none of the variables are set, in which case the field is `Some(default)`, while a value which is set but cannot be parsed is returned as an error. Without a default an optional field is `None` if none of the variables are set. A bare `default` uses `Default::default()` of the field type, so a collection, e.g., `HashMap<K, V>`, defaults to an empty one, while `Option<HashMap<K, V>>` defaults to `None`, use e.g. `default = HashMap::new()` for `Some` of an empty collection. A field without any attribute is loaded from the variable named after the field, while `#[fill(default)]` without `env` never reads a variable, so `#[fill(default)]` or `#[fill(default = None)]` on an optional field always sets it to `None`. |
+//! | `default_fn`       | None           | Use the value returned by the given function if the environment variable is not found, e.g., `default_fn = make_default`. The function receives an `envoke::DefaultCtx` with the name of the field and the environment variable names it is loaded from, with prefix and suffix applied, i.e., `fn(&DefaultCtx) -> T`. Like `default`, `env` can be omitted, in which case the names are empty. Cannot be used together with `default`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    |
+//! | `parse_fn`         | None           | Set a custom parsing function for parsing the retrieved value before assigning it to the field. This can be useful when the fields type does not implement the `FromStr` trait. Requires `arg_type` to be set. For optional fields `arg_type` can be the inner type, e.g., `arg_type = u64` on an `Option<Duration>` field, in which case the function takes and returns the inner type and is only called if the value is set. Cannot be used together with `try_parse_fn`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                               |
+//! | `try_parse_fn`     | None           | Similar to `parse_fn` except it can fail. Useful if the parse function cannot always succeed, e.g., parsing a string to an UUID. Like `parse_fn`, it takes the inner type on optional fields if `arg_type` is not optional. Requires `arg_type` to be set. Cannot be used together with `parse_fn`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        |
+//! | `split_into`       | None           | Assign the elements of the tuple returned by `parse_fn` or `try_parse_fn` to the listed fields in order, e.g., `#[fill(env = "HOSTPORT", split_into(host, port), parse_fn = parse_hostport, arg_type = String)]` on `host` loads `HOSTPORT=localhost:80` into both `host` and `port`. The list must include the field it is set on and the other fields cannot have attributes of their own. A tuple of the wrong length or element types fails to compile. These fields are not read from `config_json`. Cannot be used together with `default`, `into`, `rename_keys`, `nested`, or `ignore`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            |
+//! | `arg_type`         | None           | Specify the argument type which the `parse_fn` function requires, or which the value is converted from with `into`. As I don't know if it is possible to find the type automatically this argument is required such that the environment variable value can be parsed into the expected type first before being set as the argument in the function call. Cannot be used on its own, one of `parse_fn`, `try_parse_fn`, or `into` must be set.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                             |
+//! | `into`             | False          | Parse the value as `arg_type` and convert it into the field type with `Into::into`, e.g., `#[fill(env, arg_type = u16, into)]` on a `Port` field implementing `From<u16>`. The conversion is a single `into` call, so multiple steps only compose if the field type implements `From` for anything reaching it, e.g., `impl<T: Into<Port>> From<T> for Endpoint`. For optional fields `arg_type` can be either the inner type or optional too, e.g., `u16` or `Option<u16>`. Requires `arg_type` to be set. Cannot be used together with `parse_fn` or `try_parse_fn`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                     |
+//! | `deserialize_with` | None           | Deserialize the value with a function of the same signature as serde's `deserialize_with`, i.e., `fn<'de, D: Deserializer<'de>>(D) -> Result<T, D::Error>`, so the same function can be shared with a serde config struct. The value is passed through a string deserializer, so the function must deserialize a string first, e.g., with `String::deserialize`. Requires the `serde` feature. Cannot be used together with `parse_fn`, `try_parse_fn`, `into`, or `item_parse_fn`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        |
+//! | `validate_fn`      | None           | Set a custom validation function for ensuring the loaded value meets expectations. Note `validate_fn` supports both direct assignment and parentheses assignments. On `nested` fields only `after` is supported and the function receives the loaded struct. See [example](#validating-a-loaded-value)                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                     |
+//! | `one_of`           | None           | Only accept a value equal to one of the listed values, e.g., `one_of = ["dev", "staging", "prod"]`, otherwise a `ValidationError` listing the allowed values is returned. The comparison is done on the parsed value, not the raw string, so the values must be comparable with the field type, e.g., `one_of = [80, 443]` for integers or `one_of = [Mode::Dev]` for enums implementing `PartialEq`. Optional fields are only checked if set.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                             |
+//! | `multiple_of`      | None           | Only accept an integer which is a multiple of the given step, e.g., `multiple_of = 4096` for a buffer aligned to the page size, otherwise a `ValidationError` is returned. Checked after parsing and before `validate_fn`, so it can be combined with `one_of` and `validate_fn`. Can only be used on integer fields loaded from environment variables.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    |
+//! | `delimiter`        | Comma (,)      | Used when parsing environment variable which is a stringified map or set. The delimiter specifies the boundary between values.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                             |
+//! | `kv_delimiter`     | Equal sign (=) | Used when parsing environment variable which is a stringified map. The delimiter specifies the boundary between the key and value of each entry, e.g., `k1:v1;k2:v2` with `delimiter = ";"` and `kv_delimiter = ":"`. Cannot be the same as `delimiter`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                   |
+//! | `rename_keys`      | None           | Convert the keys of a map to the given case after parsing, e.g., `rename_keys = "kebab-case"` loads `Content_Type=json` as `content-type`. Supports the same cases as the container attribute `rename_all`. Only works on maps with `String` keys.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                         |
+//! | `case`             | None           | Rename the environment variables of this field to a different naming case than the container attribute `rename_all`, e.g., `case = "kebab-case"` for a single legacy name. Supports the same cases as `rename_all`. Like `rename_all`, the case is applied after the prefix and suffix are added, so it also applies to them unless they are left out with `no_prefix` or `no_suffix`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                     |
+//! | `keep_empty`       | False          | Keep empty elements when parsing a sequence, e.g., `a,,c` is parsed into `["a", "", "c"]`. Empty elements are parsed like any other value, so the element type must accept an empty string. Without it empty elements result in an error. Cannot be used on maps.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                          |
+//! | `enumerate`        | False          | Split the value into a list and collect it into a map keyed by the position of each element, e.g., `SERVERS=a,b,c` is loaded into `{0: "a", 1: "b", 2: "c"}`. The field must be a map with `usize` keys, e.g., `HashMap<usize, T>` or `BTreeMap<usize, T>`. Elements are parsed like those of a sequence, so `delimiter`, `keep_empty`, and `item_parse_fn` apply. Cannot be used together with `kv_delimiter`, `rename_keys`, or a custom parse function.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                 |
+//! | `no_prefix`        | False          | Disable adding the global prefix to this environment variable. This will also remove the delimiter that wouldn't normally be between the environment variable and prefix                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                   |
+//! | `no_suffix`        | False          | Disable adding the global suffix to this environment variable. This will also remove the delimiter that wouldn't normally be between the environment variable and suffix                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                   |
+//! | `prefix`           | None           | Use this prefix for the environment variable instead of the global prefix, e.g., to load a few fields with a different prefix. The delimiter is added as usual. Cannot be used together with `no_prefix`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                  |
+//! | `suffix`           | None           | Use this suffix for the environment variable instead of the global suffix. The delimiter is added as usual. Cannot be used together with `no_suffix`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                      |
+//! | `nested`           | False          | Indicate that the field is a struct. Required when the field type is another struct. If the field is an `Option`, it is `None` when none of the nested struct's environment variables, as listed by `Envoke::env_keys`, are set in the process environment. Otherwise it is loaded as usual, so all required variables must be set and parse errors are returned.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                          |
+//! | `ignore`           | False          | Indicate that the derive macro should ignore this field when parsing. Optional fields are set to `None`. Non-optional fields must be given a `default`, e.g., `#[fill(ignore, default)]`, which they are set to, e.g., for a field filled in by later code.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                |
+//! | `sensitive`        | False          | Mark the field as containing sensitive data, e.g., passwords or tokens. The value is replaced with `"***"` in `to_json_redacted` and with `"[redacted]"` in parse errors, so it does not end up in logs. For `nested` fields loaded with `pattern` or `block` the parse errors of the nested type's fields are redacted as well.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                           |
+//! | `expand_path`      | False          | Expand a leading `~` to the home directory and `$VAR`/`${VAR}` references to their values in the process environment before converting the value into the field type, e.g., `PathBuf`. The field type must implement `From<String>`. Referencing a variable which is not set results in an error. Cannot be used together with `parse_fn` or `try_parse_fn`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                               |
+//! | `group_separators` | None           | Strip digit group separators from the value before parsing it, e.g., `1_000_000`. `_` is always stripped and additional separators can be given, e.g., `group_separators = ","` to allow `1,000,000`. Only works on integer fields. Cannot be used together with `parse_fn` or `try_parse_fn`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                             |
+//! | `decimal_comma`    | False          | Accept `,` as the decimal separator of a float, e.g., `1,5` is parsed as `1.5`. The comma is replaced by `.` and the value is parsed by `FromStr`, so `1.5` is accepted too, as are `NaN`, `inf`, `-infinity`, and scientific notation, e.g., `1,5e3`. Thousands separators are not supported, e.g., `1.000,5` is an error. Only works on `f32` and `f64` fields. Cannot be used together with `parse_fn` or `try_parse_fn`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                               |
+//! | `unique`           | False          | Fail with a `ValidationError` if a sequence or set contains the same element more than once, e.g., `a,a,b`, instead of keeping the duplicate or silently dropping it from a set. Elements are compared after parsing, so the element type must implement `PartialEq`. Cannot be used on maps.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                              |
+//! | `url`              | None           | Validate the scheme of a `url::Url` field after parsing it with `FromStr`, e.g., `url(schemes = ["http", "https"])`. A URL with any other scheme fails with a `ValidationError`. `Url` lowercases the scheme, so the allowed schemes must be lowercase. Requires the `url` feature.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        |
+//! | `trim_prefix`      | None           | Strip the given prefix from the value before parsing it, e.g., `#[fill(env, trim_prefix = "Bearer ")]` loads `xyz` from `Bearer xyz`. A value without the prefix is parsed as is. For sequences each element is stripped, and for maps each value. Cannot be used together with `parse_fn`, `try_parse_fn`, or `into`, strip the value in the parse function instead.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                      |
+//! | `trim_suffix`      | None           | Strip the given suffix from the value before parsing it, e.g., `trim_suffix = "\""` together with `trim_prefix = "\""` loads a quoted path without its quotes. A value without the suffix is parsed as is. Applies to collections and conflicts like `trim_prefix`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        |
+//! | `ranges`           | None           | Parse each element of a sequence as either a single value or an inclusive range written as `start-end`, e.g., `PORTS=80,443,8000-8100`. With `ranges` or `ranges = "keep"` the field must be a sequence of ranges, e.g., `Vec<RangeInclusive<u16>>`, where a single value is a range of one. With `ranges = "flatten"` the field must be a sequence of integers, e.g., `Vec<u16>`, and each range is expanded into the values it contains. Ranges are kept in the order they are listed. Malformed elements and ranges where the start is greater than the end are returned as errors.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                     |
+//! | `radix_auto`       | False          | Parse an integer in the radix given by its prefix, i.e., `0x` for hexadecimal, `0o` for octal, `0b` for binary, and none for decimal, e.g., `0xFF` or `-0b1010`. Underscores are ignored, e.g., `1_000_000`. A value which does not fit in the type results in an overflow error instead of the usual parse error. Can only be used on integer fields and not together with `group_separators`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            |
+//! | `duration`         | False          | Parse the value as a `Duration` made up of one or more amounts with a unit, e.g., `30s` or `1h 30m`. Supported units are `ns`, `us`, `ms`, `s`, `m`, `h`, `d`, and `w`. For sequences and maps each element is parsed as a duration, e.g., `30s,5m,1h`, and an invalid element is reported with its index. With the `time` or `chrono` feature, `time::Duration` and `chrono::Duration` fields are supported too. These must be written with their crate name, e.g., `time::Duration`, since the derive recognizes the type by its path and a bare `Duration` is treated as `std::time::Duration`. Cannot be used together with `parse_fn`, `try_parse_fn`, or `item_parse_fn`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            |
+//! | `datetime`         | False          | Parse the value as an RFC 3339 date-time, e.g., `2024-05-01T12:00:00Z` or `2024-05-01T14:00:00.5+02:00`, instead of using `FromStr`. The offset is required, a date-time without one is a parse error. With the `chrono` feature `chrono::DateTime` fields are supported, where `DateTime<FixedOffset>` keeps the given offset and other time zones, e.g., `DateTime<Utc>`, are converted to it. With the `time` feature `time::OffsetDateTime` fields, which keep the given offset, and `time::UtcDateTime` fields, which are converted to UTC, are supported. In `env_line` and `diff` the value is formatted as RFC 3339. Cannot be used together with `duration` or a custom parse function.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                           |
+//! | `unix_timestamp`   | None           | Parse the value as a unix timestamp into a `std::time::SystemTime` field, counting seconds since the unix epoch, e.g., `1714564800`, or milliseconds with `unix_timestamp = "millis"`. Negative timestamps and timestamps too large for a `SystemTime` are parse errors. The value is written back as a timestamp by `env_line` and `diff`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                |
+//! | `enum_by`          | `name`         | How an enum value is represented. `name` uses the enum's `FromStr` implementation and `name_or_index` falls back to the index of the variant if that fails, e.g., both `MODE=production` and `MODE=1` are accepted. The index is into `strum::VariantNames::VARIANTS`, so the enum must implement it. For sequences and maps each element is parsed this way. Cannot be used together with `parse_fn`, `try_parse_fn`, `item_parse_fn`, or `duration`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                     |
+//! | `prefer_dotenv`    | False          | Use the value from the container's `dotenv` file over the one in the process environment if both are set, e.g., for a local override. Other fields are unaffected. Requires the container attribute `dotenv`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                              |
+//! | `lazy_parse`       | False          | Defer parsing the value until it is first accessed, so a malformed value does not prevent the struct from loading unless it is used. The field type must be `envoke::Lazy<T>` and the value is parsed with `Lazy::get`, which returns `Result<&T>`. A missing environment variable is still an error when loading. Cannot be used together with `default` or any attribute which parses the value.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                         |
+//! | `present`          | False          | Set a `bool` field to whether any of its environment variables are set, no matter their value, e.g., `#[fill(env = "VERBOSE", present)]` is `true` for both `VERBOSE=1` and `VERBOSE=`, and `false` if `VERBOSE` is not set. The value is never parsed. In `env_line` a set field is formatted as an empty value and an unset one returns `None`. Cannot be used together with `default`, `file`, a custom parse function, `validate_fn`, or `one_of`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                     |
+//! | `flag`             | False          | Parse a `bool` or `Option<bool>` field as a flag, where a variable which is set but empty means `true` and any other value is parsed as a `bool`, e.g., `FEATURE=` and `FEATURE=true` are `true` and `FEATURE=false` is `false`. For an `Option<bool>` field an unset variable is `None`, so the three states unset, enabled, and disabled can be told apart. Cannot be used together with `present` or a custom parse function.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                           |
+//! | `parser`           | None           | Parse the value with the parser registered under the given key in the `ParserRegistry` passed to `try_envoke_with_registry`, e.g., `#[fill(env, parser = "codec")]`, so parsers can be added at runtime, e.g., by plugins. The parser must return the field type, or the inner type for optional fields. Loading with any other method, or without a matching parser registered, returns a parse error. Cannot be used together with `arg_type` or any other way of parsing the value.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                     |
+//! | `rest`             | False          | Collect the variables starting with the container `prefix`, followed by `delimiter` if set, which are not read by any other field into a map keyed by the rest of their name, e.g., `APP_COLOR=red` is collected as `COLOR` into a `HashMap<String, String>`. Variables read by typed fields, including those of nested fields, are never collected, and a variable whose key is itself read by another field, e.g., `APP_HOST` collected as `HOST` next to a field loaded from `HOST`, is a `ValidationError`. Variables in the dotenv file are not collected. Requires `prefix` to be set and only one field can be marked `rest`. Cannot be used together with the container attribute `deny_unknown`, or with `env`, `default`, `nested`, `ignore`, or any other way of loading the value.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                             |
+//! | `dispatch`         | None           | Load a boxed trait object from one of several types implementing `Fill`, selected by the value of `env`, e.g., `#[fill(env = "STORAGE", dispatch(disk = DiskStorage, s3 = S3Storage))]` on a `Box<dyn Storage>` loads `DiskStorage` if `STORAGE=disk`. Any other value returns a validation error listing the allowed values. The field is skipped by `diff` and `env_line`. Cannot be used together with `default`, `nested`, `ignore`, or any attribute which parses the value.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                          |
+//! | `item_parse_fn`    | None           | Set a custom parsing function applied to each element of a map or set, e.g., `Vec<Duration>`. For maps it is applied to the values. Requires `item_arg_type` to be set. Cannot be used together with `parse_fn` or `try_parse_fn`.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                         |
+//! | `item_arg_type`    | None           | Specify the argument type which the `item_parse_fn` function requires. Each element is parsed into this type before being passed to the function.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                          |
+//! | `pattern`          | None           | Match the value of `env` against a regex with named capture groups and assign each capture to the `nested` struct's `env` field with the same name. Captures are parsed like environment variables, and missing optional captures become `None` or use the field's default. Requires `nested` and `env` to be set as well as the `regex` feature.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                          |
+//! | `block`            | False          | Load the `nested` struct from the value of `env` as if it was a dotenv file, i.e., one `KEY=VALUE` pair per line, e.g., for passing a whole config section through a single environment variable. Empty lines and comments are skipped. Keys which are not read by the nested struct and missing required fields result in an error.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                       |
+//! | `map_prefix`       | None           | Load a map of `nested` structs, e.g., `HashMap<String, DbConfig>`, from the variables named `{map_prefix}{key}_{name}`, where `name` is a variable read by the nested struct, e.g., `DB_main_HOST` with `map_prefix = "DB_"`. The keys are discovered from the variables set in the process environment, each key is included once, and the nested struct is loaded once per key with the variables of that key. If several names match the longest one is used. The prefix is used as is, without the container prefix.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                   |
+//! | `global`           | False          | Read the field's environment variables outside of any runtime prefix scope, e.g., a shared `REGION` in a struct loaded through `map_prefix` is read as `REGION` instead of `DB_main_REGION`. The container prefix is still added at compile time, use `no_prefix` as well to read the bare name. Has no effect outside a runtime prefix scope.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                             |
+//! | `conflicts_with`   | None           | Name of another field which cannot be set at the same time as this field. Can be chained multiple times. If an environment variable of both fields is found a validation error naming both fields is returned. Both fields must be loaded from the environment.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            |
+//! | `required_if`      | None           | Require the field to be set in the environment if another field equals a value, e.g., `required_if(field = "auth_mode", equals = "oauth")`. The value can be any expression which the other field can be compared to with `==`. If the condition holds and none of the field's environment variables are found a validation error is returned. Only works on optional fields.                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                              |
 //!
 //! </br>
 //!
@@ -74,14 +140,19 @@
 //! Below are the current implemented container attributes. This list will be
 //! updated as more are added or changed.
 //!
-//! | Attribute    | Default        | Description                                                                                                                                                                                                                                                                                                                                                                                                                                |
-//! | ------------ | -------------- | ------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------ |
-//! | `env`        | container name | Environment variable name to load the field value from. Can be chained multiple times to allow for fallbacks. The macro follows a first come, first serve basis meaning it attempts to load the variables in the order they are listed. Once an value is found it will try to parse it into the specified type. If it fails it will return an error and wont try the remaining ones in the list. This behavior might change in the future. |
-//! | `prefix`     | None           | Set a custom prefix which will be prepended infront of environment variables before fetching                                                                                                                                                                                                                                                                                                                                               |
-//! | `suffix`     | None           | Set a custom prefix which will be appended infront of environment variables before fetching                                                                                                                                                                                                                                                                                                                                                |
-//! | `delimiter`  | None           | Set a customer delimiter used for separated prefix, environment variable, and suffix. **NB!** If you are using the `rename_all` attribute as well it will take priority over the delimiter. It can still be useful to include the delimiter to ensure the prefix, environment variable, and suffix are separated before renaming occurs otherwise they will be interpreted as a single word!                                               |
-//! | `rename_all` | None           | Rename all environment variables to a different naming case. See [name cases](#name-cases) for a full list and description of the different options.                                                                                                                                                                                                                                                                                       |
-//! | `dotenv`     | None           | Set a dotenv file to use when loading environment variables into structs/enums. Note that environment variables in the process's environment have a higher priority than those found in the dotenv file.                                                                                                                                                                                                                                   |
+//! | Attribute          | Default        | Description                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                      |
+//! | ------------------ | -------------- | ------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------ |
+//! | `env`              | container name | Environment variable name to load the field value from. Can be chained multiple times to allow for fallbacks. The macro follows a first come, first serve basis meaning it attempts to load the variables in the order they are listed. Once an value is found it will try to parse it into the specified type. If it fails it will return an error and wont try the remaining ones in the list. This behavior might change in the future. Use `env(name = "...", no_prefix, no_suffix)` to disable the global prefix or suffix for a single name, e.g., for a legacy fallback.                  |
+//! | `prefix`           | None           | Set a custom prefix which will be prepended infront of environment variables before fetching                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                     |
+//! | `suffix`           | None           | Set a custom prefix which will be appended infront of environment variables before fetching                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                      |
+//! | `delimiter`        | Empty          | Set a custom delimiter used to separate the prefix, environment variable, and suffix. Without it they are joined as is, e.g., `prefix = "APP"` loads `APPport`, so either include the separator in the prefix, e.g., `prefix = "APP_"`, or set `delimiter = "_"`. **NB!** If you are using the `rename_all` attribute as well it will take priority over the delimiter. It can still be useful to include the delimiter to ensure the prefix, environment variable, and suffix are separated before renaming occurs otherwise they will be interpreted as a single word!                         |
+//! | `rename_all`       | None           | Rename all environment variables to a different naming case. See [name cases](#name-cases) for a full list and description of the different options.                                                                                                                                                                                                                                                                                                                                                                                                                                             |
+//! | `dotenv`           | None           | Set a dotenv file to use when loading environment variables into structs/enums. Note that environment variables in the process's environment have a higher priority than those found in the dotenv file.                                                                                                                                                                                                                                                                                                                                                                                         |
+//! | `json`             | False          | Generate `to_json_redacted` and `to_json_value_redacted` methods for the enum. Unit variants are serialized as the name they are loaded with and other variants use the inner type's method. Requires the `serde` feature.                                                                                                                                                                                                                                                                                                                                                                       |
+//! | `env_line`         | False          | Generate an `env_value()` method which returns the canonical name of the loaded variant, i.e., its `rename` or variant name with prefix and suffix applied, never the alias it was loaded with, and an `env_line(field)` method which returns the `KEY=value` line of the enum if `field` is its first environment variable name, e.g., `MODE=production` for a variant loaded from `MODE=prod`. Other names are looked up in the struct of the loaded variant, which must then also have this attribute. Exporting the canonical name keeps round-tripped configuration free of legacy aliases. |
+//! | `parse_fn`         | None           | Set a function transforming the loaded value before it is matched against the variant names, e.g., to lowercase it or map legacy codes. Takes and returns a `String`, i.e., `fn(String) -> String`. Cannot be used together with `try_parse_fn`.                                                                                                                                                                                                                                                                                                                                                 |
+//! | `try_parse_fn`     | None           | Similar to `parse_fn` except it can fail, i.e., `fn(String) -> Result<String, E>` where `E` converts into a boxed error. An error is returned as a parse error of the enum even if a variant is marked `default`. Cannot be used together with `parse_fn`.                                                                                                                                                                                                                                                                                                                                       |
+//! | `case_insensitive` | False          | Match the loaded value against the variant names ignoring ASCII case, e.g., `production` matches a variant named `PRODUCTION`. Names are still renamed by `rename_all` first, so it only changes how words are joined, e.g., `prod_eu` does not match `prod-eu`. Names which only differ in case are rejected as duplicates. `env_value` still returns the renamed name.                                                                                                                                                                                                                         |
 //!
 //! </br>
 //!
@@ -90,13 +161,70 @@
 //! Below are the current implemented variant attributes. This list will be
 //! updated as more are added or changed.
 //!
-//! | Attribute   | Default | Description                                                                                                                                                                                             |
-//! | ----------- | ------- | ------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
-//! | `rename`    | None    | Rename the name of the field. This overwrites the default field name and as such this name will be used instead. If you want extra names to match on in addition to the field name use `alias` instead. |
-//! | `alias`     | None    | Additional names, including the field name, to match on.                                                                                                                                                |
-//! | `no_prefix` | False   | Disable adding the global prefix to this environment variable. This will also remove the delimiter that wouldn't normally be between the environment variable and prefix                                |
-//! | `no_suffix` | False   | Disable adding the global suffix to this environment variable. This will also remove the delimiter that wouldn't normally be between the environment variable and suffix                                |
-//! | `default`   | False   | Set this as the default variant to load if none of the names matches the container value                                                                                                                |
+//! | Attribute              | Default | Description                                                                                                                                                                                                                                                                                                                                                                                |
+//! | ---------------------- | ------- | ------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------ |
+//! | `rename`               | None    | Rename the name of the field. This overwrites the default field name and as such this name will be used instead. If you want extra names to match on in addition to the field name use `alias` instead.                                                                                                                                                                                    |
+//! | `alias`                | None    | Additional names, including the field name, to match on.                                                                                                                                                                                                                                                                                                                                   |
+//! | `no_prefix`            | False   | Disable adding the global prefix to this environment variable. This will also remove the delimiter that wouldn't normally be between the environment variable and prefix                                                                                                                                                                                                                   |
+//! | `no_suffix`            | False   | Disable adding the global suffix to this environment variable. This will also remove the delimiter that wouldn't normally be between the environment variable and suffix                                                                                                                                                                                                                   |
+//! | `default`              | False   | Set this as the default variant to load if none of the names matches the container value                                                                                                                                                                                                                                                                                                   |
+//! | `matches_prefix`       | None    | Also select this variant if the value starts with the given prefix, e.g., `matches_prefix = "postgres://"`.                                                                                                                                                                                                                                                                                |
+//! | `remainder_into_inner` | False   | Parse the type held by the variant from the rest of the value after `matches_prefix` with its `FromStr` implementation instead of loading it from the environment, e.g., `Postgres(PgUrl)` is parsed from `localhost/db` if the value is `postgres://localhost/db`. The variant is then only selected by its prefix. Requires `matches_prefix` and cannot be used together with `default`. |
+//!
+//! </br>
+//!
+//! ### Types
+//!
+//! Fields are parsed with their type's `FromStr` implementation, sequences
+//! and maps are parsed from delimited values, e.g., `a,b,c` and `a=1,b=2`.
+//! This means types from other crates work without a feature as long as
+//! they implement `FromStr`, e.g., `camino::Utf8PathBuf`, which also
+//! supports `expand_path`. Likewise, any collection implementing
+//! `FromIterator` can be used, such as `VecDeque<T>`, `LinkedList<T>` or
+//! `BinaryHeap<T>`, and sequences keep the order of the elements in the
+//! value. Below are the types which are parsed differently.
+//!
+//! | Type                                                                           | Format                 | Description                                                                                                                                                                                                                           |
+//! | ------------------------------------------------------------------------------ | ---------------------- | ------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+//! | `(Bound<T>, Bound<T>)`                                                         | `[0,100)`              | Interval notation where `[`/`]` are inclusive and `(`/`)` are exclusive bounds. A side without a value is unbounded, e.g., `(,100]`.                                                                                                  |
+//! | `IpAddr`, `Ipv4Addr`, `Ipv6Addr`, `SocketAddr`, `SocketAddrV4`, `SocketAddrV6` | `10.0.0.1`, `[::1]:80` | Parsed with `FromStr` from `std::net`, but an invalid address reports why it is invalid, also for each element of a sequence, e.g., `element at index 1 is invalid: `bad` is not a valid network address: invalid IP address syntax`. |
+//! | `Saturating<T>`, `Wrapping<T>`                                                 | `42`                   | Parsed as `T` and wrapped, also for each element of a sequence or each value of a map, e.g., `Vec<Saturating<u32>>`. The value must still fit in `T`, only arithmetic on the loaded value saturates or wraps.                         |
+//!
+//! Any of the above can be wrapped in an `Option` and used as the elements of
+//! a sequence or map, e.g., `Option<Vec<T>>` or `HashMap<K, V>`. The
+//! elements are parsed one at a time, so a field such as `Option<Option<T>>`,
+//! `Vec<Option<T>>` or `Vec<Vec<T>>` is rejected at compile time unless it
+//! is parsed by `parse_fn`, `try_parse_fn` or `item_parse_fn`.
+//!
+//! `PhantomData<T>` fields without any attributes are not loaded, they are
+//! set to `PhantomData` as if marked `ignore`, so generic structs can carry a
+//! marker without an attribute.
+//!
+//! A `Result<T, E>` field marked `env` is loaded as `T`, but an error from
+//! reading or parsing the variable is stored in the field instead of failing
+//! the whole struct. The error is converted with `From<envoke::Error>`, or
+//! holds the error message if `E` is `String`.
+//!
+//! </br>
+//!
+//! ### Features
+//!
+//! Below are the optional features which can be enabled to support additional
+//! types. They are all disabled by default.
+//!
+//! | Feature           | Description                                                                                                                                                                                                                                                                                                                                                                                            |
+//! | ----------------- | ------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------ |
+//! | `arrayvec`        | Parse `arrayvec::ArrayVec<T, CAP>` fields like other sequences, e.g., `a,b,c`. More than `CAP` elements result in a parse error instead of a panic.                                                                                                                                                                                                                                                    |
+//! | `bytes`           | Parse `bytes::Bytes` fields from the UTF-8 bytes of the value. The type must be written with its crate path, a bare `Bytes` is parsed through `FromStr`.                                                                                                                                                                                                                                               |
+//! | `chrono`          | Parse `chrono::Duration` fields with the `duration` attribute and `chrono::DateTime` fields with the `datetime` attribute.                                                                                                                                                                                                                                                                             |
+//! | `glob`            | Parse `glob::Pattern` fields with `Pattern::new`. The type must be written with its crate path, a bare `Pattern` is parsed through `FromStr`. Invalid patterns are reported as a parse error containing the pattern syntax error instead of a generic unexpected value error.                                                                                                                          |
+//! | `log`             | Log warnings, e.g., about deprecated environment variables, with `log::warn!`. Without it or `tracing`, warnings are dropped unless a handler is set with `envoke::set_warning_handler`.                                                                                                                                                                                                               |
+//! | `secrecy`         | Parse `secrecy::SecretString` fields so the loaded value is only accessible through `ExposeSecret`. The type must be written with its crate path, a bare `SecretString` is parsed through `FromStr`.                                                                                                                                                                                                   |
+//! | `serde`           | Enables the `json` container attribute and the `deserialize_with` field attribute.                                                                                                                                                                                                                                                                                                                     |
+//! | `system-defaults` | Enables the `default = cpus` and `default = cpus_times(n)` defaults, which resolve to the number of CPUs available to the process, or that number multiplied by `n`, e.g., `#[fill(env = "WORKERS", default = cpus_times(2))]`. They take the place of any function with the same name.                                                                                                                |
+//! | `time`            | Parse `time::Duration` fields with the `duration` attribute and `time::OffsetDateTime` and `time::UtcDateTime` fields with the `datetime` attribute.                                                                                                                                                                                                                                                   |
+//! | `tracing`         | Instrument `try_envoke` with `tracing`, i.e., a `try_envoke` span with the struct name and a debug event per field with the key it is loaded from and whether its default is used. Field values are never recorded, so `sensitive` fields stay out of the logs. Warnings, e.g., about deprecated environment variables, are emitted with `tracing::warn!` unless the `log` feature is enabled as well. |
+//! | `url`             | Enables the `url` field attribute validating the scheme of `url::Url` fields.                                                                                                                                                                                                                                                                                                                          |
 //!
 //! </br>
 //!
@@ -116,21 +244,101 @@
 //! conditions. </sub>
 
 mod errors;
+mod lazy;
 mod load;
 mod load_opt;
+mod registry;
 mod utils;
 
 #[doc(hidden)]
-pub use errors::{EnumError, Error, ParseError, Result, RetrieveError, ValidationError};
+pub use errors::{
+    EnumError, Error, ErrorCategory, Errors, ParseError, Result, RetrieveError, ValidationError,
+};
+
+pub use lazy::Lazy;
+
+pub use registry::ParserRegistry;
+
+pub use utils::{DefaultCtx, FieldDiff};
 
 #[doc(hidden)]
-pub use load::{Envloader, FromMap, FromSet};
+pub use lazy::parse_lazy;
 
 #[doc(hidden)]
-pub use load_opt::{FromMapOpt, FromSetOpt, OptEnvloader};
+pub use registry::parse_registered;
 
 #[doc(hidden)]
-pub use utils::load_dotenv;
+pub use load::{Envloader, FromMap, FromMapWith, FromSet, FromSetWith};
+
+#[doc(hidden)]
+pub use load_opt::{FromMapOpt, FromMapOptWith, FromSetOpt, FromSetOptWith, OptEnvloader};
+
+#[doc(hidden)]
+pub use utils::{
+    collect_enumerated, deny_duplicates, deny_removed, deny_unknown, expand_path, find_source,
+    flatten_ranges, format_unix_timestamp, load_block, load_dotenv, load_file, load_global,
+    load_nested_map, load_rest, parse_addr, parse_decimal_comma, parse_duration, parse_element,
+    parse_flag, parse_grouped, parse_interval, parse_name_or_index, parse_radix, parse_range,
+    parse_str, parse_unix_timestamp, trim_affixes, warn_deprecated, with_fuzzy_names, with_source,
+    FromStrRadix,
+};
+
+pub use utils::set_warning_handler;
+
+#[cfg(feature = "arrayvec")]
+#[doc(hidden)]
+pub use utils::collect_array_vec;
+
+#[cfg(feature = "bytes")]
+#[doc(hidden)]
+pub use utils::parse_bytes;
+
+#[cfg(feature = "chrono")]
+#[doc(hidden)]
+pub use utils::{format_chrono_datetime, parse_chrono_datetime, parse_chrono_duration};
+
+#[cfg(feature = "glob")]
+#[doc(hidden)]
+pub use utils::parse_glob;
+
+#[cfg(feature = "regex")]
+#[doc(hidden)]
+pub use utils::{match_captures, FromCaptures};
+
+#[cfg(feature = "schema")]
+#[doc(hidden)]
+pub use utils::{validate_schema, SchemaCache};
+
+#[cfg(feature = "secrecy")]
+#[doc(hidden)]
+pub use utils::parse_secret;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde_json;
+
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing;
+
+#[cfg(feature = "url")]
+#[doc(hidden)]
+pub use utils::check_url_scheme;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use utils::deserialize_with;
+
+#[doc(hidden)]
+pub use convert_case;
+
+#[cfg(feature = "system-defaults")]
+#[doc(hidden)]
+pub use utils::{cpus, cpus_times};
+
+#[cfg(feature = "time")]
+#[doc(hidden)]
+pub use utils::{format_time_datetime, parse_time_datetime, parse_time_duration};
 
 #[doc(hidden)]
 pub use envoke_derive::Fill;
@@ -149,10 +357,10 @@ pub trait Envoke: Sized {
     ///
     /// # Examples
     ///
-    /// ```
-    /// use envload::Envoke;
+    /// ```no_run
+    /// use envoke::{Envoke, Fill};
     ///
-    /// #[derive(Envoke)]
+    /// #[derive(Fill)]
     /// struct Config {
     ///     #[fill(env = "TEST_ENV")]
     ///     key: String,
@@ -176,10 +384,10 @@ pub trait Envoke: Sized {
     ///
     /// # Examples
     ///
-    /// ```
-    /// use envload::Envoke;
+    /// ```no_run
+    /// use envoke::{Envoke, Fill};
     ///
-    /// #[derive(Envoke)]
+    /// #[derive(Fill)]
     /// struct Config {
     ///     #[fill(env = "TEST_ENV")]
     ///     key: String,
@@ -191,4 +399,139 @@ pub trait Envoke: Sized {
     /// }
     /// ```
     fn try_envoke() -> Result<Self>;
+
+    /// Attempts to create an instance of `Self` like [`Envoke::try_envoke`],
+    /// but keeps loading the remaining fields after a field fails, so the
+    /// errors of all fields are returned at once, e.g., to report every
+    /// invalid setting in a single response.
+    ///
+    /// Fields of `nested` structs are collected as well. Errors which are not
+    /// tied to a field, e.g., a missing dotenv file, and checks across fields,
+    /// e.g., `conflicts_with`, which only run once every field is loaded, end
+    /// the load as with [`Envoke::try_envoke`]. The values of fields used in
+    /// placeholders are needed to load other fields, so their errors end the
+    /// load as well.
+    ///
+    /// # Errors
+    /// Returns the errors of every field which cannot be loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use envoke::{Envoke, Fill};
+    ///
+    /// #[derive(Fill)]
+    /// struct Config {
+    ///     #[fill(env = "HOST")]
+    ///     host: String,
+    ///
+    ///     #[fill(env = "PORT")]
+    ///     port: u16,
+    /// }
+    ///
+    /// if let Err(errors) = Config::try_envoke_all() {
+    ///     for err in &errors {
+    ///         eprintln!("{:?} ({:?}): {err}", err.field(), err.category());
+    ///     }
+    /// }
+    /// ```
+    fn try_envoke_all() -> std::result::Result<Self, Errors> {
+        Self::try_envoke_collect(true)
+    }
+
+    /// Loads `Self`, collecting the errors of all fields if `collect` is set
+    /// or returning after the first one otherwise. Only derived structs
+    /// collect errors
+    #[doc(hidden)]
+    fn try_envoke_collect(_collect: bool) -> std::result::Result<Self, Errors> {
+        Self::try_envoke().map_err(Errors::from)
+    }
+
+    /// Returns the names of all environment variables read when loading
+    /// `Self`, including fallback and deprecated names and those of nested
+    /// fields.
+    ///
+    /// The names are resolved the same way as when loading, i.e., after
+    /// renaming and adding any prefix or suffix. No environment variables
+    /// are read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use envoke::{Envoke, Fill};
+    ///
+    /// #[derive(Fill)]
+    /// #[fill(prefix = "APP_", rename_all = "UPPERCASE")]
+    /// struct Config {
+    ///     #[fill(env, env = "FALLBACK")]
+    ///     key: String,
+    /// }
+    ///
+    /// assert_eq!(Config::env_keys(), vec!["APP_KEY", "APP_FALLBACK"]);
+    /// ```
+    fn env_keys() -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Attempts to create an instance of `Self` like [`Envoke::try_envoke`],
+    /// but with `overrides` taking precedence over the process environment,
+    /// e.g., command line arguments.
+    ///
+    /// Each override is of the form `KEY=VALUE` or `--KEY=VALUE`, where `KEY`
+    /// is the resolved environment variable name. Variables without an
+    /// override are read from the process environment and then the dotenv
+    /// file as usual.
+    ///
+    /// # Errors
+    /// Returns an error if an override is not of the form `KEY=VALUE`, or if
+    /// `Self` cannot be loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use envoke::{Envoke, Fill};
+    ///
+    /// #[derive(Fill)]
+    /// #[fill(rename_all = "UPPERCASE")]
+    /// struct Config {
+    ///     #[fill(env, default = 8080)]
+    ///     port: u16,
+    /// }
+    ///
+    /// let args: Vec<String> = std::env::args().skip(1).collect();
+    /// let config = Config::try_envoke_with_overrides(&args).unwrap();
+    /// ```
+    fn try_envoke_with_overrides(overrides: &[impl AsRef<str>]) -> Result<Self> {
+        utils::load_with_overrides(overrides)
+    }
+
+    /// Attempts to create an instance of `Self` like [`Envoke::try_envoke`],
+    /// but with fields marked `parser = "key"` parsed by the parser
+    /// registered under `key` in `registry`, including those of nested
+    /// fields.
+    ///
+    /// # Errors
+    /// Returns an error if `Self` cannot be loaded, including if no parser
+    /// returning the field type is registered under a field's key.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use envoke::{Envoke, Fill, ParserRegistry};
+    ///
+    /// #[derive(Fill)]
+    /// struct Config {
+    ///     #[fill(env = "CODEC", parser = "codec")]
+    ///     codec: String,
+    /// }
+    ///
+    /// let mut registry = ParserRegistry::new();
+    /// registry.register("codec", |value: &str| {
+    ///     Ok::<_, std::convert::Infallible>(value.to_lowercase())
+    /// });
+    /// let config = Config::try_envoke_with_registry(&registry).unwrap();
+    /// ```
+    fn try_envoke_with_registry(registry: &ParserRegistry) -> Result<Self> {
+        registry::load_with_registry(registry)
+    }
 }
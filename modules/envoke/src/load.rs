@@ -1,8 +1,8 @@
-use std::{collections::HashMap, marker::PhantomData, str::FromStr};
+use std::{borrow::Cow, collections::HashMap, marker::PhantomData, str::FromStr};
 
 use crate::{
-    errors::Result,
-    utils::{load_once, parse_map, parse_set, parse_str},
+    errors::{ParseError, Result},
+    utils::{load_once, load_raw, parse_element, parse_map, parse_set, parse_str},
 };
 
 pub struct Envloader<T> {
@@ -13,8 +13,11 @@ pub trait FromMap<M, K, V> {
     fn load_once(
         envs: &[impl AsRef<str>],
         delim: &str,
+        kv_delim: &str,
         fallback: Option<&HashMap<String, String>>,
     ) -> Result<M>;
+
+    fn parse_once(value: &str, delim: &str, kv_delim: &str) -> Result<M>;
 }
 
 impl<M, K, V> FromMap<M, K, V> for Envloader<M>
@@ -26,17 +29,68 @@ where
     fn load_once(
         envs: &[impl AsRef<str>],
         delim: &str,
+        kv_delim: &str,
         fallback: Option<&HashMap<String, String>>,
     ) -> Result<M> {
-        let value: String = match load_once(envs) {
-            Ok(value) => value,
+        <Self as FromMapWith<M, K, V>>::load_once_with(envs, delim, kv_delim, fallback, |value| {
+            parse_str(value)
+        })
+    }
+
+    fn parse_once(value: &str, delim: &str, kv_delim: &str) -> Result<M> {
+        <Self as FromMapWith<M, K, V>>::parse_once_with(value, delim, kv_delim, |value| {
+            parse_str(value)
+        })
+    }
+}
+
+pub trait FromMapWith<M, K, V> {
+    fn load_once_with(
+        envs: &[impl AsRef<str>],
+        delim: &str,
+        kv_delim: &str,
+        fallback: Option<&HashMap<String, String>>,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<M>;
+
+    fn parse_once_with(
+        value: &str,
+        delim: &str,
+        kv_delim: &str,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<M>;
+}
+
+impl<M, K, V> FromMapWith<M, K, V> for Envloader<M>
+where
+    K: FromStr,
+    M: FromIterator<(K, V)>,
+{
+    fn load_once_with(
+        envs: &[impl AsRef<str>],
+        delim: &str,
+        kv_delim: &str,
+        fallback: Option<&HashMap<String, String>>,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<M> {
+        let value: Cow<str> = match load_raw(envs) {
+            Ok(value) => Cow::Owned(value),
             Err(e) => match fallback.and_then(|f| envs.iter().find_map(|e| f.get(e.as_ref()))) {
-                Some(value) => value.to_owned(),
+                Some(value) => Cow::Borrowed(value),
                 None => return Err(e),
             },
         };
 
-        parse_map(&value, delim).map_err(|e| e.into())
+        <Self as FromMapWith<M, K, V>>::parse_once_with(&value, delim, kv_delim, parse)
+    }
+
+    fn parse_once_with(
+        value: &str,
+        delim: &str,
+        kv_delim: &str,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<M> {
+        parse_map(value, delim, kv_delim, parse).map_err(|e| e.into())
     }
 }
 
@@ -46,6 +100,8 @@ pub trait FromSet<S, V> {
         delim: &str,
         fallback: Option<&HashMap<String, String>>,
     ) -> Result<S>;
+
+    fn parse_once(value: &str, delim: &str) -> Result<S>;
 }
 
 impl<S, V> FromSet<S, V> for Envloader<S>
@@ -58,15 +114,58 @@ where
         delim: &str,
         fallback: Option<&HashMap<String, String>>,
     ) -> Result<S> {
-        let value: String = match load_once(envs) {
-            Ok(value) => value,
+        <Self as FromSetWith<S, V>>::load_once_with(envs, delim, fallback, |value| {
+            parse_element(value)
+        })
+    }
+
+    fn parse_once(value: &str, delim: &str) -> Result<S> {
+        <Self as FromSetWith<S, V>>::parse_once_with(value, delim, |value| parse_element(value))
+    }
+}
+
+pub trait FromSetWith<S, V> {
+    fn load_once_with(
+        envs: &[impl AsRef<str>],
+        delim: &str,
+        fallback: Option<&HashMap<String, String>>,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<S>;
+
+    fn parse_once_with(
+        value: &str,
+        delim: &str,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<S>;
+}
+
+impl<S, V> FromSetWith<S, V> for Envloader<S>
+where
+    S: FromIterator<V>,
+{
+    fn load_once_with(
+        envs: &[impl AsRef<str>],
+        delim: &str,
+        fallback: Option<&HashMap<String, String>>,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<S> {
+        let value: Cow<str> = match load_raw(envs) {
+            Ok(value) => Cow::Owned(value),
             Err(e) => match fallback.and_then(|f| envs.iter().find_map(|e| f.get(e.as_ref()))) {
-                Some(value) => value.to_owned(),
+                Some(value) => Cow::Borrowed(value),
                 None => return Err(e),
             },
         };
 
-        parse_set(&value, delim).map_err(Into::into)
+        <Self as FromSetWith<S, V>>::parse_once_with(&value, delim, parse)
+    }
+
+    fn parse_once_with(
+        value: &str,
+        delim: &str,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<S> {
+        parse_set(value, delim, parse).map_err(Into::into)
     }
 }
 
@@ -85,4 +184,8 @@ where
                 .map_or(Err(e), |val| parse_str(val).map_err(Into::into))
         })
     }
+
+    pub fn parse_once(value: &str, _delim: &str) -> Result<V> {
+        parse_str(value).map_err(Into::into)
+    }
 }
@@ -1,20 +1,29 @@
-use std::{collections::HashMap, marker::PhantomData, str::FromStr};
+use std::{borrow::Cow, collections::HashMap, marker::PhantomData, str::FromStr};
 
 use crate::{
-    errors::Result,
-    utils::{load_once, parse_map, parse_set, parse_str},
+    errors::{Error, ParseError, Result, RetrieveError},
+    utils::{load_once, load_raw, parse_element, parse_map, parse_set, parse_str},
 };
 
 pub struct OptEnvloader<T> {
     _marker: PhantomData<T>,
 }
 
+/// Checks if the error is caused by none of the environment variables being
+/// set, which is the only case where an optional field is `None`
+fn is_not_found(e: &Error) -> bool {
+    matches!(e, Error::RetrieveError(RetrieveError::NotFound { .. }))
+}
+
 pub trait FromMapOpt<M, K, V> {
     fn load_once(
         envs: &[impl AsRef<str>],
         delim: &str,
+        kv_delim: &str,
         fallback: Option<&HashMap<String, String>>,
     ) -> Result<Option<M>>;
+
+    fn parse_once(value: &str, delim: &str, kv_delim: &str) -> Result<Option<M>>;
 }
 
 impl<M, K, V> FromMapOpt<M, K, V> for OptEnvloader<Option<M>>
@@ -26,17 +35,75 @@ where
     fn load_once(
         envs: &[impl AsRef<str>],
         delim: &str,
+        kv_delim: &str,
+        fallback: Option<&HashMap<String, String>>,
+    ) -> Result<Option<M>> {
+        <Self as FromMapOptWith<M, K, V>>::load_once_with(
+            envs,
+            delim,
+            kv_delim,
+            fallback,
+            |value| parse_str(value),
+        )
+    }
+
+    fn parse_once(value: &str, delim: &str, kv_delim: &str) -> Result<Option<M>> {
+        <Self as FromMapOptWith<M, K, V>>::parse_once_with(value, delim, kv_delim, |value| {
+            parse_str(value)
+        })
+    }
+}
+
+pub trait FromMapOptWith<M, K, V> {
+    fn load_once_with(
+        envs: &[impl AsRef<str>],
+        delim: &str,
+        kv_delim: &str,
+        fallback: Option<&HashMap<String, String>>,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<Option<M>>;
+
+    fn parse_once_with(
+        value: &str,
+        delim: &str,
+        kv_delim: &str,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<Option<M>>;
+}
+
+impl<M, K, V> FromMapOptWith<M, K, V> for OptEnvloader<Option<M>>
+where
+    K: FromStr,
+    M: FromIterator<(K, V)>,
+{
+    fn load_once_with(
+        envs: &[impl AsRef<str>],
+        delim: &str,
+        kv_delim: &str,
         fallback: Option<&HashMap<String, String>>,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
     ) -> Result<Option<M>> {
-        let value: String = match load_once(envs) {
-            Ok(value) => value,
+        let value: Cow<str> = match load_raw(envs) {
+            Ok(value) => Cow::Owned(value),
+            Err(e) if !is_not_found(&e) => return Err(e),
             Err(_) => match fallback.and_then(|f| envs.iter().find_map(|e| f.get(e.as_ref()))) {
-                Some(value) => value.to_owned(),
+                Some(value) => Cow::Borrowed(value),
                 None => return Ok(None),
             },
         };
 
-        parse_map(&value, delim).map(Some).map_err(|e| e.into())
+        <Self as FromMapOptWith<M, K, V>>::parse_once_with(&value, delim, kv_delim, parse)
+    }
+
+    fn parse_once_with(
+        value: &str,
+        delim: &str,
+        kv_delim: &str,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<Option<M>> {
+        parse_map(value, delim, kv_delim, parse)
+            .map(Some)
+            .map_err(|e| e.into())
     }
 }
 
@@ -46,6 +113,8 @@ pub trait FromSetOpt<S, V> {
         delim: &str,
         fallback: Option<&HashMap<String, String>>,
     ) -> Result<Option<S>>;
+
+    fn parse_once(value: &str, delim: &str) -> Result<Option<S>>;
 }
 
 impl<S, V> FromSetOpt<S, V> for OptEnvloader<Option<S>>
@@ -58,15 +127,61 @@ where
         delim: &str,
         fallback: Option<&HashMap<String, String>>,
     ) -> Result<Option<S>> {
-        let value: String = match load_once(envs) {
-            Ok(value) => value,
+        <Self as FromSetOptWith<S, V>>::load_once_with(envs, delim, fallback, |value| {
+            parse_element(value)
+        })
+    }
+
+    fn parse_once(value: &str, delim: &str) -> Result<Option<S>> {
+        <Self as FromSetOptWith<S, V>>::parse_once_with(value, delim, |value| parse_element(value))
+    }
+}
+
+pub trait FromSetOptWith<S, V> {
+    fn load_once_with(
+        envs: &[impl AsRef<str>],
+        delim: &str,
+        fallback: Option<&HashMap<String, String>>,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<Option<S>>;
+
+    fn parse_once_with(
+        value: &str,
+        delim: &str,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<Option<S>>;
+}
+
+impl<S, V> FromSetOptWith<S, V> for OptEnvloader<Option<S>>
+where
+    S: FromIterator<V>,
+{
+    fn load_once_with(
+        envs: &[impl AsRef<str>],
+        delim: &str,
+        fallback: Option<&HashMap<String, String>>,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<Option<S>> {
+        let value: Cow<str> = match load_raw(envs) {
+            Ok(value) => Cow::Owned(value),
+            Err(e) if !is_not_found(&e) => return Err(e),
             Err(_) => match fallback.and_then(|f| envs.iter().find_map(|e| f.get(e.as_ref()))) {
-                Some(value) => value.to_owned(),
+                Some(value) => Cow::Borrowed(value),
                 None => return Ok(None),
             },
         };
 
-        parse_set(&value, delim).map(Some).map_err(|e| e.into())
+        <Self as FromSetOptWith<S, V>>::parse_once_with(&value, delim, parse)
+    }
+
+    fn parse_once_with(
+        value: &str,
+        delim: &str,
+        parse: impl Fn(&str) -> std::result::Result<V, ParseError>,
+    ) -> Result<Option<S>> {
+        parse_set(value, delim, parse)
+            .map(Some)
+            .map_err(|e| e.into())
     }
 }
 
@@ -79,12 +194,19 @@ where
         _delim: &str,
         fallback: Option<&HashMap<String, String>>,
     ) -> Result<Option<V>> {
-        load_once(envs).map(Some).or_else(|e| {
-            fallback
+        // Only fall back if none of the variables are set, a value which is
+        // set but cannot be parsed is an error just like for required fields
+        match load_once(envs) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if !is_not_found(&e) => Err(e),
+            Err(_) => fallback
                 .and_then(|f| envs.iter().find_map(|e| f.get(e.as_ref())))
-                .map(parse_str)
-                .transpose()
-                .or(Err(e))
-        })
+                .map(|value| parse_str(value).map_err(Into::into))
+                .transpose(),
+        }
+    }
+
+    pub fn parse_once(value: &str, _delim: &str) -> Result<Option<V>> {
+        parse_str(value).map(Some).map_err(Into::into)
     }
 }
@@ -0,0 +1,76 @@
+use std::{any::Any, cell::RefCell, collections::HashMap, sync::Arc};
+
+use crate::{
+    errors::{BoxError, ParseError, Result},
+    utils::short_type_name,
+};
+
+type Parser<T> = Arc<dyn Fn(&str) -> std::result::Result<T, BoxError> + Send + Sync>;
+
+/// Parsers registered at runtime under a key, e.g., by plugins contributing
+/// their own config types. Fields with the `parser` attribute are parsed by
+/// the parser registered under their key when loaded with
+/// [`Envoke::try_envoke_with_registry`](crate::Envoke::try_envoke_with_registry)
+#[derive(Clone, Default)]
+pub struct ParserRegistry {
+    parsers: HashMap<String, Arc<dyn Any + Send + Sync>>,
+}
+
+impl ParserRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `parser` under `key`, replacing any parser already
+    /// registered under it
+    pub fn register<T, E, F>(&mut self, key: impl Into<String>, parser: F) -> &mut Self
+    where
+        T: 'static,
+        E: Into<BoxError>,
+        F: Fn(&str) -> std::result::Result<T, E> + Send + Sync + 'static,
+    {
+        let parser: Parser<T> = Arc::new(move |value| parser(value).map_err(Into::into));
+        self.parsers.insert(key.into(), Arc::new(parser));
+        self
+    }
+
+    /// Returns the parser registered under `key` if it returns a `T`
+    fn get<T: 'static>(&self, key: &str) -> Option<Parser<T>> {
+        self.parsers.get(key)?.downcast_ref::<Parser<T>>().cloned()
+    }
+}
+
+thread_local! {
+    /// The registry fields with the `parser` attribute are parsed with
+    static REGISTRY: RefCell<Option<ParserRegistry>> = const { RefCell::new(None) };
+}
+
+/// Loads `T` with `registry` available to fields with the `parser` attribute.
+/// The previous registry is restored afterwards, even if loading panics
+pub fn load_with_registry<T: crate::Envoke>(registry: &ParserRegistry) -> Result<T> {
+    struct Restore(Option<ParserRegistry>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            REGISTRY.with(|r| *r.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(REGISTRY.with(|r| r.replace(Some(registry.clone()))));
+    T::try_envoke()
+}
+
+/// Parses `value` with the parser registered under `key` in the current
+/// registry. The parser is called after the registry is released, so it may
+/// load other types itself
+pub fn parse_registered<T: 'static>(key: &str, value: &str) -> std::result::Result<T, BoxError> {
+    let parser = REGISTRY.with(|r| r.borrow().as_ref().and_then(|r| r.get::<T>(key)));
+    match parser {
+        Some(parser) => parser(value),
+        None => Err(ParseError::UnknownParser {
+            key: key.to_string(),
+            ty: short_type_name::<T>().to_string(),
+        }
+        .into()),
+    }
+}
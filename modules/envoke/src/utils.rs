@@ -1,14 +1,46 @@
-use std::{collections::HashMap, env, io::BufRead, str::FromStr};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeSet, HashMap},
+    env,
+    ffi::OsString,
+    io::BufRead,
+    net::AddrParseError,
+    num::{IntErrorKind, ParseIntError},
+    ops::{Bound, RangeInclusive},
+    str::FromStr,
+    sync::{PoisonError, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use crate::errors::{ParseError, Result, RetrieveError};
+#[cfg(feature = "schema")]
+use crate::errors::BoxError;
+use crate::{
+    errors::{ParseError, Result, RetrieveError, ValidationError},
+    Envoke,
+};
 
+/// Loads the `KEY=VALUE` lines of the dotenv file at `filepath`. A file which
+/// cannot be opened or read, e.g., a missing file, a directory, or a file
+/// which is not valid UTF-8, is an error
 pub fn load_dotenv(filepath: &str) -> Result<HashMap<String, String>> {
-    let file = std::fs::File::open(filepath).unwrap();
-    let reader = std::io::BufReader::new(file);
+    let read_err = |err| RetrieveError::ReadFile {
+        path: filepath.to_string(),
+        err,
+    };
 
-    let envs = reader
+    let file = std::fs::File::open(filepath).map_err(read_err)?;
+    let lines = std::io::BufReader::new(file)
         .lines()
-        .flat_map(|line| line.ok())
+        .collect::<std::io::Result<Vec<String>>>()
+        .map_err(read_err)?;
+
+    Ok(parse_dotenv(lines.into_iter()))
+}
+
+/// Parses the `KEY=VALUE` lines of a dotenv file, skipping empty lines and
+/// comments
+fn parse_dotenv(lines: impl Iterator<Item = String>) -> HashMap<String, String> {
+    lines
         .map(|line| line.trim().to_owned())
         .filter(|line| !line.is_empty() && !line.starts_with('#'))
         .filter_map(|line| {
@@ -23,29 +55,320 @@ pub fn load_dotenv(filepath: &str) -> Result<HashMap<String, String>> {
 
             Some((key.to_string(), value.to_string()))
         })
+        .collect()
+}
+
+/// Loads `T` from a value made up of `KEY=VALUE` lines like a dotenv file,
+/// e.g., a config section passed through a single environment variable. Keys
+/// which are not read by `T` are an error
+pub fn load_block<T: Envoke>(block: &str) -> Result<T> {
+    let source = parse_dotenv(block.lines().map(str::to_owned));
+
+    let keys = T::env_keys();
+    if let Some(key) = source.keys().filter(|key| !keys.contains(key)).min() {
+        return Err(ParseError::UnknownKey { key: key.clone() }.into());
+    }
+
+    with_source(source, T::try_envoke)
+}
+
+/// Returns the names of the variables starting with `prefix` which are not
+/// one of `keys`, in sorted order
+fn unread_vars(prefix: &str, keys: &[String]) -> BTreeSet<String> {
+    var_names()
+        .into_iter()
+        .filter(|key| key.starts_with(prefix) && !keys.contains(key))
+        .collect()
+}
+
+/// Fails if any variable starting with `prefix` is set which is not one of
+/// `keys`, listing all of them in sorted order
+pub fn deny_unknown(prefix: &str, keys: &[String]) -> Result<()> {
+    let unknown = unread_vars(prefix, keys);
+
+    match unknown.is_empty() {
+        true => Ok(()),
+        false => Err(ValidationError::UnknownVariables {
+            keys: unknown.into_iter().collect(),
+        })?,
+    }
+}
+
+/// Fails if any of the removed variables is set, naming the first one along
+/// with the note on what replaced it, if any
+pub fn deny_removed(removed: &[(&str, Option<&str>)]) -> Result<()> {
+    match removed.iter().find(|(key, _)| exact_var(key).is_ok()) {
+        Some((key, note)) => Err(RetrieveError::Removed {
+            key: key.to_string(),
+            note: note.map(str::to_string),
+        })?,
+        None => Ok(()),
+    }
+}
+
+/// Collects the variables starting with `prefix` which are not one of `keys`
+/// into a map keyed by the rest of their name, e.g., `APP_COLOR` is collected
+/// as `COLOR`. A key which is itself one of `keys` would make the variable
+/// look like it is read by another field and is an error
+pub fn load_rest<M>(prefix: &str, keys: &[String]) -> Result<M>
+where
+    M: FromIterator<(String, String)>,
+{
+    let mut rest = Vec::new();
+    for name in unread_vars(prefix, keys) {
+        let key = name[prefix.len()..].to_string();
+        if keys.contains(&key) {
+            return Err(ValidationError::RestOverlap { name, key }.into());
+        }
+
+        match exact_var(&name) {
+            Ok(value) => rest.push((key, value)),
+            Err(env::VarError::NotPresent) => continue,
+            Err(env::VarError::NotUnicode(_)) => Err(RetrieveError::InvalidUnicode { key: name })?,
+        }
+    }
+
+    Ok(rest.into_iter().collect())
+}
+
+/// Variables read instead of the process environment. Values are kept as
+/// `OsString` so invalid Unicode is reported when read, like it is for the
+/// process environment
+type Source = HashMap<String, OsString>;
+
+thread_local! {
+    static SOURCE: RefCell<Option<Source>> = const { RefCell::new(None) };
+
+    /// The sources replaced by prefix scopes, innermost last
+    static PARENTS: RefCell<Vec<Option<Source>>> = const { RefCell::new(Vec::new()) };
+
+    /// Whether a variable which is not set may be read from one whose name
+    /// only differs in case and separators
+    static FUZZY_NAMES: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with values read from `source`, or the process environment if it
+/// is `None`. The previous source is restored afterwards, even if `f` panics
+fn replace_source<T>(source: Option<Source>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Source>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SOURCE.with(|s| *s.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(SOURCE.with(|s| s.replace(source)));
+    f()
+}
+
+/// Runs `f` with values read from `source` instead of the process
+/// environment. The previous source is restored afterwards, even if `f`
+/// panics
+pub fn with_source<T>(source: HashMap<String, String>, f: impl FnOnce() -> T) -> T {
+    let source = source
+        .into_iter()
+        .map(|(key, value)| (key, value.into()))
+        .collect();
+    replace_source(Some(source), f)
+}
+
+/// Loads `T` with `overrides` of the form `KEY=VALUE` or `--KEY=VALUE` taking
+/// precedence over the current source, which is still read for other keys
+pub fn load_with_overrides<T: Envoke>(overrides: &[impl AsRef<str>]) -> Result<T> {
+    let mut source: Source = var_names()
+        .into_iter()
+        .filter_map(|key| var_os(&key).map(|value| (key, value)))
+        .collect();
+
+    for arg in overrides {
+        let arg = arg.as_ref();
+        let pair = arg.strip_prefix("--").unwrap_or(arg);
+        let Some((key, value)) = pair.split_once('=').filter(|(key, _)| !key.is_empty()) else {
+            return Err(ParseError::InvalidOverride {
+                arg: arg.to_string(),
+            })?;
+        };
+
+        source.insert(key.to_string(), value.into());
+    }
+
+    replace_source(Some(source), T::try_envoke)
+}
+
+/// Runs `f` with values read from `source`, which holds the variables of a
+/// prefix scope. Fields marked `global` still read the source the scope
+/// replaced
+fn with_scope<T>(source: HashMap<String, String>, f: impl FnOnce() -> T) -> T {
+    struct Pop;
+
+    impl Drop for Pop {
+        fn drop(&mut self) {
+            PARENTS.with(|p| p.borrow_mut().pop());
+        }
+    }
+
+    let parent = SOURCE.with(|s| s.borrow().clone());
+    PARENTS.with(|p| p.borrow_mut().push(parent));
+    let _pop = Pop;
+    with_source(source, f)
+}
+
+/// Runs `f` with the source replaced by the innermost prefix scope, e.g., the
+/// process environment while loading a map of nested structs. Outside a
+/// prefix scope the current source is kept
+pub fn load_global<T>(f: impl FnOnce() -> T) -> T {
+    match PARENTS.with(|p| p.borrow().last().cloned()) {
+        Some(parent) => replace_source(parent, f),
+        None => f(),
+    }
+}
+
+/// Runs `f` with variables which are not set read from a variable whose name
+/// only differs in case and separators, e.g., `app-port` for `APP_PORT`. The
+/// previous mode is restored afterwards, even if `f` panics
+pub fn with_fuzzy_names<T>(f: impl FnOnce() -> T) -> T {
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            FUZZY_NAMES.with(|n| n.set(self.0));
+        }
+    }
+
+    let _restore = Restore(FUZZY_NAMES.with(|n| n.replace(true)));
+    f()
+}
+
+/// Lowercases `name` and unifies the separators `-`, `.`, and `_`
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '-' | '.' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+/// Reads `key` from the current source, which is the process environment
+/// unless it is replaced by [`with_source`]. In [`with_fuzzy_names`] a key
+/// which is not set is looked up by its normalized name, using the first
+/// matching variable in sorted order
+fn var(key: &str) -> std::result::Result<String, env::VarError> {
+    match exact_var(key) {
+        Err(env::VarError::NotPresent) if FUZZY_NAMES.with(Cell::get) => {
+            let target = normalize_name(key);
+            let name = var_names()
+                .into_iter()
+                .filter(|name| normalize_name(name) == target)
+                .min()
+                .ok_or(env::VarError::NotPresent)?;
+            exact_var(&name)
+        }
+        result => result,
+    }
+}
+
+fn exact_var(key: &str) -> std::result::Result<String, env::VarError> {
+    var_os(key)
+        .ok_or(env::VarError::NotPresent)?
+        .into_string()
+        .map_err(env::VarError::NotUnicode)
+}
+
+fn var_os(key: &str) -> Option<OsString> {
+    SOURCE.with(|s| match &*s.borrow() {
+        Some(source) => source.get(key).cloned(),
+        None => env::var_os(key),
+    })
+}
+
+/// Returns the names of all variables in the current source. Variables with
+/// invalid Unicode in their name are skipped
+fn var_names() -> Vec<String> {
+    SOURCE.with(|s| match &*s.borrow() {
+        Some(source) => source.keys().cloned().collect(),
+        None => env::vars_os()
+            .filter_map(|(key, _)| key.into_string().ok())
+            .collect(),
+    })
+}
+
+/// Loads a map of nested structs from the variables named
+/// `{prefix}{key}_{name}`, where `name` is a variable read by `T`, e.g.,
+/// `DB_main_HOST`. The keys are discovered from the variables which are set
+/// and each key is loaded once from its own variables
+pub fn load_nested_map<M, K, T>(prefix: &str) -> Result<M>
+where
+    M: FromIterator<(K, T)>,
+    K: FromStr,
+    T: Envoke,
+{
+    let names = T::env_keys();
+
+    // If several names match, the longest one is used, e.g., `DB_main_DB_HOST`
+    // has the key `main` and not `main_DB` if `T` reads `DB_HOST`
+    let keys: BTreeSet<String> = var_names()
+        .iter()
+        .filter_map(|var| {
+            let rest = var.strip_prefix(prefix)?;
+            names
+                .iter()
+                .filter_map(|name| rest.strip_suffix(name.as_str())?.strip_suffix('_'))
+                .filter(|key| !key.is_empty())
+                .min_by_key(|key| key.len())
+                .map(str::to_owned)
+        })
         .collect();
-    Ok(envs)
+
+    keys.into_iter()
+        .map(|key| {
+            let source = names
+                .iter()
+                .filter_map(|name| {
+                    let value = var(&format!("{prefix}{key}_{name}")).ok()?;
+                    Some((name.clone(), value))
+                })
+                .collect();
+
+            let value = with_scope(source, T::try_envoke)?;
+            let key = key.parse().map_err(|_| ParseError::UnexpectedKeyType {
+                key,
+                ty: short_type_name::<K>().to_string(),
+            })?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
+/// Returns the name of the type without its module path, e.g., `Region` for
+/// `config::Region`, to name the expected type in errors
+pub fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
 }
 
 pub fn load_once<T: FromStr>(envs: &[impl AsRef<str>]) -> Result<T> {
+    let value = load_raw(envs)?;
+    match value.trim().parse() {
+        Ok(value) => Ok(value),
+        Err(_) => Err(ParseError::UnexpectedValueType { value })?,
+    }
+}
+
+/// Returns the unparsed value of the first environment variable which is set.
+/// Sequences and maps are split from the value directly, so this avoids
+/// copying it into another `String` as `load_once::<String>` would
+pub fn load_raw(envs: &[impl AsRef<str>]) -> Result<String> {
     for key in envs {
         let key = key.as_ref().trim();
 
-        let value = match env::var(key) {
-            Ok(value) => value,
-            Err(e) => match e {
-                env::VarError::NotPresent => continue,
-                env::VarError::NotUnicode(_) => {
-                    return Err(RetrieveError::InvalidUnicode {
-                        key: key.to_string(),
-                    })?
-                }
-            },
-        };
-
-        return match value.trim().parse() {
+        return match var(key) {
             Ok(value) => Ok(value),
-            Err(_) => Err(ParseError::UnexpectedValueType { value })?,
+            Err(env::VarError::NotPresent) => continue,
+            Err(env::VarError::NotUnicode(_)) => Err(RetrieveError::InvalidUnicode {
+                key: key.to_string(),
+            })?,
         };
     }
 
@@ -58,20 +381,181 @@ pub fn load_once<T: FromStr>(envs: &[impl AsRef<str>]) -> Result<T> {
     })?
 }
 
-pub fn parse_map<K, V, M>(pairs: &str, delim: &str) -> std::result::Result<M, ParseError>
+/// Reads the trimmed contents of the file at `path`, e.g., a secret mounted by
+/// Docker or Kubernetes. Returns `None` if the file does not exist
+pub fn load_file(path: &str) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(value) => Ok(Some(value.trim().to_string())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(RetrieveError::ReadFile {
+            path: path.to_string(),
+            err,
+        })?,
+    }
+}
+
+/// Returns the first key which satisfies the lookup. Keys are checked in the
+/// same order as the loaders, i.e., the process environment before `fallback`
+pub fn find_source<'a>(
+    envs: &'a [impl AsRef<str>],
+    fallback: Option<&HashMap<String, String>>,
+) -> Option<&'a str> {
+    envs.iter()
+        .map(|key| key.as_ref().trim())
+        .find(|key| !matches!(var(key), Err(env::VarError::NotPresent)))
+        .or_else(|| {
+            let fallback = fallback?;
+            envs.iter()
+                .map(|key| key.as_ref())
+                .find(|key| fallback.contains_key(*key))
+        })
+}
+
+static WARNING_HANDLER: RwLock<fn(&str)> = RwLock::new(print_warning);
+
+#[cfg(feature = "log")]
+fn print_warning(message: &str) {
+    log::warn!("{message}");
+}
+
+#[cfg(all(feature = "tracing", not(feature = "log")))]
+fn print_warning(message: &str) {
+    tracing::warn!("{message}");
+}
+
+// Without a logging feature warnings are only passed to a handler set with
+// `set_warning_handler`, as a library should not write to stderr unasked
+#[cfg(not(any(feature = "log", feature = "tracing")))]
+fn print_warning(_: &str) {}
+
+/// A field whose value differs between two instances of a struct, returned by
+/// the `diff` method generated by the `diff` container attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// Name of the field, nested fields are prefixed with the name of their
+    /// parent, e.g., `db.host`
+    pub field: String,
+
+    /// The formatted old value, or `None` if it is not set
+    pub old: Option<String>,
+
+    /// The formatted new value, or `None` if it is not set
+    pub new: Option<String>,
+}
+
+/// The context passed to a `default_fn`, describing the field whose default
+/// is computed
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultCtx<'a> {
+    /// Name of the field
+    pub field: &'a str,
+
+    /// The environment variable names the field is loaded from, with prefix
+    /// and suffix applied. Empty if the field is not loaded from the
+    /// environment
+    pub envs: &'a [&'a str],
+}
+
+/// Sets the function which is called with warnings emitted while loading,
+/// e.g., when a value is read from a deprecated environment variable. By
+/// default warnings are logged with `log::warn!` if the `log` feature is
+/// enabled, or `tracing::warn!` if the `tracing` feature is, and dropped
+/// otherwise
+pub fn set_warning_handler(handler: fn(&str)) {
+    *WARNING_HANDLER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = handler;
+}
+
+pub fn warn_deprecated(field: &str, key: &str, hint: &str) {
+    let handler = *WARNING_HANDLER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    handler(&format!(
+        "environment variable `{key}` used by `{field}` is deprecated, {hint}"
+    ));
+}
+
+/// Expands a leading `~` to the home directory and `$VAR`/`${VAR}` references
+/// to their values in the process environment
+pub fn expand_path<P: From<String>>(value: String) -> std::result::Result<P, RetrieveError> {
+    fn var(key: &str) -> std::result::Result<String, RetrieveError> {
+        env::var(key).map_err(|e| match e {
+            env::VarError::NotPresent => RetrieveError::NotFound {
+                keys: format!("`{key}`"),
+            },
+            env::VarError::NotUnicode(_) => RetrieveError::InvalidUnicode {
+                key: key.to_string(),
+            },
+        })
+    }
+
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value.as_str();
+
+    if rest == "~" || rest.starts_with("~/") {
+        let home = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+        expanded.push_str(&var(home)?);
+        rest = &rest[1..];
+    }
+
+    while let Some(pos) = rest.find('$') {
+        expanded.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+
+        let (key, remaining) = match rest.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => ("", rest),
+            },
+            None => {
+                let end = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            }
+        };
+
+        // Not a variable reference, keep the dollar sign as is
+        if key.is_empty() {
+            expanded.push('$');
+            continue;
+        }
+
+        expanded.push_str(&var(key)?);
+        rest = remaining;
+    }
+
+    expanded.push_str(rest);
+    Ok(P::from(expanded))
+}
+
+/// Parses a map from `pairs`, parsing each value with the given function
+pub fn parse_map<K, V, M, F>(
+    pairs: &str,
+    delim: &str,
+    kv_delim: &str,
+    parse: F,
+) -> std::result::Result<M, ParseError>
 where
     K: FromStr,
-    V: FromStr,
     M: FromIterator<(K, V)>,
+    F: Fn(&str) -> std::result::Result<V, ParseError>,
 {
     pairs
         .trim()
         .split(delim)
         .map(|part| {
-            let mut parts = part.splitn(2, "=");
+            let mut parts = part.splitn(2, kv_delim);
             let key = parts.next().ok_or(ParseError::MissingKey)?.trim();
             let val = parts.next().ok_or(ParseError::MissingValue)?.trim();
 
+            // A separator with nothing but other separators around it, e.g.,
+            // `=` or `==`, has neither a key nor a value
+            if key.is_empty() && val.replace(kv_delim, "").trim().is_empty() {
+                return Err(ParseError::UnexpectedEqualsign);
+            }
+
             if key.is_empty() {
                 return Err(ParseError::MissingKey);
             }
@@ -82,37 +566,52 @@ where
 
             let parsed_key: K = key.parse().map_err(|_| ParseError::UnexpectedKeyType {
                 key: key.to_string(),
+                ty: short_type_name::<K>().to_string(),
             })?;
-            let parsed_val = val.parse().map_err(|_| ParseError::UnexpectedValueType {
-                value: val.to_string(),
-            })?;
+            let parsed_val = parse(val)?;
 
             Ok((parsed_key, parsed_val))
         })
         .collect()
 }
 
-pub fn parse_set<S, V>(sequence: &str, delim: &str) -> std::result::Result<S, ParseError>
+/// Parses a sequence from `sequence`, parsing each element with the given
+/// function. Empty elements are passed to the function as well, see
+/// [`parse_element`] for rejecting them
+pub fn parse_set<S, V, F>(
+    sequence: &str,
+    delim: &str,
+    parse: F,
+) -> std::result::Result<S, ParseError>
 where
-    V: FromStr,
     S: FromIterator<V>,
+    F: Fn(&str) -> std::result::Result<V, ParseError>,
 {
     sequence
         .trim()
         .split(delim)
-        .map(|part| {
-            let val = part.trim();
-            if val.is_empty() {
-                return Err(ParseError::MissingValue);
-            }
-
-            val.parse().map_err(|_| ParseError::UnexpectedValueType {
-                value: val.to_string(),
+        .enumerate()
+        .map(|(index, part)| {
+            parse(part.trim()).map_err(|err| ParseError::InvalidElement {
+                index,
+                err: Box::new(err),
             })
         })
         .collect()
 }
 
+/// Parses an element of a sequence, treating empty elements as missing
+pub fn parse_element<V>(value: &str) -> std::result::Result<V, ParseError>
+where
+    V: FromStr,
+{
+    if value.is_empty() {
+        return Err(ParseError::MissingValue);
+    }
+
+    parse_str(value)
+}
+
 pub fn parse_str<V>(value: impl AsRef<str>) -> std::result::Result<V, ParseError>
 where
     V: FromStr,
@@ -122,3 +621,586 @@ where
         value: val.to_string(),
     })
 }
+
+/// Strips `prefix` and `suffix` from the value, e.g., `Bearer ` from
+/// `Bearer xyz`. A prefix or suffix which is not present is left as is
+pub fn trim_affixes<'a>(value: &'a str, prefix: &str, suffix: &str) -> &'a str {
+    let value = value.strip_prefix(prefix).unwrap_or(value);
+    value.strip_suffix(suffix).unwrap_or(value)
+}
+
+/// Parses a flag, where an empty value means `true` and any other value is
+/// parsed as a `bool`, e.g., both `FEATURE=` and `FEATURE=true` are `true`
+pub fn parse_flag(value: impl AsRef<str>) -> std::result::Result<bool, ParseError> {
+    match value.as_ref().trim() {
+        "" => Ok(true),
+        value => parse_str(value),
+    }
+}
+
+/// Parses a network address, e.g., an `IpAddr` or `SocketAddr`, keeping the
+/// reason it is invalid. Empty values are treated as missing
+pub fn parse_addr<T>(value: impl AsRef<str>) -> std::result::Result<T, ParseError>
+where
+    T: FromStr<Err = AddrParseError>,
+{
+    let value = value.as_ref();
+    if value.is_empty() {
+        return Err(ParseError::MissingValue);
+    }
+
+    value.parse().map_err(|err| ParseError::InvalidAddress {
+        value: value.to_string(),
+        err,
+    })
+}
+
+/// Parses an enum from either its name or the index of the variant, e.g., both
+/// `production` and `1`. The name is tried first
+pub fn parse_name_or_index<T>(value: impl AsRef<str>) -> std::result::Result<T, ParseError>
+where
+    T: FromStr + strum::VariantNames,
+{
+    let value = value.as_ref();
+    if let Ok(value) = value.parse() {
+        return Ok(value);
+    }
+
+    value
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| T::VARIANTS.get(index))
+        .ok_or_else(|| ParseError::UnexpectedValueType {
+            value: value.to_string(),
+        })
+        .and_then(parse_str)
+}
+
+/// Parses an integer after removing the group separators, e.g., `1_000_000`
+pub fn parse_grouped<T: FromStr>(
+    value: String,
+    separators: &str,
+) -> std::result::Result<T, ParseError> {
+    let stripped: String = value.chars().filter(|c| !separators.contains(*c)).collect();
+    stripped
+        .parse()
+        .map_err(|_| ParseError::UnexpectedValueType { value })
+}
+
+/// Parses a float which may use `,` as the decimal separator, e.g., `1,5`.
+/// Only the separator is replaced, the number is parsed by `FromStr`
+pub fn parse_decimal_comma<T: FromStr>(value: String) -> std::result::Result<T, ParseError> {
+    value
+        .replace(',', ".")
+        .parse()
+        .map_err(|_| ParseError::UnexpectedValueType { value })
+}
+
+/// Integer types which can be parsed in a given radix
+pub trait FromStrRadix: Sized {
+    fn from_str_radix(src: &str, radix: u32) -> std::result::Result<Self, ParseIntError>;
+}
+
+macro_rules! impl_from_str_radix {
+    ($($ty:ty),*) => {
+        $(
+            impl FromStrRadix for $ty {
+                fn from_str_radix(src: &str, radix: u32) -> std::result::Result<Self, ParseIntError> {
+                    <$ty>::from_str_radix(src, radix)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str_radix!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Parses an integer in the radix given by its prefix, i.e., `0x`, `0o`, `0b`,
+/// or none for decimal. Underscores are ignored, e.g., `-0xFF_FF`
+pub fn parse_radix<T: FromStrRadix>(value: String) -> std::result::Result<T, ParseError> {
+    let stripped: String = value.trim().chars().filter(|c| *c != '_').collect();
+    let (sign, unsigned) = match stripped.strip_prefix(['-', '+']) {
+        Some(rest) => (&stripped[..1], rest),
+        None => ("", stripped.as_str()),
+    };
+
+    let (radix, digits) = match unsigned.get(..2).map(str::to_ascii_lowercase).as_deref() {
+        Some("0x") => (16, &unsigned[2..]),
+        Some("0o") => (8, &unsigned[2..]),
+        Some("0b") => (2, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+
+    // The sign is checked here as `from_str_radix` would accept a second one
+    if digits.starts_with(['-', '+']) {
+        return Err(ParseError::UnexpectedValueType { value });
+    }
+
+    T::from_str_radix(&format!("{sign}{digits}"), radix).map_err(|e| match e.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+            ParseError::IntegerOverflow { value }
+        }
+        _ => ParseError::UnexpectedValueType { value },
+    })
+}
+
+/// Parses a duration made up of one or more amounts with a unit, e.g., `30s`
+/// or `1h 30m`
+pub fn parse_duration(value: impl AsRef<str>) -> std::result::Result<Duration, ParseError> {
+    let value = value.as_ref();
+    let invalid = || ParseError::InvalidDuration {
+        value: value.to_string(),
+    };
+
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total = Duration::ZERO;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let amount: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = rest[digits..].trim_start();
+
+        let letters = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = &rest[..letters];
+        rest = rest[letters..].trim_start();
+
+        let secs = |factor: u64| amount.checked_mul(factor).map(Duration::from_secs);
+        let part = match unit {
+            "ns" | "nsec" | "nanos" => Some(Duration::from_nanos(amount)),
+            "us" | "µs" | "usec" | "micros" => Some(Duration::from_micros(amount)),
+            "ms" | "msec" | "millis" => Some(Duration::from_millis(amount)),
+            "s" | "sec" | "secs" | "second" | "seconds" => secs(1),
+            "m" | "min" | "mins" | "minute" | "minutes" => secs(60),
+            "h" | "hr" | "hrs" | "hour" | "hours" => secs(60 * 60),
+            "d" | "day" | "days" => secs(24 * 60 * 60),
+            "w" | "week" | "weeks" => secs(7 * 24 * 60 * 60),
+            _ => None,
+        };
+
+        total = part
+            .and_then(|part| total.checked_add(part))
+            .ok_or_else(invalid)?;
+    }
+
+    Ok(total)
+}
+
+/// Parses a unix timestamp counting seconds, or milliseconds if `millis` is
+/// set, since the unix epoch into a `SystemTime`
+pub fn parse_unix_timestamp(
+    value: impl AsRef<str>,
+    millis: bool,
+) -> std::result::Result<SystemTime, ParseError> {
+    let value = value.as_ref();
+    let invalid = |reason: &str| ParseError::InvalidTimestamp {
+        value: value.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let amount: u64 = value
+        .trim()
+        .parse()
+        .map_err(|e: ParseIntError| match e.kind() {
+            IntErrorKind::PosOverflow => invalid("the timestamp is too large"),
+            _ if value.trim().starts_with('-') => {
+                invalid("timestamps before the unix epoch are not supported")
+            }
+            _ => invalid("expected a whole number"),
+        })?;
+
+    let since_epoch = match millis {
+        true => Duration::from_millis(amount),
+        false => Duration::from_secs(amount),
+    };
+    UNIX_EPOCH
+        .checked_add(since_epoch)
+        .ok_or_else(|| invalid("the timestamp is too large"))
+}
+
+/// Formats a `SystemTime` as the unix timestamp it is parsed from. Times
+/// before the unix epoch are formatted as `0`
+pub fn format_unix_timestamp(value: &SystemTime, millis: bool) -> String {
+    let since_epoch = value.duration_since(UNIX_EPOCH).unwrap_or_default();
+    match millis {
+        true => since_epoch.as_millis().to_string(),
+        false => since_epoch.as_secs().to_string(),
+    }
+}
+
+/// Parses a duration like [`parse_duration`] into a `time::Duration`
+#[cfg(feature = "time")]
+pub fn parse_time_duration(
+    value: impl AsRef<str>,
+) -> std::result::Result<time::Duration, ParseError> {
+    let value = value.as_ref();
+    parse_duration(value).and_then(|duration| {
+        time::Duration::try_from(duration).map_err(|_| ParseError::InvalidDuration {
+            value: value.to_string(),
+        })
+    })
+}
+
+/// Parses a duration like [`parse_duration`] into a `chrono::Duration`
+#[cfg(feature = "chrono")]
+pub fn parse_chrono_duration(
+    value: impl AsRef<str>,
+) -> std::result::Result<chrono::Duration, ParseError> {
+    let value = value.as_ref();
+    parse_duration(value).and_then(|duration| {
+        chrono::Duration::from_std(duration).map_err(|_| ParseError::InvalidDuration {
+            value: value.to_string(),
+        })
+    })
+}
+
+/// Parses an RFC 3339 date-time, e.g., `2024-05-01T12:00:00+02:00`, into a
+/// `chrono::DateTime`. The offset is kept for `DateTime<FixedOffset>` and the
+/// value is converted for other time zones, e.g., `DateTime<Utc>`
+#[cfg(feature = "chrono")]
+pub fn parse_chrono_datetime<T>(value: impl AsRef<str>) -> std::result::Result<T, ParseError>
+where
+    T: From<chrono::DateTime<chrono::FixedOffset>>,
+{
+    let value = value.as_ref();
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(T::from)
+        .map_err(|e| ParseError::InvalidDateTime {
+            value: value.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+/// Formats a `chrono::DateTime` as RFC 3339 so it is parsed back as is
+#[cfg(feature = "chrono")]
+pub fn format_chrono_datetime<Tz>(value: &chrono::DateTime<Tz>) -> String
+where
+    Tz: chrono::TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    value.to_rfc3339()
+}
+
+/// Parses an RFC 3339 date-time, e.g., `2024-05-01T12:00:00+02:00`, into a
+/// `time::OffsetDateTime`, keeping the offset, or a `time::UtcDateTime`
+#[cfg(feature = "time")]
+pub fn parse_time_datetime<T>(value: impl AsRef<str>) -> std::result::Result<T, ParseError>
+where
+    T: From<time::OffsetDateTime>,
+{
+    let value = value.as_ref();
+    time::OffsetDateTime::parse(value, &time::format_description::well_known::Rfc3339)
+        .map(T::from)
+        .map_err(|e| ParseError::InvalidDateTime {
+            value: value.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+/// Formats a `time` date-time as RFC 3339 so it is parsed back as is. Values
+/// RFC 3339 cannot represent, e.g., years after 9999, are formatted as empty
+#[cfg(feature = "time")]
+pub fn format_time_datetime<T>(value: &T) -> String
+where
+    T: Copy + Into<time::OffsetDateTime>,
+{
+    (*value)
+        .into()
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
+}
+
+/// Parses interval notation, e.g., `[0,100)`, into a pair of bounds. `[` and
+/// `]` are inclusive while `(` and `)` are exclusive. An empty side is
+/// unbounded, e.g., `(,100]`
+pub fn parse_interval<T: FromStr>(
+    value: String,
+) -> std::result::Result<(Bound<T>, Bound<T>), ParseError> {
+    let invalid = || ParseError::InvalidInterval {
+        value: value.clone(),
+    };
+
+    let trimmed = value.trim();
+    let mut chars = trimmed.chars();
+    let (Some(open), Some(close)) = (chars.next(), chars.next_back()) else {
+        return Err(invalid());
+    };
+
+    let (start, end) = chars.as_str().split_once(',').ok_or_else(invalid)?;
+    let bound = |value: &str, inclusive: bool| {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(Bound::Unbounded);
+        }
+
+        let value = value.parse().map_err(|_| invalid())?;
+        match inclusive {
+            true => Ok(Bound::Included(value)),
+            false => Ok(Bound::Excluded(value)),
+        }
+    };
+
+    let start = match open {
+        '[' => bound(start, true)?,
+        '(' => bound(start, false)?,
+        _ => return Err(invalid()),
+    };
+
+    let end = match close {
+        ']' => bound(end, true)?,
+        ')' => bound(end, false)?,
+        _ => return Err(invalid()),
+    };
+
+    Ok((start, end))
+}
+
+/// Parses a single value or an inclusive range written as `start-end`, e.g.,
+/// `80` into `80..=80` and `8000-8100` into `8000..=8100`. The separator is
+/// looked for after the first character so a negative start is allowed.
+/// Empty values are treated as missing
+pub fn parse_range<T>(value: impl AsRef<str>) -> std::result::Result<RangeInclusive<T>, ParseError>
+where
+    T: FromStr + PartialOrd + Clone,
+{
+    let value = value.as_ref().trim();
+    if value.is_empty() {
+        return Err(ParseError::MissingValue);
+    }
+
+    let invalid = || ParseError::InvalidRange {
+        value: value.to_string(),
+    };
+
+    let separator = value.char_indices().skip(1).find(|(_, c)| *c == '-');
+    let (start, end) = match separator {
+        Some((index, _)) => value.split_at(index),
+        None => (value, ""),
+    };
+    let start: T = start.trim().parse().map_err(|_| invalid())?;
+    let end: T = match end.strip_prefix('-') {
+        Some(end) => end.trim().parse().map_err(|_| invalid())?,
+        None => start.clone(),
+    };
+
+    if start > end {
+        return Err(ParseError::ReversedRange {
+            value: value.to_string(),
+        });
+    }
+
+    Ok(start..=end)
+}
+
+/// Expands the loaded ranges into the values they contain, e.g.,
+/// `[80..=80, 8000..=8002]` into `[80, 8000, 8001, 8002]`
+pub fn flatten_ranges<T, C>(
+    ranges: Vec<RangeInclusive<T>>,
+) -> std::result::Result<C, std::convert::Infallible>
+where
+    RangeInclusive<T>: Iterator<Item = T>,
+    C: FromIterator<T>,
+{
+    Ok(ranges.into_iter().flatten().collect())
+}
+
+/// Collects the loaded elements into a map keyed by their position, e.g.,
+/// `a,b,c` into `{0: "a", 1: "b", 2: "c"}`
+pub fn collect_enumerated<V, M>(values: Vec<V>) -> std::result::Result<M, std::convert::Infallible>
+where
+    M: FromIterator<(usize, V)>,
+{
+    Ok(values.into_iter().enumerate().collect())
+}
+
+/// Checks that no element is equal to an earlier one, reporting the index of
+/// the first duplicate
+pub fn deny_duplicates<'a, T>(
+    field: &str,
+    values: impl IntoIterator<Item = &'a T>,
+) -> std::result::Result<(), ValidationError>
+where
+    T: PartialEq + 'a,
+{
+    let mut seen: Vec<&T> = Vec::new();
+    for (index, value) in values.into_iter().enumerate() {
+        if seen.contains(&value) {
+            return Err(ValidationError::Duplicate {
+                field: field.to_string(),
+                index,
+            });
+        }
+
+        seen.push(value);
+    }
+
+    Ok(())
+}
+
+/// Collects the loaded elements into an `ArrayVec`, failing instead of
+/// panicking if there are more elements than it can hold
+#[cfg(feature = "arrayvec")]
+pub fn collect_array_vec<T, const CAP: usize>(
+    values: Vec<T>,
+) -> std::result::Result<arrayvec::ArrayVec<T, CAP>, ParseError> {
+    if values.len() > CAP {
+        return Err(ParseError::CapacityExceeded {
+            len: values.len(),
+            capacity: CAP,
+        });
+    }
+
+    Ok(values.into_iter().collect())
+}
+
+/// Returns the number of CPUs available to the process, or 1 if it cannot be
+/// determined
+#[cfg(feature = "system-defaults")]
+pub fn cpus() -> usize {
+    std::thread::available_parallelism().map_or(1, std::num::NonZero::get)
+}
+
+/// Returns the number of CPUs available to the process multiplied by
+/// `factor`, e.g., for a number of workers per CPU
+#[cfg(feature = "system-defaults")]
+pub fn cpus_times(factor: usize) -> usize {
+    cpus().saturating_mul(factor)
+}
+
+/// Wraps the loaded value in a `SecretString`, which does not implement
+/// `FromStr` itself
+#[cfg(feature = "secrecy")]
+pub fn parse_secret(
+    value: String,
+) -> std::result::Result<secrecy::SecretString, std::convert::Infallible> {
+    Ok(value.into())
+}
+
+/// Converts the value into `Bytes` without copying it
+#[cfg(feature = "bytes")]
+pub fn parse_bytes(value: String) -> std::result::Result<bytes::Bytes, std::convert::Infallible> {
+    Ok(bytes::Bytes::from(value.into_bytes()))
+}
+
+/// Feeds the value to a function with the signature of a serde
+/// `deserialize_with` function through a string deserializer
+#[cfg(feature = "serde")]
+pub fn deserialize_with<T>(
+    value: String,
+    f: impl FnOnce(
+        serde::de::value::StringDeserializer<serde::de::value::Error>,
+    ) -> std::result::Result<T, serde::de::value::Error>,
+) -> std::result::Result<T, serde::de::value::Error> {
+    f(serde::de::IntoDeserializer::into_deserializer(value))
+}
+
+/// Checks that the URL has one of the allowed schemes
+#[cfg(feature = "url")]
+pub fn check_url_scheme(
+    field: &str,
+    url: &url::Url,
+    schemes: &[&str],
+) -> std::result::Result<(), ValidationError> {
+    if schemes.contains(&url.scheme()) {
+        return Ok(());
+    }
+
+    Err(ValidationError::InvalidScheme {
+        field: field.to_string(),
+        scheme: url.scheme().to_string(),
+        allowed: schemes.iter().map(|scheme| scheme.to_string()).collect(),
+    })
+}
+
+#[cfg(feature = "glob")]
+pub fn parse_glob(value: String) -> std::result::Result<glob::Pattern, glob::PatternError> {
+    glob::Pattern::new(&value)
+}
+
+/// Implemented by the derive macro for structs which can be loaded from the
+/// named capture groups of a field's `pattern`
+#[cfg(feature = "regex")]
+pub trait FromCaptures: Sized {
+    fn from_captures(captures: &HashMap<String, String>) -> Result<Self>;
+}
+
+/// Matches `value` against `pattern` and returns the named capture groups
+/// which participated in the match
+#[cfg(feature = "regex")]
+pub fn match_captures(value: &str, pattern: &str, field: &str) -> Result<HashMap<String, String>> {
+    let regex = regex::Regex::new(pattern).map_err(|e| ParseError::failed(field, e))?;
+
+    let captures = regex
+        .captures(value)
+        .ok_or_else(|| ParseError::PatternMismatch {
+            field: field.to_string(),
+            pattern: pattern.to_string(),
+        })?;
+
+    Ok(regex
+        .capture_names()
+        .flatten()
+        .filter_map(|name| Some((name.to_string(), captures.name(name)?.as_str().to_string())))
+        .collect())
+}
+
+/// Holds the validator compiled from the schema of a struct, so the schema
+/// is only compiled on the first load
+#[cfg(feature = "schema")]
+#[derive(Default)]
+pub struct SchemaCache(std::sync::OnceLock<jsonschema::Validator>);
+
+#[cfg(feature = "schema")]
+impl SchemaCache {
+    pub const fn new() -> Self {
+        Self(std::sync::OnceLock::new())
+    }
+}
+
+/// Serializes `value` and validates it against the JSON Schema `schema`
+/// embedded from `schema_file`. The compiled validator is kept in `cache`
+#[cfg(feature = "schema")]
+pub fn validate_schema<T: serde::Serialize>(
+    value: &T,
+    schema_file: &str,
+    schema: &str,
+    cache: &SchemaCache,
+) -> Result<()> {
+    let validator = match cache.0.get() {
+        Some(validator) => validator,
+        None => {
+            let load_validator = || -> std::result::Result<jsonschema::Validator, BoxError> {
+                let schema: serde_json::Value = serde_json::from_str(schema)?;
+                Ok(jsonschema::validator_for(&schema)?)
+            };
+
+            let validator = load_validator().map_err(|err| ValidationError::InvalidSchema {
+                path: schema_file.to_string(),
+                err,
+            })?;
+            cache.0.get_or_init(|| validator)
+        }
+    };
+
+    let instance = serde_json::to_value(value).map_err(|e| ValidationError::Unserializable {
+        path: schema_file.to_string(),
+        err: e.into(),
+    })?;
+
+    validator
+        .validate(&instance)
+        .map_err(|e| ValidationError::Schema {
+            instance_path: e.instance_path.to_string(),
+            schema_path: e.schema_path.to_string(),
+            message: e.to_string(),
+        })?;
+
+    Ok(())
+}
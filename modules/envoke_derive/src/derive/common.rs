@@ -1,6 +1,8 @@
 use std::str::FromStr;
 
 use convert_case::{Case as ConvertCase, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
 use strum::VariantNames;
 
 use crate::utils::find_closest_match;
@@ -180,6 +182,72 @@ pub enum Case {
     /// ```
     #[strum(serialize = "SCREAMING-KEBAB-CASE")]
     ScreamingKebab,
+
+    /// Capitalizes the first letter of each word and uses hyphens `-` to
+    /// separate words.
+    ///
+    /// Used if [ContainerAttributes::rename_all] is set to `Train-Case`
+    ///
+    /// ### Example
+    ///
+    /// Renames `some_field_name` to `Some-Field-Name`
+    ///
+    /// ```
+    /// #[derive(Fill)]
+    /// #[fill(rename_all = "Train-Case")]
+    /// struct Example {
+    ///     #[fill(env = "some_field_name")]
+    ///     field: String,
+    /// }
+    ///
+    /// let _ = Example::try_invoke()?;
+    /// ```
+    #[strum(serialize = "Train-Case")]
+    Train,
+
+    /// Converts names to lowercase and uses dots `.` to separate words.
+    ///
+    /// Used if [ContainerAttributes::rename_all] is set to `dot.case`
+    ///
+    /// ### Example
+    ///
+    /// Renames `some_field_name` to `some.field.name`
+    ///
+    /// ```
+    /// #[derive(Fill)]
+    /// #[fill(rename_all = "dot.case")]
+    /// struct Example {
+    ///     #[fill(env = "some_field_name")]
+    ///     field: String,
+    /// }
+    ///
+    /// let _ = Example::try_invoke()?;
+    /// ```
+    #[strum(serialize = "dot.case")]
+    Dot,
+
+    /// Converts names to lowercase and removes the separators between words.
+    /// Unlike `lowercase`, words are split on case changes first, e.g.,
+    /// `someField` and `some_field` both become `somefield`.
+    ///
+    /// Used if [ContainerAttributes::rename_all] is set to `flatcase`
+    ///
+    /// ### Example
+    ///
+    /// Renames `some_field_name` to `somefieldname`
+    ///
+    /// ```
+    /// #[derive(Fill)]
+    /// #[fill(rename_all = "flatcase")]
+    /// struct Example {
+    ///     #[fill(env = "some_field_name")]
+    ///     field: String,
+    /// }
+    ///
+    /// let _ = Example::try_invoke()?;
+    /// ```
+    #[strum(serialize = "flatcase")]
+    Flat,
 }
 
 impl syn::parse::Parse for Case {
@@ -208,6 +276,258 @@ impl Case {
             Case::ScreamingSnake => s.to_case(ConvertCase::UpperSnake),
             Case::Kebab => s.to_case(ConvertCase::Kebab),
             Case::ScreamingKebab => s.to_case(ConvertCase::UpperKebab),
+            Case::Train => s.to_case(ConvertCase::Train),
+            Case::Dot => s.to_case(ConvertCase::Custom {
+                boundaries: &[],
+                pattern: convert_case::pattern::lowercase,
+                delim: ".",
+            }),
+            Case::Flat => s.to_case(ConvertCase::Flat),
         }
     }
+
+    /// Generates an expression renaming the string `value` at runtime, the
+    /// same way [Case::rename] does
+    pub fn rename_call(&self, value: TokenStream) -> TokenStream {
+        let case = match self {
+            Case::Lower => return quote! { #value.to_lowercase() },
+            Case::Upper => return quote! { #value.to_uppercase() },
+            Case::Pascal => quote! { Pascal },
+            Case::Camel => quote! { Camel },
+            Case::Snake => quote! { Snake },
+            Case::ScreamingSnake => quote! { UpperSnake },
+            Case::Kebab => quote! { Kebab },
+            Case::ScreamingKebab => quote! { UpperKebab },
+            Case::Train => quote! { Train },
+            Case::Dot => quote! {
+                Custom {
+                    boundaries: &[],
+                    pattern: envoke::convert_case::pattern::lowercase,
+                    delim: ".",
+                }
+            },
+            Case::Flat => quote! { Flat },
+        };
+
+        quote! {
+            envoke::convert_case::Casing::to_case(&#value, envoke::convert_case::Case::#case)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, strum::EnumString, strum::VariantNames)]
+pub enum EnumBy {
+    /// Parses the value using the enum's `FromStr` implementation.
+    ///
+    /// Used if [FieldAttributes::enum_by] is set to `name`
+    #[strum(serialize = "name")]
+    Name,
+
+    /// Parses the value using the enum's `FromStr` implementation, falling
+    /// back to interpreting it as the index of the variant. The enum must
+    /// implement `strum::VariantNames`.
+    ///
+    /// Used if [FieldAttributes::enum_by] is set to `name_or_index`
+    ///
+    /// ### Example
+    ///
+    /// Loads both `MODE=production` and `MODE=1` into `Mode::Production`
+    ///
+    /// ```
+    /// #[derive(strum::EnumString, strum::VariantNames)]
+    /// #[strum(serialize_all = "lowercase")]
+    /// enum Mode {
+    ///     Development,
+    ///     Production,
+    /// }
+    ///
+    /// #[derive(Fill)]
+    /// struct Example {
+    ///     #[fill(env = "MODE", enum_by = "name_or_index")]
+    ///     mode: Mode,
+    /// }
+    /// ```
+    #[strum(serialize = "name_or_index")]
+    NameOrIndex,
+}
+
+impl syn::parse::Parse for EnumBy {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let input: syn::LitStr = input.parse()?;
+        let value = input.value();
+        EnumBy::from_str(&value).map_err(|_| {
+            let mut message = format!("unexpected enum representation `{value}`");
+            if let Some(closest_match) = find_closest_match(&value, EnumBy::VARIANTS) {
+                message = format!("{message}, did you mean `{closest_match}`?")
+            }
+
+            syn::Error::new_spanned(input, message)
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, strum::EnumString, strum::VariantNames)]
+pub enum TimestampUnit {
+    /// The timestamp counts seconds since the unix epoch, e.g., `1714564800`.
+    ///
+    /// Used if [FieldAttributes::unix_timestamp] is set without a value or to
+    /// `seconds`
+    #[strum(serialize = "seconds")]
+    Seconds,
+
+    /// The timestamp counts milliseconds since the unix epoch, e.g.,
+    /// `1714564800000`.
+    ///
+    /// Used if [FieldAttributes::unix_timestamp] is set to `millis`
+    #[strum(serialize = "millis")]
+    Millis,
+}
+
+impl syn::parse::Parse for TimestampUnit {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let input: syn::LitStr = input.parse()?;
+        let value = input.value();
+        TimestampUnit::from_str(&value).map_err(|_| {
+            let mut message = format!("unexpected timestamp unit `{value}`");
+            if let Some(closest_match) = find_closest_match(&value, TimestampUnit::VARIANTS) {
+                message = format!("{message}, did you mean `{closest_match}`?")
+            }
+
+            syn::Error::new_spanned(input, message)
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, strum::EnumString, strum::VariantNames)]
+pub enum EnvPriority {
+    /// The first environment variable of a field which is set is used.
+    ///
+    /// Used if [ContainerAttributes::env_priority] is set to `first`
+    #[strum(serialize = "first")]
+    First,
+
+    /// The last environment variable of a field which is set is used, so a
+    /// more specific override can be listed after the general name.
+    ///
+    /// Used if [ContainerAttributes::env_priority] is set to `last`
+    ///
+    /// ### Example
+    ///
+    /// Loads `APP_PORT` over `PORT` if both are set
+    ///
+    /// ```
+    /// #[derive(Fill)]
+    /// #[fill(env_priority = "last")]
+    /// struct Example {
+    ///     #[fill(env = "PORT", env = "APP_PORT")]
+    ///     port: u16,
+    /// }
+    /// ```
+    #[strum(serialize = "last")]
+    Last,
+}
+
+impl syn::parse::Parse for EnvPriority {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let input: syn::LitStr = input.parse()?;
+        let value = input.value();
+        EnvPriority::from_str(&value).map_err(|_| {
+            let mut message = format!("unexpected environment variable priority `{value}`");
+            if let Some(closest_match) = find_closest_match(&value, EnvPriority::VARIANTS) {
+                message = format!("{message}, did you mean `{closest_match}`?")
+            }
+
+            syn::Error::new_spanned(input, message)
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, strum::EnumString, strum::VariantNames)]
+pub enum Ranges {
+    /// Keeps each single value or range as a `RangeInclusive`, where a single
+    /// value is a range of one.
+    ///
+    /// Used if [FieldAttributes::ranges] is set without a value or to `keep`
+    ///
+    /// ### Example
+    ///
+    /// Loads `PORTS=80,8000-8100` into `[80..=80, 8000..=8100]`
+    ///
+    /// ```
+    /// #[derive(Fill)]
+    /// struct Example {
+    ///     #[fill(env = "PORTS", ranges)]
+    ///     ports: Vec<RangeInclusive<u16>>,
+    /// }
+    /// ```
+    #[strum(serialize = "keep")]
+    Keep,
+
+    /// Expands each single value or range into the values it contains.
+    ///
+    /// Used if [FieldAttributes::ranges] is set to `flatten`
+    ///
+    /// ### Example
+    ///
+    /// Loads `PORTS=80,8000-8002` into `[80, 8000, 8001, 8002]`
+    ///
+    /// ```
+    /// #[derive(Fill)]
+    /// struct Example {
+    ///     #[fill(env = "PORTS", ranges = "flatten")]
+    ///     ports: Vec<u16>,
+    /// }
+    /// ```
+    #[strum(serialize = "flatten")]
+    Flatten,
+}
+
+impl syn::parse::Parse for Ranges {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let input: syn::LitStr = input.parse()?;
+        let value = input.value();
+        Ranges::from_str(&value).map_err(|_| {
+            let mut message = format!("unexpected ranges representation `{value}`");
+            if let Some(closest_match) = find_closest_match(&value, Ranges::VARIANTS) {
+                message = format!("{message}, did you mean `{closest_match}`?")
+            }
+
+            syn::Error::new_spanned(input, message)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_train() {
+        let case = Case::from_str("Train-Case").unwrap();
+        assert_eq!(case.rename("some_field_name"), "Some-Field-Name");
+        assert_eq!(case.rename("APP_someField"), "App-Some-Field");
+    }
+
+    #[test]
+    fn test_rename_dot() {
+        let case = Case::from_str("dot.case").unwrap();
+        assert_eq!(case.rename("some_field_name"), "some.field.name");
+        assert_eq!(case.rename("APP_someField"), "app.some.field");
+    }
+
+    #[test]
+    fn test_rename_flat() {
+        let case = Case::from_str("flatcase").unwrap();
+        assert_eq!(case.rename("some_field_name"), "somefieldname");
+        assert_eq!(case.rename("APP_someField"), "appsomefield");
+    }
+
+    #[test]
+    fn test_rename_unknown_case() {
+        assert!(Case::from_str("Train_Case").is_err());
+        assert_eq!(
+            find_closest_match("dotcase", Case::VARIANTS),
+            Some("dot.case")
+        );
+    }
 }
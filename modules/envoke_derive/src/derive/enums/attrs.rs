@@ -7,7 +7,7 @@ use crate::{derive::common::Case, errors::Error, utils::find_closest_match};
 #[derive(Debug, Default)]
 pub struct ContainerAttributes {
     // Envvars to look for
-    pub envs: Option<Vec<String>>,
+    pub envs: Option<Vec<Env>>,
 
     // Change case of names
     pub rename_all: Option<Case>,
@@ -18,7 +18,7 @@ pub struct ContainerAttributes {
     // Suffix to put after all names
     pub suffix: Option<String>,
 
-    // Delimiter used to separate prefix, name, and suffix
+    // Delimiter used to separate prefix, name, and suffix, empty by default
     pub delimiter: Option<String>,
 
     /// Define a dotenv file to load and add to the struct fields
@@ -32,6 +32,37 @@ pub struct ContainerAttributes {
     ///
     /// **Default**: None
     pub dotenv: Option<String>,
+
+    /// Generate a `to_json_redacted` method which serializes the loaded enum to
+    /// JSON. Requires the `serde` feature
+    ///
+    /// **Default**: false
+    pub json: bool,
+
+    /// Generate an `env_value` method which returns the canonical name of the
+    /// loaded variant and an `env_line` method which returns the `KEY=value`
+    /// line of the enum, e.g., to export a variant loaded through an alias
+    ///
+    /// **Default**: false
+    pub env_line: bool,
+
+    /// A function taking the loaded `String` and returning the `String` to
+    /// match against the variant names, e.g., to normalize the case
+    ///
+    /// **Default**: None
+    pub parse_fn: Option<syn::Path>,
+
+    /// Similar to `parse_fn` except it returns a `Result<String, E>`, e.g.,
+    /// to reject unknown legacy codes. The error is returned as is
+    ///
+    /// **Default**: None
+    pub try_parse_fn: Option<syn::Path>,
+
+    /// Match the loaded value against the variant names ignoring ASCII case,
+    /// e.g., `production` matches `PRODUCTION`
+    ///
+    /// **Default**: false
+    pub case_insensitive: bool,
 }
 
 impl ContainerAttributes {
@@ -42,40 +73,44 @@ impl ContainerAttributes {
         "suffix",
         "delimiter",
         "dotenv",
+        "json",
+        "env_line",
+        "parse_fn",
+        "try_parse_fn",
+        "case_insensitive",
     ];
 
     fn add_env(&mut self, input: &DeriveInput, meta: ParseNestedMeta) -> syn::Result<()> {
-        // Allows the user to specify both
-        // 1. `#[fill(env)]` - Uses the field name as environment variable
+        // Allows the user to specify
+        // 1. `#[fill(env)]` - Uses the enum name as environment variable
         // 2. `#[fill(env = "env")]` - Uses `env` as the environment variable
-        let env = match meta.input.peek(syn::Token![=]) {
-            true => {
-                let str: syn::LitStr = meta.value()?.parse()?;
-                let env = str.value();
-                if env.is_empty() {
-                    return Err(Error::invalid_attribute("env", "attribute cannot be empty")
-                        .to_syn_error(meta.path.span()));
-                }
-
-                if self.envs.as_ref().is_some_and(|e| e.contains(&env)) {
-                    return Err(Error::duplicate_attribute(format!("env::{env}"))
-                        .to_syn_error(meta.path.span()));
-                }
-
-                env
-            }
-            false => {
-                let ident = &input.ident;
-                let env = quote! { #ident }.to_string();
+        // 3. `#[fill(env(name = "env", no_prefix))]` - Same as above but with control
+        //    over the prefix and suffix of this name only
+        let ident = &input.ident;
+        let default_name = quote! { #ident }.to_string();
+
+        let env = if meta.input.peek(syn::Token![=]) {
+            let str: syn::LitStr = meta.value()?.parse()?;
+            Env::new(str.value())
+        } else if meta.input.peek(syn::token::Paren) {
+            Env::from_nested_meta(&meta, default_name)?
+        } else {
+            Env::new(default_name)
+        };
 
-                if self.envs.as_ref().is_some_and(|e| e.contains(&env)) {
-                    return Err(Error::duplicate_attribute(format!("env::{env}"))
-                        .to_syn_error(meta.path.span()));
-                }
+        if env.name.is_empty() {
+            return Err(Error::invalid_attribute("env", "attribute cannot be empty")
+                .to_syn_error(meta.path.span()));
+        }
 
-                env
-            }
-        };
+        if self
+            .envs
+            .as_ref()
+            .is_some_and(|e| e.iter().any(|e| e.name == env.name))
+        {
+            return Err(Error::duplicate_attribute(format!("env::{}", env.name))
+                .to_syn_error(meta.path.span()));
+        }
 
         self.envs.get_or_insert(Vec::new()).push(env);
         Ok(())
@@ -131,6 +166,77 @@ impl ContainerAttributes {
         Ok(())
     }
 
+    fn set_json(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if self.json {
+            return Err(Error::duplicate_attribute("json").to_syn_error(meta.path.span()));
+        }
+
+        if cfg!(not(feature = "serde")) {
+            return Err(Error::invalid_attribute(
+                "json",
+                "requires the `serde` feature to be enabled",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.json = true;
+        Ok(())
+    }
+
+    fn set_env_line(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if self.env_line {
+            return Err(Error::duplicate_attribute("env_line").to_syn_error(meta.path.span()));
+        }
+
+        self.env_line = true;
+        Ok(())
+    }
+
+    fn set_parse_fn(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if self.try_parse_fn.is_some() {
+            return Err(Error::invalid_attribute(
+                "parse_fn",
+                "`parse_fn` cannot be used together with `try_parse_fn`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        if self.parse_fn.is_some() {
+            return Err(Error::duplicate_attribute("parse_fn").to_syn_error(meta.path.span()));
+        }
+
+        self.parse_fn = Some(meta.value()?.parse()?);
+        Ok(())
+    }
+
+    fn set_try_parse_fn(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if self.parse_fn.is_some() {
+            return Err(Error::invalid_attribute(
+                "try_parse_fn",
+                "`try_parse_fn` cannot be used together with `parse_fn`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        if self.try_parse_fn.is_some() {
+            return Err(Error::duplicate_attribute("try_parse_fn").to_syn_error(meta.path.span()));
+        }
+
+        self.try_parse_fn = Some(meta.value()?.parse()?);
+        Ok(())
+    }
+
+    fn set_case_insensitive(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if self.case_insensitive {
+            return Err(
+                Error::duplicate_attribute("case_insensitive").to_syn_error(meta.path.span())
+            );
+        }
+
+        self.case_insensitive = true;
+        Ok(())
+    }
+
     fn get_prefix(&self) -> &str {
         self.prefix.as_deref().unwrap_or_default()
     }
@@ -171,7 +277,7 @@ impl ContainerAttributes {
             .clone()
             .unwrap()
             .into_iter()
-            .map(|e| self.rename(e, false, false))
+            .map(|e| self.rename(e.name, e.no_prefix, e.no_suffix))
             .collect()
     }
 }
@@ -191,12 +297,17 @@ impl TryFrom<&DeriveInput> for ContainerAttributes {
                 let ident = quote! { #ident }.to_string();
 
                 match ident.as_ref() {
-                    "env" => ca.add_env(&input, meta),
+                    "env" => ca.add_env(input, meta),
                     "rename_all" => ca.set_rename_all(meta),
                     "prefix" => ca.set_prefix(meta),
                     "suffix" => ca.set_suffix(meta),
                     "delimiter" => ca.set_delimiter(meta),
                     "dotenv" => ca.set_dotenv(meta),
+                    "json" => ca.set_json(meta),
+                    "env_line" => ca.set_env_line(meta),
+                    "parse_fn" => ca.set_parse_fn(meta),
+                    "try_parse_fn" => ca.set_try_parse_fn(meta),
+                    "case_insensitive" => ca.set_case_insensitive(meta),
                     _ => {
                         let closest_match = find_closest_match(&ident, Self::VARIANTS);
                         Err(Error::unexpected_attribute(ident, closest_match)
@@ -213,13 +324,91 @@ impl TryFrom<&DeriveInput> for ContainerAttributes {
             let ident = &input.ident;
             let env = quote! { #ident }.to_string();
 
-            ca.envs.get_or_insert(Vec::new()).push(env);
+            ca.envs.get_or_insert(Vec::new()).push(Env::new(env));
         }
 
         Ok(ca)
     }
 }
 
+/// An environment variable the selected variant is loaded from
+#[derive(Debug, Clone)]
+pub struct Env {
+    pub name: String,
+
+    // Disable adding the container prefix to this name only
+    pub no_prefix: bool,
+
+    // Disable adding the container suffix to this name only
+    pub no_suffix: bool,
+}
+
+impl Env {
+    const VARIANTS: &[&str] = &["name", "no_prefix", "no_suffix"];
+
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            no_prefix: false,
+            no_suffix: false,
+        }
+    }
+
+    fn from_nested_meta(meta: &ParseNestedMeta, default_name: String) -> syn::Result<Self> {
+        let mut name: Option<String> = None;
+        let mut no_prefix = false;
+        let mut no_suffix = false;
+
+        meta.parse_nested_meta(|meta| {
+            let ident = meta.path.get_ident();
+            let ident = quote! { #ident }.to_string();
+
+            match ident.as_ref() {
+                "name" => {
+                    if name.is_some() {
+                        return Err(
+                            Error::duplicate_attribute("env::name").to_syn_error(meta.path.span())
+                        );
+                    }
+
+                    let str: syn::LitStr = meta.value()?.parse()?;
+                    name = Some(str.value());
+                    Ok(())
+                }
+                "no_prefix" => {
+                    if no_prefix {
+                        return Err(Error::duplicate_attribute("env::no_prefix")
+                            .to_syn_error(meta.path.span()));
+                    }
+
+                    no_prefix = true;
+                    Ok(())
+                }
+                "no_suffix" => {
+                    if no_suffix {
+                        return Err(Error::duplicate_attribute("env::no_suffix")
+                            .to_syn_error(meta.path.span()));
+                    }
+
+                    no_suffix = true;
+                    Ok(())
+                }
+                _ => {
+                    let closest_match = find_closest_match(&ident, Self::VARIANTS);
+                    Err(Error::unexpected_attribute(ident, closest_match)
+                        .to_syn_error(meta.path.span()))
+                }
+            }
+        })?;
+
+        Ok(Self {
+            name: name.unwrap_or(default_name),
+            no_prefix,
+            no_suffix,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct Default {
     pub span: Span,
@@ -247,10 +436,24 @@ pub struct VariantAttributes {
 
     // Set this as the default field if nothing is found
     pub default: Option<Default>,
+
+    // Also select this variant if the value starts with the prefix
+    pub matches_prefix: Option<String>,
+
+    // Parse the inner type from the value after `matches_prefix`
+    pub remainder_into_inner: bool,
 }
 
 impl VariantAttributes {
-    const VARIANTS: &[&str] = &["rename", "alias", "no_prefix", "no_suffix", "default"];
+    const VARIANTS: &[&str] = &[
+        "rename",
+        "alias",
+        "no_prefix",
+        "no_suffix",
+        "default",
+        "matches_prefix",
+        "remainder_into_inner",
+    ];
 
     fn set_rename(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
         let str: syn::LitStr = meta.value()?.parse()?;
@@ -328,6 +531,35 @@ impl VariantAttributes {
         });
         Ok(())
     }
+
+    fn set_matches_prefix(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if self.matches_prefix.is_some() {
+            return Err(Error::duplicate_attribute("matches_prefix").to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let value = str.value();
+        if value.is_empty() {
+            return Err(
+                Error::invalid_attribute("matches_prefix", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.matches_prefix = Some(value);
+        Ok(())
+    }
+
+    fn set_remainder_into_inner(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if self.remainder_into_inner {
+            return Err(
+                Error::duplicate_attribute("remainder_into_inner").to_syn_error(meta.path.span())
+            );
+        }
+
+        self.remainder_into_inner = true;
+        Ok(())
+    }
 }
 
 impl TryFrom<&syn::Variant> for VariantAttributes {
@@ -350,6 +582,8 @@ impl TryFrom<&syn::Variant> for VariantAttributes {
                     "no_prefix" => va.disable_prefix(meta),
                     "no_suffix" => va.disable_suffix(meta),
                     "default" => va.set_default(meta),
+                    "matches_prefix" => va.set_matches_prefix(meta),
+                    "remainder_into_inner" => va.set_remainder_into_inner(meta),
                     _ => {
                         let closest_match = find_closest_match(&ident, Self::VARIANTS);
                         Err(Error::unexpected_attribute(ident, closest_match)
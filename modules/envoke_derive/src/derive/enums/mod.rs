@@ -2,7 +2,7 @@ use attrs::{ContainerAttributes, Name, VariantAttributes};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{spanned::Spanned, DeriveInput, Ident, Type};
-use utils::{generate_variant_calls, get_enum_data};
+use utils::{generate_env_value_calls, generate_json_calls, generate_variant_calls, get_enum_data};
 
 use crate::errors::Error;
 
@@ -34,6 +34,32 @@ impl TryFrom<syn::Variant> for Variant {
             _ => return Err(Error::UnsupportedEnumType.to_syn_error(variant.span())),
         };
 
+        if attrs.remainder_into_inner {
+            if attrs.matches_prefix.is_none() {
+                return Err(Error::missing_attribute(
+                    "matches_prefix",
+                    "required if `remainder_into_inner` is set",
+                )
+                .to_syn_error(variant.span()));
+            }
+
+            if inner_ident.is_none() {
+                return Err(Error::invalid_attribute(
+                    "remainder_into_inner",
+                    "can only be used on variants holding a type",
+                )
+                .to_syn_error(variant.span()));
+            }
+
+            if attrs.default.is_some() {
+                return Err(Error::invalid_attribute(
+                    "remainder_into_inner",
+                    "cannot be used together with `default`",
+                )
+                .to_syn_error(variant.span()));
+            }
+        }
+
         Ok(Self {
             ident: variant.ident.clone(),
             inner_ident,
@@ -60,6 +86,21 @@ impl Variant {
 
         names
     }
+
+    /// Returns the type the variant holds if it is loaded with `Envoke`, which
+    /// is not the case if it is parsed from the remainder of the value
+    fn loaded_inner(&self) -> Option<&Ident> {
+        self.inner_ident
+            .as_ref()
+            .filter(|_| !self.attrs.remainder_into_inner)
+    }
+
+    /// Returns the name the variant is exported as, i.e., its renamed name
+    /// rather than any of its aliases
+    fn canonical_name(&self, c_attrs: &ContainerAttributes) -> String {
+        let name = self.get_names().remove(0);
+        c_attrs.rename(name.value, self.attrs.no_prefix, self.attrs.no_suffix)
+    }
 }
 
 pub fn derive_for(input: DeriveInput) -> syn::Result<TokenStream> {
@@ -93,6 +134,97 @@ pub fn derive_for(input: DeriveInput) -> syn::Result<TokenStream> {
         },
     };
 
+    let json_impl = match c_attrs.json {
+        true => {
+            let json_calls = generate_json_calls(enum_name, &variants, &c_attrs);
+            quote! {
+                impl #impl_generics #enum_name #type_generics #where_clause {
+                    /// Serializes the enum to a JSON value, replacing the
+                    /// value of sensitive fields with `"***"`
+                    pub fn to_json_value_redacted(&self) -> envoke::Result<envoke::serde_json::Value> {
+                        Ok(match self {
+                            #(#json_calls),*
+                        })
+                    }
+
+                    /// Serializes the enum to a JSON string, replacing the
+                    /// value of sensitive fields with `"***"`
+                    pub fn to_json_redacted(&self) -> envoke::Result<String> {
+                        self.to_json_value_redacted().map(|value| value.to_string())
+                    }
+                }
+            }
+        }
+        false => quote! {},
+    };
+
+    let env_line_impl = match c_attrs.env_line {
+        true => {
+            let key = &envs[0];
+            let value_calls = generate_env_value_calls(enum_name, &variants, &c_attrs);
+            let mut nested_calls: Vec<TokenStream> = variants
+                .iter()
+                .filter(|variant| variant.loaded_inner().is_some())
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    quote! { #enum_name::#ident(inner) => inner.env_line(field) }
+                })
+                .collect();
+            if nested_calls.len() < variants.len() {
+                nested_calls.push(quote! { _ => None });
+            }
+
+            quote! {
+                impl #impl_generics #enum_name #type_generics #where_clause {
+                    /// Returns the canonical name of the loaded variant, which
+                    /// loads the same variant if it is used as the value
+                    pub fn env_value(&self) -> &'static str {
+                        match self {
+                            #(#value_calls),*
+                        }
+                    }
+
+                    /// Returns the `KEY=value` line of the enum if `field` is
+                    /// the name of its environment variable. Other names are
+                    /// looked up in the struct of the loaded variant
+                    pub fn env_line(&self, field: &str) -> Option<String> {
+                        if field == #key {
+                            return Some(format!("{}={}", #key, self.env_value()));
+                        }
+
+                        match self {
+                            #(#nested_calls),*
+                        }
+                    }
+                }
+            }
+        }
+        false => quote! {},
+    };
+
+    // Every variant holding a struct may be loaded, so all of their variables
+    // are listed
+    let inner_idents: Vec<&Ident> = variants.iter().filter_map(Variant::loaded_inner).collect();
+    let env_keys_call = quote! {
+        let mut keys: Vec<String> = vec![#(#envs.to_string()),*];
+        #(keys.extend(<#inner_idents as envoke::Envoke>::env_keys());)*
+
+        let mut seen = std::collections::HashSet::new();
+        keys.retain(|key| seen.insert(key.clone()));
+        keys
+    };
+
+    // The loaded value is transformed before it is matched against the names
+    let name = enum_name.to_string();
+    let parse_call = match (&c_attrs.parse_fn, &c_attrs.try_parse_fn) {
+        (Some(parse_fn), _) => quote! { let value: String = #parse_fn(value); },
+        (_, Some(try_parse_fn)) => quote! {
+            let value: String = #try_parse_fn(value)
+                .map_err(|e| envoke::ParseError::failed(#name, e))?;
+        },
+        _ => quote! {},
+    };
+
     let (calls, default_call) = generate_variant_calls(enum_name, variants, c_attrs)?;
 
     let value_call = match default_call {
@@ -101,6 +233,7 @@ pub fn derive_for(input: DeriveInput) -> syn::Result<TokenStream> {
                 Ok(value) => value,
                 Err(_) => return Ok(#default)
             };
+            #parse_call
 
             let mut found = None;
             #(#calls);*
@@ -112,6 +245,7 @@ pub fn derive_for(input: DeriveInput) -> syn::Result<TokenStream> {
         },
         None => quote! {
             let value = #value_call?;
+            #parse_call
 
             let mut found = None;
             #(#calls);*
@@ -132,7 +266,15 @@ pub fn derive_for(input: DeriveInput) -> syn::Result<TokenStream> {
 
                 #value_call
             }
+
+            fn env_keys() -> Vec<String> {
+                #env_keys_call
+            }
         }
+
+        #json_impl
+
+        #env_line_impl
     };
 
     Ok(expanded)
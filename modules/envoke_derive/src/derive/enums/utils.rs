@@ -13,6 +13,60 @@ pub fn get_enum_data(data: Data) -> syn::Result<DataEnum> {
     }
 }
 
+pub fn generate_json_calls(
+    enum_name: &Ident,
+    variants: &[Variant],
+    c_attrs: &ContainerAttributes,
+) -> Vec<TokenStream> {
+    variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            match (&variant.inner_ident, variant.loaded_inner()) {
+                (Some(_), Some(_)) => quote! {
+                    #enum_name::#ident(inner) => inner.to_json_value_redacted()?
+                },
+                // Parsed from the remainder of the value, which may contain
+                // secrets, e.g., a connection string
+                (Some(_), None) => {
+                    let name = variant.canonical_name(c_attrs);
+
+                    quote! {
+                        #enum_name::#ident(_) => envoke::serde_json::Value::String(#name.to_string())
+                    }
+                }
+                (None, _) => {
+                    // Unit variants are represented by the name they are loaded with
+                    let name = variant.canonical_name(c_attrs);
+
+                    quote! {
+                        #enum_name::#ident => envoke::serde_json::Value::String(#name.to_string())
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+pub fn generate_env_value_calls(
+    enum_name: &Ident,
+    variants: &[Variant],
+    c_attrs: &ContainerAttributes,
+) -> Vec<TokenStream> {
+    variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            let name = variant.canonical_name(c_attrs);
+
+            match &variant.inner_ident {
+                Some(_) => quote! { #enum_name::#ident(_) => #name },
+                None => quote! { #enum_name::#ident => #name },
+            }
+        })
+        .collect()
+}
+
 pub fn generate_variant_calls(
     enum_name: &Ident,
     variants: Vec<Variant>,
@@ -21,7 +75,7 @@ pub fn generate_variant_calls(
     let mut calls = Vec::new();
     let mut default_call = None;
 
-    let mut existing_names = Vec::new();
+    let mut existing_names: Vec<String> = Vec::new();
     for variant in variants {
         let ident = &variant.ident;
         let inner_ident = &variant.inner_ident;
@@ -37,7 +91,14 @@ pub fn generate_variant_calls(
                 variant.attrs.no_suffix,
             );
 
-            if existing_names.contains(&new_name) {
+            // Names which only differ in case cannot be told apart
+            let is_used = match c_attrs.case_insensitive {
+                true => existing_names
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(&new_name)),
+                false => existing_names.contains(&new_name),
+            };
+            if is_used {
                 return Err(Error::already_used(format!("name::{}", name.value))
                     .to_syn_error(name.span.unwrap_or(variant.span)));
             }
@@ -47,14 +108,35 @@ pub fn generate_variant_calls(
         }
 
         // Generate match call
-        let call = match inner_ident {
-            Some(inner) => quote! {
-                if [#(#renamed),*].iter().any(|n| value.eq(n)) {
+        let is_match = match c_attrs.case_insensitive {
+            true => quote! { [#(#renamed),*].iter().any(|n| value.eq_ignore_ascii_case(n)) },
+            false => quote! { [#(#renamed),*].iter().any(|n| value.eq(n)) },
+        };
+        let is_match = match &variant.attrs.matches_prefix {
+            Some(prefix) => quote! { (#is_match || value.starts_with(#prefix)) },
+            None => is_match,
+        };
+        let call = match (inner_ident, &variant.attrs.matches_prefix) {
+            // Only the prefix selects the variant as there is no remainder
+            // to parse if the value is one of its names
+            (Some(inner), Some(prefix)) if variant.attrs.remainder_into_inner => {
+                let name = enum_name.to_string();
+                quote! {
+                    if let Some(remainder) = value.strip_prefix(#prefix) {
+                        let inner = remainder
+                            .parse::<#inner>()
+                            .map_err(|e| envoke::ParseError::failed(#name, e))?;
+                        found = Some(#enum_name::#ident(inner))
+                    }
+                }
+            }
+            (Some(inner), _) => quote! {
+                if #is_match {
                     found = Some(#enum_name::#ident(#inner::try_envoke()?))
                 }
             },
-            None => quote! {
-                if [#(#renamed),*].iter().any(|n| value.eq(n)) {
+            (None, _) => quote! {
+                if #is_match {
                     found = Some(#enum_name::#ident)
                 }
             },
@@ -2,7 +2,16 @@ use syn::{spanned::Spanned, DeriveInput};
 
 use quote::quote;
 
-use crate::{derive::common::Case, errors::Error, utils::find_closest_match};
+use super::utils::datetime_fns;
+use crate::{
+    derive::common::{Case, EnumBy, EnvPriority, Ranges, TimestampUnit},
+    errors::Error,
+    utils::{
+        find_closest_match, first_type_arg, is_collection, is_integer, is_map, is_optional,
+        is_phantom_data, is_std_collection, is_type, last_type_arg, optional_inner,
+        unsupported_shape,
+    },
+};
 
 #[derive(Debug, Default)]
 pub struct ContainerAttributes {
@@ -85,10 +94,14 @@ pub struct ContainerAttributes {
     /// delimiter. Although it can still be good to include the delimiter to
     /// separate the prefix/suffix from the original name!
     ///
+    /// Without a delimiter the prefix and suffix are joined to the name as is,
+    /// so `prefix = "APP"` loads `APPfield` while `prefix = "APP_"` or
+    /// `delimiter = "_"` loads `APP_field`.
+    ///
     /// See [ContainerAttributes::prefix] or [ContainerAttributes::suffix] for
     /// examples on how to use this attribute
     ///
-    /// **Default:** `"_"`
+    /// **Default:** `""`
     pub delimiter: Option<String>,
 
     /// Define a dotenv file to load and add to the struct fields
@@ -102,10 +115,114 @@ pub struct ContainerAttributes {
     ///
     /// **Default**: None
     pub dotenv: Option<String>,
+
+    /// Generate a `to_json_redacted` method which serializes the loaded struct
+    /// to JSON. Fields marked as [`FieldAttributes::sensitive`] are replaced
+    /// with `"***"`. Requires the `serde` feature
+    ///
+    /// **Default**: false
+    pub json: bool,
+
+    /// A JSON Schema file to validate the loaded struct against, relative to
+    /// the crate's manifest directory. The struct must implement
+    /// `serde::Serialize`. Requires the `schema` feature
+    ///
+    /// **Default**: None
+    pub schema: Option<String>,
+
+    /// An environment variable containing the whole struct as JSON, named with
+    /// the container's prefix, suffix and case. Fields whose own environment
+    /// variable is set take priority over the values in the JSON. Requires the
+    /// `serde` feature
+    ///
+    /// **Default**: None
+    pub config_json: Option<String>,
+
+    /// Generate an `impl TryFrom<HashMap<String, String>>` which loads the
+    /// struct from the given map instead of the process environment
+    ///
+    /// **Default**: false
+    pub from_map: bool,
+
+    /// Generate an `env_line` method which returns the `KEY=value` line of a
+    /// single field
+    ///
+    /// **Default**: false
+    pub env_line: bool,
+
+    /// Generate a `diff` method which returns the fields whose values differ
+    /// from another instance
+    ///
+    /// **Default**: false
+    pub diff: bool,
+
+    /// Generate a `reload` method which loads a new instance from the
+    /// environment, keeping the values of ignored fields
+    ///
+    /// **Default**: false
+    pub reload: bool,
+
+    /// Which of a field's environment variables takes precedence if several
+    /// are set, the first or the last one listed
+    ///
+    /// **Default**: `first`
+    pub env_priority: Option<EnvPriority>,
+
+    /// Generate a `global` method which returns a `'static` instance loaded
+    /// on first access
+    ///
+    /// **Default**: false
+    pub global: bool,
+
+    /// A function called if loading fails with a `RetrieveError`, e.g., to
+    /// populate the environment from a remote source, after which loading is
+    /// retried once. Must be a `fn() -> Result<(), E>` where `E` converts into
+    /// a boxed error
+    ///
+    /// **Default**: None
+    pub hydrate: Option<syn::Path>,
+
+    /// Fail to load if a variable starting with the prefix is set which is
+    /// not read by any field, e.g., to catch typos in variable names
+    ///
+    /// **Default**: false
+    pub deny_unknown: bool,
+
+    /// Read a variable which is not set from one whose name only differs in
+    /// case and separators, e.g., `app-port` for `APP_PORT`
+    ///
+    /// **Default**: false
+    pub fuzzy_names: bool,
+
+    /// Variables which have been removed, with an optional note on what
+    /// replaced them. Loading fails if any of them is set, e.g.,
+    /// `removed(env = "OLD_VAR", note = "use NEW_VAR")`
+    ///
+    /// **Default**: `[]`
+    pub removed: Vec<(String, Option<String>)>,
 }
 
 impl ContainerAttributes {
-    const VARIANTS: &[&str] = &["rename_all", "prefix", "suffix", "delimiter", "dotenv"];
+    const VARIANTS: &[&str] = &[
+        "rename_all",
+        "prefix",
+        "suffix",
+        "delimiter",
+        "dotenv",
+        "json",
+        "schema",
+        "config_json",
+        "from_map",
+        "env_line",
+        "diff",
+        "reload",
+        "env_priority",
+        "global",
+        "hydrate",
+        "deny_unknown",
+        "fuzzy_names",
+        "removed",
+    ];
 
     fn set_rename_all(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
         if self.rename_all.is_some() {
@@ -157,7 +274,207 @@ impl ContainerAttributes {
         Ok(())
     }
 
-    fn get_prefix(&self) -> &str {
+    fn set_json(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.json {
+            return Err(Error::duplicate_attribute("json").to_syn_error(meta.path.span()));
+        }
+
+        if cfg!(not(feature = "serde")) {
+            return Err(Error::invalid_attribute(
+                "json",
+                "requires the `serde` feature to be enabled",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.json = true;
+        Ok(())
+    }
+
+    fn set_schema(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.schema.is_some() {
+            return Err(Error::duplicate_attribute("schema").to_syn_error(meta.path.span()));
+        }
+
+        if cfg!(not(feature = "schema")) {
+            return Err(Error::invalid_attribute(
+                "schema",
+                "requires the `schema` feature to be enabled",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        let schema: syn::LitStr = meta.value()?.parse()?;
+        self.schema = Some(schema.value());
+        Ok(())
+    }
+
+    fn set_config_json(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.config_json.is_some() {
+            return Err(Error::duplicate_attribute("config_json").to_syn_error(meta.path.span()));
+        }
+
+        if cfg!(not(feature = "serde")) {
+            return Err(Error::invalid_attribute(
+                "config_json",
+                "requires the `serde` feature to be enabled",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        let config_json: syn::LitStr = meta.value()?.parse()?;
+        self.config_json = Some(config_json.value());
+        Ok(())
+    }
+
+    fn set_from_map(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.from_map {
+            return Err(Error::duplicate_attribute("from_map").to_syn_error(meta.path.span()));
+        }
+
+        self.from_map = true;
+        Ok(())
+    }
+
+    fn set_env_line(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.env_line {
+            return Err(Error::duplicate_attribute("env_line").to_syn_error(meta.path.span()));
+        }
+
+        self.env_line = true;
+        Ok(())
+    }
+
+    fn set_reload(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.reload {
+            return Err(Error::duplicate_attribute("reload").to_syn_error(meta.path.span()));
+        }
+
+        self.reload = true;
+        Ok(())
+    }
+
+    fn set_diff(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.diff {
+            return Err(Error::duplicate_attribute("diff").to_syn_error(meta.path.span()));
+        }
+
+        self.diff = true;
+        Ok(())
+    }
+
+    fn set_global(
+        &mut self,
+        input: &DeriveInput,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.global {
+            return Err(Error::duplicate_attribute("global").to_syn_error(meta.path.span()));
+        }
+
+        // A static cannot depend on the generics of the struct
+        if !input.generics.params.is_empty() {
+            return Err(
+                Error::invalid_attribute("global", "cannot be used on generic structs")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.global = true;
+        Ok(())
+    }
+
+    fn set_env_priority(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.env_priority.is_some() {
+            return Err(Error::duplicate_attribute("env_priority").to_syn_error(meta.path.span()));
+        }
+
+        let env_priority: EnvPriority = meta.value()?.parse()?;
+        self.env_priority = Some(env_priority);
+        Ok(())
+    }
+
+    fn set_hydrate(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.hydrate.is_some() {
+            return Err(Error::duplicate_attribute("hydrate").to_syn_error(meta.path.span()));
+        }
+
+        let hydrate: syn::Path = meta.value()?.parse()?;
+        self.hydrate = Some(hydrate);
+        Ok(())
+    }
+
+    fn set_deny_unknown(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.deny_unknown {
+            return Err(Error::duplicate_attribute("deny_unknown").to_syn_error(meta.path.span()));
+        }
+
+        self.deny_unknown = true;
+        Ok(())
+    }
+
+    fn set_fuzzy_names(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.fuzzy_names {
+            return Err(Error::duplicate_attribute("fuzzy_names").to_syn_error(meta.path.span()));
+        }
+
+        self.fuzzy_names = true;
+        Ok(())
+    }
+
+    fn add_removed(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        let span = meta.path.span();
+        let mut env = None;
+        let mut note = None;
+        meta.parse_nested_meta(|meta| {
+            let ident = meta.path.get_ident();
+            let ident = quote! { #ident }.to_string();
+            let target = match ident.as_str() {
+                "env" => &mut env,
+                "note" => &mut note,
+                _ => {
+                    return Err(Error::unexpected_attribute(
+                        format!("removed::{ident}"),
+                        find_closest_match(&ident, &["env", "note"]),
+                    )
+                    .to_syn_error(meta.path.span()));
+                }
+            };
+
+            if target.is_some() {
+                return Err(Error::duplicate_attribute(format!("removed::{ident}"))
+                    .to_syn_error(meta.path.span()));
+            }
+
+            let str: syn::LitStr = meta.value()?.parse()?;
+            *target = Some(str.value());
+            Ok(())
+        })?;
+
+        let Some(env) = env.filter(|env| !env.is_empty()) else {
+            return Err(Error::missing_attribute(
+                "removed::env",
+                "the name of the removed variable is required, e.g., `removed(env = \"OLD_VAR\")`",
+            )
+            .to_syn_error(span));
+        };
+
+        if self.removed.iter().any(|(removed, _)| *removed == env) {
+            return Err(Error::duplicate_attribute(format!("removed::{env}")).to_syn_error(span));
+        }
+
+        self.removed.push((env, note));
+        Ok(())
+    }
+
+    /// Returns the prefix variables are checked against by `deny_unknown` and
+    /// collected by a `rest` field, i.e., the prefix followed by the delimiter
+    /// as written
+    pub fn unknown_prefix(&self) -> String {
+        format!("{}{}", self.get_prefix(), self.get_delimiter())
+    }
+
+    pub fn get_prefix(&self) -> &str {
         self.prefix.as_deref().unwrap_or_default()
     }
 
@@ -165,27 +482,39 @@ impl ContainerAttributes {
         self.suffix.as_deref().unwrap_or_default()
     }
 
+    /// Returns the delimiter, which is empty unless set so a prefix such as
+    /// `APP_` can carry its own separator
     fn get_delimiter(&self) -> &str {
         self.delimiter.as_deref().unwrap_or_default()
     }
 
-    pub fn rename(&self, original: String, no_prefix: bool, no_suffix: bool) -> String {
+    /// Renames `original` with the container's prefix, suffix and case, unless
+    /// overridden by the field's own
+    pub fn rename(
+        &self,
+        original: String,
+        no_prefix: bool,
+        no_suffix: bool,
+        prefix_override: Option<&str>,
+        suffix_override: Option<&str>,
+        case_override: Option<&Case>,
+    ) -> String {
         let delim = self.get_delimiter();
         let prefix = if !no_prefix {
-            format!("{}{delim}", self.get_prefix())
+            format!("{}{delim}", prefix_override.unwrap_or(self.get_prefix()))
         } else {
             String::new()
         };
 
         let suffix = if !no_suffix {
-            format!("{delim}{}", self.get_suffix())
+            format!("{delim}{}", suffix_override.unwrap_or(self.get_suffix()))
         } else {
             String::new()
         };
 
         let renamed = format!("{prefix}{original}{suffix}");
 
-        if let Some(case) = &self.rename_all {
+        if let Some(case) = case_override.or(self.rename_all.as_ref()) {
             case.rename(&renamed)
         } else {
             renamed
@@ -214,6 +543,19 @@ impl TryFrom<&DeriveInput> for ContainerAttributes {
                     "suffix" => ca.set_suffix(meta),
                     "delimiter" => ca.set_delimiter(meta),
                     "dotenv" => ca.set_dotenv(meta),
+                    "json" => ca.set_json(meta),
+                    "schema" => ca.set_schema(meta),
+                    "config_json" => ca.set_config_json(meta),
+                    "from_map" => ca.set_from_map(meta),
+                    "env_line" => ca.set_env_line(meta),
+                    "diff" => ca.set_diff(meta),
+                    "reload" => ca.set_reload(meta),
+                    "env_priority" => ca.set_env_priority(meta),
+                    "global" => ca.set_global(input, meta),
+                    "hydrate" => ca.set_hydrate(meta),
+                    "deny_unknown" => ca.set_deny_unknown(meta),
+                    "fuzzy_names" => ca.set_fuzzy_names(meta),
+                    "removed" => ca.add_removed(meta),
                     _ => {
                         let closest_match = find_closest_match(&ident, Self::VARIANTS);
                         Err(Error::unexpected_attribute(ident, closest_match)
@@ -225,6 +567,23 @@ impl TryFrom<&DeriveInput> for ContainerAttributes {
             })?;
         }
 
+        // Without a prefix every variable in the environment would be unknown
+        if ca.deny_unknown && ca.get_prefix().is_empty() {
+            return Err(
+                Error::missing_attribute("prefix", "required if `deny_unknown` is set")
+                    .to_syn_error(input.span()),
+            );
+        }
+
+        // A variable read through its fuzzy name would be unknown
+        if ca.deny_unknown && ca.fuzzy_names {
+            return Err(Error::invalid_attribute(
+                "deny_unknown",
+                "cannot be used together with `fuzzy_names`",
+            )
+            .to_syn_error(input.span()));
+        }
+
         Ok(ca)
     }
 }
@@ -238,6 +597,34 @@ pub enum DefaultValue {
         path: syn::ExprPath,
         args: Vec<syn::Expr>,
     },
+    /// A function taking an `envoke::DefaultCtx`, set by `default_fn`
+    Fn(syn::Path),
+}
+
+/// Argument of a default function which is replaced by the first resolved
+/// environment variable name of the field
+pub const ENV_NAME_ARG: &str = "env_name";
+
+/// Argument of a default function which is replaced by all resolved
+/// environment variable names of the field
+pub const ENV_NAMES_ARG: &str = "env_names";
+
+/// Checks if the argument is the bare identifier `name`
+pub fn is_arg(arg: &syn::Expr, name: &str) -> bool {
+    matches!(arg, syn::Expr::Path(path) if path.path.is_ident(name))
+}
+
+impl DefaultValue {
+    /// Checks if the default function takes the environment variable name(s)
+    /// of the field as an argument
+    fn uses_env_name(&self) -> bool {
+        match self {
+            DefaultValue::Call { args, .. } => args
+                .iter()
+                .any(|arg| is_arg(arg, ENV_NAME_ARG) || is_arg(arg, ENV_NAMES_ARG)),
+            _ => false,
+        }
+    }
 }
 
 impl syn::parse::Parse for DefaultValue {
@@ -331,6 +718,134 @@ impl ValidateFn {
     }
 }
 
+#[derive(Debug)]
+pub struct RequiredIf {
+    /// The name of the field the condition depends on
+    pub field: syn::LitStr,
+
+    /// The value the other field is compared against
+    pub equals: syn::Expr,
+}
+
+impl RequiredIf {
+    const VARIANTS: &[&str] = &["field", "equals"];
+
+    fn from_nested_meta(meta: syn::meta::ParseNestedMeta) -> syn::Result<Self> {
+        let mut field: Option<syn::LitStr> = None;
+        let mut equals: Option<syn::Expr> = None;
+
+        meta.parse_nested_meta(|meta| {
+            let ident = meta.path.get_ident();
+            let ident = quote! { #ident }.to_string();
+
+            match ident.as_ref() {
+                "field" => {
+                    if field.is_some() {
+                        return Err(Error::duplicate_attribute("required_if::field")
+                            .to_syn_error(meta.path.span()));
+                    }
+
+                    field = Some(meta.value()?.parse()?);
+                    Ok(())
+                }
+                "equals" => {
+                    if equals.is_some() {
+                        return Err(Error::duplicate_attribute("required_if::equals")
+                            .to_syn_error(meta.path.span()));
+                    }
+
+                    equals = Some(meta.value()?.parse()?);
+                    Ok(())
+                }
+                _ => {
+                    let closest_match = find_closest_match(&ident, Self::VARIANTS);
+                    Err(Error::unexpected_attribute(ident, closest_match)
+                        .to_syn_error(meta.path.span()))
+                }
+            }
+        })?;
+
+        let Some(field) = field else {
+            return Err(Error::missing_attribute(
+                "required_if::field",
+                "the field to compare against",
+            )
+            .to_syn_error(meta.path.span()));
+        };
+
+        let Some(equals) = equals else {
+            return Err(Error::missing_attribute(
+                "required_if::equals",
+                "the value to compare against",
+            )
+            .to_syn_error(meta.path.span()));
+        };
+
+        Ok(Self { field, equals })
+    }
+}
+
+#[derive(Debug)]
+pub struct UrlRules {
+    /// The schemes the URL may have, e.g., `http` and `https`
+    pub schemes: Vec<syn::LitStr>,
+}
+
+impl UrlRules {
+    const VARIANTS: &[&str] = &["schemes"];
+
+    fn from_nested_meta(meta: syn::meta::ParseNestedMeta) -> syn::Result<Self> {
+        let mut schemes: Option<Vec<syn::LitStr>> = None;
+
+        meta.parse_nested_meta(|meta| {
+            let ident = meta.path.get_ident();
+            let ident = quote! { #ident }.to_string();
+
+            match ident.as_ref() {
+                "schemes" => {
+                    if schemes.is_some() {
+                        return Err(Error::duplicate_attribute("url::schemes")
+                            .to_syn_error(meta.path.span()));
+                    }
+
+                    let array: syn::ExprArray = meta.value()?.parse()?;
+                    let values = array
+                        .elems
+                        .into_iter()
+                        .map(|elem| match elem {
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(scheme),
+                                ..
+                            }) => Ok(scheme),
+                            elem => Err(Error::invalid_attribute(
+                                "url::schemes",
+                                "schemes must be string literals, e.g., `[\"http\", \"https\"]`",
+                            )
+                            .to_syn_error(elem.span())),
+                        })
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    schemes = Some(values);
+                    Ok(())
+                }
+                _ => {
+                    let closest_match = find_closest_match(&ident, Self::VARIANTS);
+                    Err(Error::unexpected_attribute(ident, closest_match)
+                        .to_syn_error(meta.path.span()))
+                }
+            }
+        })?;
+
+        match schemes {
+            Some(schemes) if !schemes.is_empty() => Ok(Self { schemes }),
+            _ => Err(Error::missing_attribute(
+                "url::schemes",
+                "the allowed schemes, e.g., `schemes = [\"http\", \"https\"]`",
+            )
+            .to_syn_error(meta.path.span())),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct FieldAttributes {
     /// Environment variables to load the field value from.
@@ -342,12 +857,41 @@ pub struct FieldAttributes {
     /// **Default:** `None`.
     pub envs: Option<Vec<String>>,
 
+    /// Deprecated environment variables to load the field value from.
+    ///
+    /// These are checked after `envs` and a warning is emitted if the value
+    /// is loaded from one of them.
+    ///
+    /// **Default:** `[]`.
+    pub deprecated_envs: Vec<String>,
+
+    /// Additional environment variables to load the field value from.
+    ///
+    /// These are appended to `envs` after the canonical names, but are kept
+    /// here as well to tell them apart.
+    ///
+    /// **Default:** `[]`.
+    pub aliases: Vec<String>,
+
+    /// Path of a file to read the value from if none of the environment
+    /// variables are set, e.g., a secret mounted at `/run/secrets/db_password`
+    ///
+    /// **Default:** `None`
+    pub file: Option<String>,
+
+    /// Hint shown in the warning when the value is loaded from a deprecated
+    /// environment variable, e.g., `use NEW_NAME instead`
+    ///
+    /// **Default:** `None`
+    pub deprecated: Option<String>,
+
     /// Use the default value if the environment variable is not found
     ///
     /// This function can be used without specifying `envs` to provide a static
     /// fallback.
     ///
     /// **Default:** `None`
+    /// Set by both `default` and `default_fn`, see [`DefaultValue::Fn`]
     pub default: Option<DefaultValue>,
 
     /// A function to parse the loaded value with before applying to the field.
@@ -373,17 +917,78 @@ pub struct FieldAttributes {
     /// **Default:** `None`
     pub arg_type: Option<syn::Type>,
 
+    /// Convert the value parsed as `arg_type` into the field type with
+    /// `Into::into`. Requires `arg_type` to be set if used.
+    ///
+    /// **Default:** `false`
+    pub into: bool,
+
+    /// A function with the signature of a serde `deserialize_with` function
+    /// which the value is deserialized with. Requires the `serde` feature
+    ///
+    /// **Default:** `None`
+    pub deserialize_with: Option<syn::Path>,
+
+    /// A function to convert each element of a collection with before it is
+    /// added to the collection. For maps it is applied to the values. Requires
+    /// `item_arg_type` to be set if used.
+    ///
+    /// **Default:** `None`
+    pub item_parse_fn: Option<syn::Path>,
+
+    /// Arg type in the item_parse_fn function. Required by `item_parse_fn` if
+    /// used.
+    ///
+    /// **Default:** `None`
+    pub item_arg_type: Option<syn::Type>,
+
     /// A function to call after the value is loaded and parsed for extra
     /// validations, e.g., ensuring i64 is above 0
     ///
     /// **Default:** `None`
     pub validate_fn: ValidateFn,
 
+    /// The values the loaded value is allowed to be equal to. Compared with
+    /// the parsed value, so the values must be of the field's type
+    ///
+    /// **Default:** `None`
+    pub one_of: Option<Vec<syn::Expr>>,
+
+    /// The step the loaded integer must be a multiple of, e.g., a page size
+    ///
+    /// **Default:** `None`
+    pub multiple_of: Option<syn::LitInt>,
+
     /// Delimiter used when parsing list-type fields (e.g., `Vec<String>`).
     ///
     /// **Default:** `","`
     pub delimiter: Option<String>,
 
+    /// Delimiter between the key and value of each entry when parsing
+    /// map-type fields (e.g., `HashMap<String, String>`). Cannot be the same
+    /// as [`FieldAttributes::delimiter`]
+    ///
+    /// **Default:** `"="`
+    pub kv_delimiter: Option<String>,
+
+    /// Converts the keys of map-type fields to the specified case format
+    /// after parsing, e.g., to normalize header names. The keys must be
+    /// `String`s.
+    ///
+    /// See [Case] for a full list of supported cases
+    ///
+    /// **Default:** `None`
+    pub rename_keys: Option<Case>,
+
+    /// Converts the environment variable names of this field to the specified
+    /// case format instead of the one given by
+    /// [`ContainerAttributes::rename_all`]
+    ///
+    /// See [Case] for a full list of supported cases
+    ///
+    /// **Default:** `None`
+    pub case: Option<Case>,
+
     /// Disable adding prefix to this environment variables. This will also
     /// remove the delimiter that wouldn't normally be between the environment
     /// variable and prefix
@@ -398,29 +1003,275 @@ pub struct FieldAttributes {
     /// **Default:** `false`
     pub no_suffix: bool,
 
+    /// Prefix to use for this field instead of the container's prefix. Cannot
+    /// be used together with `no_prefix`
+    ///
+    /// **Default:** `None`
+    pub prefix: Option<String>,
+
+    /// Suffix to use for this field instead of the container's suffix. Cannot
+    /// be used together with `no_suffix`
+    ///
+    /// **Default:** `None`
+    pub suffix: Option<String>,
+
     /// Indicates the the field is a nested struct in which the parser needs to
     /// call try_envoke on
     ///
     /// **Default**: false
     pub is_nested: bool,
 
-    /// Indicates that the field should not be done anything with
+    /// Indicates that the field should not be done anything with. The field is
+    /// `None`, or its default if one is given
     pub is_ignore: bool,
+
+    /// Marks the field as containing sensitive data which should never be
+    /// shown, e.g., it is replaced with `"***"` in `to_json_redacted` and
+    /// with `"[redacted]"` in parse errors
+    ///
+    /// **Default**: false
+    pub sensitive: bool,
+
+    /// Expand a leading `~` and `$VAR`/`${VAR}` references in the loaded value
+    /// using the process environment before converting it to a path
+    ///
+    /// **Default**: false
+    pub expand_path: bool,
+
+    /// A regex with named capture groups which the loaded value is matched
+    /// against. Each capture is assigned to the nested struct's field with the
+    /// same name. Requires the `regex` feature
+    ///
+    /// **Default**: `None`
+    pub pattern: Option<String>,
+
+    /// Load the nested struct from the `KEY=VALUE` lines of the `env` value
+    /// like a dotenv file instead of from the environment
+    ///
+    /// **Default**: false
+    pub block: bool,
+
+    /// Load a map of nested structs from the variables named
+    /// `{map_prefix}{key}_{name}`, where `name` is a variable read by the
+    /// nested struct
+    ///
+    /// **Default**: `None`
+    pub map_prefix: Option<String>,
+
+    /// Names of other fields which cannot be set in the environment at the
+    /// same time as this field
+    ///
+    /// **Default**: `[]`
+    pub conflicts_with: Vec<syn::LitStr>,
+
+    /// Fields to assign the elements of the tuple returned by `parse_fn` or
+    /// `try_parse_fn` to, in order. Must include this field
+    ///
+    /// **Default**: `None`
+    pub split_into: Option<Vec<syn::Ident>>,
+
+    /// The field whose `split_into` assigns this field. Set when the struct
+    /// is derived, not by an attribute
+    ///
+    /// **Default**: `None`
+    pub split_from: Option<syn::Ident>,
+
+    /// Require the field to be set in the environment if another field is
+    /// equal to the given value
+    ///
+    /// **Default**: `None`
+    pub required_if: Option<RequiredIf>,
+
+    /// Characters to strip from the loaded value before parsing it as an
+    /// integer, e.g., to allow `1_000_000`. Always includes `_`
+    ///
+    /// **Default**: `None`
+    pub group_separators: Option<String>,
+
+    /// Accept `,` as the decimal separator of a float, e.g., `1,5`
+    ///
+    /// **Default**: false
+    pub decimal_comma: bool,
+
+    /// Reject a sequence or set whose value contains the same element more
+    /// than once, instead of keeping or silently dropping the duplicate
+    ///
+    /// **Default**: false
+    pub unique: bool,
+
+    /// Validate the scheme of a `url::Url` field after parsing it
+    ///
+    /// **Default**: None
+    pub url: Option<UrlRules>,
+
+    /// Prefix to strip from the loaded value before parsing it, if present,
+    /// e.g., `Bearer ` in `Bearer xyz`. Elements of a collection are stripped
+    /// one at a time
+    ///
+    /// **Default**: `None`
+    pub trim_prefix: Option<String>,
+
+    /// Suffix to strip from the loaded value before parsing it, if present.
+    /// Elements of a collection are stripped one at a time
+    ///
+    /// **Default**: `None`
+    pub trim_suffix: Option<String>,
+
+    /// Keep empty elements when parsing a sequence instead of returning an
+    /// error, e.g., `a,,c` is parsed into `["a", "", "c"]`
+    ///
+    /// **Default**: false
+    pub keep_empty: bool,
+
+    /// Parse the value as a duration with units, e.g., `1h 30m`. For
+    /// collections each element is parsed as a duration
+    ///
+    /// **Default**: false
+    pub duration: bool,
+
+    /// Parse the value as an RFC 3339 date-time, e.g., `2024-05-01T12:00:00Z`,
+    /// into a `chrono` or `time` date-time
+    ///
+    /// **Default**: false
+    pub datetime: bool,
+
+    /// Parse the value as a unix timestamp into a `SystemTime`, counting
+    /// `seconds`, the default, or `millis` since the unix epoch
+    ///
+    /// **Default**: None
+    pub unix_timestamp: Option<TimestampUnit>,
+
+    /// How an enum value is represented, either `name` or `name_or_index`.
+    /// For collections each element is parsed this way
+    ///
+    /// **Default**: `None`
+    pub enum_by: Option<EnumBy>,
+
+    /// Use the value from the dotenv file over the one in the process
+    /// environment if both are set
+    ///
+    /// **Default**: false
+    pub prefer_dotenv: bool,
+
+    /// Read the environment variables outside of any runtime prefix scope,
+    /// e.g., a shared variable in a struct loaded with `map_prefix`
+    ///
+    /// **Default**: false
+    pub global: bool,
+
+    /// Parse integers written with a `0x`, `0o`, or `0b` prefix in the
+    /// corresponding radix, e.g., `0xFF`. Underscores are ignored
+    ///
+    /// **Default**: false
+    pub radix_auto: bool,
+
+    /// Split the value into a list and collect it into a map keyed by the
+    /// position of each element, e.g., `a,b` into `{0: "a", 1: "b"}`
+    ///
+    /// **Default**: false
+    pub enumerate: bool,
+
+    /// Store the loaded value in an `envoke::Lazy` and parse it on first
+    /// access instead of when loading
+    ///
+    /// **Default**: false
+    pub lazy_parse: bool,
+
+    /// Types to load the boxed trait object from, keyed by the value of `env`
+    /// selecting them, e.g., `dispatch(a = TypeA, b = TypeB)`
+    ///
+    /// **Default**: `[]`
+    pub dispatch: Vec<(String, syn::Type)>,
+
+    /// Set the `bool` field to whether any of the environment variables are
+    /// set, no matter their value
+    ///
+    /// **Default**: false
+    pub present: bool,
+
+    /// Parse the `bool` field as a flag, where an empty value means `true`
+    ///
+    /// **Default**: false
+    pub flag: bool,
+
+    /// Parse each element of a sequence as a single value or an inclusive
+    /// range, e.g., `80,8000-8100`, and keep or flatten the ranges
+    ///
+    /// **Default**: `None`
+    pub ranges: Option<Ranges>,
+
+    /// Key of the parser in the `ParserRegistry` passed to
+    /// `try_envoke_with_registry` to parse the value with
+    ///
+    /// **Default**: `None`
+    pub parser: Option<String>,
+
+    /// Collect the variables starting with the container prefix which are not
+    /// read by any other field into the map, keyed by the rest of their name
+    ///
+    /// **Default**: false
+    pub is_rest: bool,
 }
 
 impl FieldAttributes {
     const VARIANTS: &[&str] = &[
         "env",
+        "alias",
+        "file",
+        "split_into",
+        "deprecated_env",
+        "deprecated",
         "default",
+        "default_fn",
         "parse_fn",
         "try_parse_fn",
         "arg_type",
+        "into",
+        "deserialize_with",
+        "item_parse_fn",
+        "item_arg_type",
         "validate_fn",
+        "one_of",
+        "multiple_of",
         "delimiter",
+        "kv_delimiter",
+        "rename_keys",
+        "case",
         "no_prefix",
         "no_suffix",
+        "prefix",
+        "suffix",
         "nested",
         "ignore",
+        "sensitive",
+        "expand_path",
+        "pattern",
+        "block",
+        "map_prefix",
+        "conflicts_with",
+        "required_if",
+        "group_separators",
+        "decimal_comma",
+        "unique",
+        "url",
+        "trim_prefix",
+        "trim_suffix",
+        "keep_empty",
+        "duration",
+        "datetime",
+        "unix_timestamp",
+        "enum_by",
+        "prefer_dotenv",
+        "global",
+        "radix_auto",
+        "enumerate",
+        "lazy_parse",
+        "dispatch",
+        "present",
+        "flag",
+        "ranges",
+        "parser",
+        "rest",
     ];
 
     fn add_env(&mut self, field: &syn::Field, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
@@ -444,8 +1295,15 @@ impl FieldAttributes {
                 env
             }
             false => {
-                let ident = &field.ident;
-                let env = quote! { #ident }.to_string();
+                // Fields of tuple structs have no name to fall back on
+                let Some(ident) = &field.ident else {
+                    return Err(Error::missing_attribute(
+                        "env",
+                        "a name is required for fields of tuple structs, e.g., `env = \"PORT\"`",
+                    )
+                    .to_syn_error(meta.path.span()));
+                };
+                let env = ident.to_string();
 
                 if self.envs.as_ref().is_some_and(|e| e.contains(&env)) {
                     return Err(Error::duplicate_attribute(format!("env::{env}"))
@@ -460,11 +1318,100 @@ impl FieldAttributes {
         Ok(())
     }
 
+    fn add_alias(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let alias = str.value();
+        if alias.is_empty() {
+            return Err(
+                Error::invalid_attribute("alias", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        let is_duplicate = self.aliases.contains(&alias)
+            || self.deprecated_envs.contains(&alias)
+            || self.envs.as_ref().is_some_and(|e| e.contains(&alias));
+        if is_duplicate {
+            return Err(
+                Error::duplicate_attribute(format!("env::{alias}")).to_syn_error(meta.path.span())
+            );
+        }
+
+        self.aliases.push(alias);
+        Ok(())
+    }
+
+    fn set_file(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.file.is_some() {
+            return Err(Error::duplicate_attribute("file").to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let file = str.value();
+        if file.is_empty() {
+            return Err(
+                Error::invalid_attribute("file", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.file = Some(file);
+        Ok(())
+    }
+
+    fn add_deprecated_env(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let env = str.value();
+        if env.is_empty() {
+            return Err(
+                Error::invalid_attribute("deprecated_env", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        let is_duplicate = self.deprecated_envs.contains(&env)
+            || self.envs.as_ref().is_some_and(|e| e.contains(&env));
+        if is_duplicate {
+            return Err(
+                Error::duplicate_attribute(format!("env::{env}")).to_syn_error(meta.path.span())
+            );
+        }
+
+        self.deprecated_envs.push(env);
+        Ok(())
+    }
+
+    fn set_deprecated(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.deprecated.is_some() {
+            return Err(Error::duplicate_attribute("deprecated").to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let deprecated = str.value();
+        if deprecated.is_empty() {
+            return Err(
+                Error::invalid_attribute("deprecated", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.deprecated = Some(deprecated);
+        Ok(())
+    }
+
     fn set_default(
         &mut self,
         field: &syn::Field,
         meta: syn::meta::ParseNestedMeta,
     ) -> syn::Result<()> {
+        if matches!(self.default, Some(DefaultValue::Fn(_))) {
+            return Err(Error::invalid_attribute(
+                "default",
+                "cannot be used together with `default_fn`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
         if self.default.is_some() {
             return Err(Error::duplicate_attribute("default").to_syn_error(meta.path.span()));
         }
@@ -480,6 +1427,26 @@ impl FieldAttributes {
         Ok(())
     }
 
+    fn set_default_fn(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        match &self.default {
+            Some(DefaultValue::Fn(_)) => {
+                return Err(Error::duplicate_attribute("default_fn").to_syn_error(meta.path.span()));
+            }
+            Some(_) => {
+                return Err(Error::invalid_attribute(
+                    "default_fn",
+                    "cannot be used together with `default`",
+                )
+                .to_syn_error(meta.path.span()));
+            }
+            None => {}
+        }
+
+        let default_fn: syn::Path = meta.value()?.parse()?;
+        self.default = Some(DefaultValue::Fn(default_fn));
+        Ok(())
+    }
+
     fn set_parse_fn(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
         if self.parse_fn.is_some() {
             return Err(Error::invalid_attribute(
@@ -523,106 +1490,915 @@ impl FieldAttributes {
         Ok(())
     }
 
-    fn set_validate_fn(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
-        if self.validate_fn.before.is_some() || self.validate_fn.after.is_some() {
-            return Err(Error::duplicate_attribute("validate_fn").to_syn_error(meta.path.span()));
+    fn set_rename_keys(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.rename_keys.is_some() {
+            return Err(Error::duplicate_attribute("rename_keys").to_syn_error(meta.path.span()));
         }
 
-        self.validate_fn = match meta.input.peek(syn::Token![=]) {
-            true => ValidateFn::from_direct_assignment(meta),
-            false => ValidateFn::from_nested_meta(meta),
-        }?;
+        let case: Case = meta.value()?.parse()?;
+        self.rename_keys = Some(case);
         Ok(())
     }
 
-    fn set_delimiter(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
-        if self.delimiter.is_some() {
-            return Err(Error::duplicate_attribute("delimiter").to_syn_error(meta.path.span()));
+    fn set_case(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.case.is_some() {
+            return Err(Error::duplicate_attribute("case").to_syn_error(meta.path.span()));
         }
 
-        let str: syn::LitStr = meta.value()?.parse()?;
-        let delimiter = str.value();
-        if delimiter == "=" {
-            return Err(
-                Error::invalid_attribute("delimiter", "delimiter reserved by the macro")
-                    .to_syn_error(meta.path.span()),
-            );
+        let case: Case = meta.value()?.parse()?;
+        self.case = Some(case);
+        Ok(())
+    }
+
+    fn set_into(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.into {
+            return Err(Error::duplicate_attribute("into").to_syn_error(meta.path.span()));
         }
 
-        if delimiter.is_empty() {
+        self.into = true;
+        Ok(())
+    }
+
+    fn set_deserialize_with(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.deserialize_with.is_some() {
             return Err(
-                Error::invalid_attribute("delimiter", "attribute cannot be empty")
-                    .to_syn_error(meta.path.span()),
+                Error::duplicate_attribute("deserialize_with").to_syn_error(meta.path.span())
             );
         }
 
-        self.delimiter = Some(delimiter);
+        if cfg!(not(feature = "serde")) {
+            return Err(Error::invalid_attribute(
+                "deserialize_with",
+                "requires the `serde` feature to be enabled",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.deserialize_with = Some(meta.value()?.parse()?);
         Ok(())
     }
 
-    fn disable_prefix(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
-        if self.no_prefix {
-            return Err(Error::duplicate_attribute("no_prefix").to_syn_error(meta.path.span()));
+    fn set_item_parse_fn(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.item_parse_fn.is_some() {
+            return Err(Error::duplicate_attribute("item_parse_fn").to_syn_error(meta.path.span()));
         }
 
-        self.no_prefix = true;
+        self.item_parse_fn = Some(meta.value()?.parse()?);
         Ok(())
     }
 
-    fn disable_suffix(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
-        if self.no_suffix {
-            return Err(Error::duplicate_attribute("no_suffix").to_syn_error(meta.path.span()));
+    fn set_item_arg_type(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.item_arg_type.is_some() {
+            return Err(Error::duplicate_attribute("item_arg_type").to_syn_error(meta.path.span()));
         }
 
-        self.no_suffix = true;
+        self.item_arg_type = Some(meta.value()?.parse()?);
         Ok(())
     }
 
-    fn set_nested(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
-        if self.is_nested {
-            return Err(Error::duplicate_attribute("nested").to_syn_error(meta.path.span()));
+    fn set_validate_fn(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.validate_fn.before.is_some() || self.validate_fn.after.is_some() {
+            return Err(Error::duplicate_attribute("validate_fn").to_syn_error(meta.path.span()));
         }
 
-        self.is_nested = true;
+        self.validate_fn = match meta.input.peek(syn::Token![=]) {
+            true => ValidateFn::from_direct_assignment(meta),
+            false => ValidateFn::from_nested_meta(meta),
+        }?;
         Ok(())
     }
 
-    fn set_ignore(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
-        if self.is_nested {
-            return Err(Error::duplicate_attribute("ignore").to_syn_error(meta.path.span()));
+    fn set_one_of(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.one_of.is_some() {
+            return Err(Error::duplicate_attribute("one_of").to_syn_error(meta.path.span()));
         }
 
-        self.is_ignore = true;
+        let array: syn::ExprArray = meta.value()?.parse()?;
+        if array.elems.is_empty() {
+            return Err(Error::invalid_attribute(
+                "one_of",
+                "requires at least one value, e.g., `one_of = [\"dev\", \"prod\"]`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.one_of = Some(array.elems.into_iter().collect());
         Ok(())
     }
-}
 
-impl TryFrom<&syn::Field> for FieldAttributes {
-    type Error = syn::Error;
+    fn set_multiple_of(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.multiple_of.is_some() {
+            return Err(Error::duplicate_attribute("multiple_of").to_syn_error(meta.path.span()));
+        }
 
-    fn try_from(field: &syn::Field) -> Result<Self, Self::Error> {
-        let mut fa = FieldAttributes::default();
-        for attr in &field.attrs {
-            if !attr.path().is_ident("fill") {
-                continue;
-            }
+        if !is_integer(optional_inner(&field.ty).unwrap_or(&field.ty)) {
+            return Err(Error::invalid_attribute(
+                "multiple_of",
+                "can only be used on integer fields",
+            )
+            .to_syn_error(meta.path.span()));
+        }
 
-            attr.parse_nested_meta(|meta| {
-                let ident = meta.path.get_ident();
-                let ident = quote! { #ident }.to_string();
+        let step: syn::LitInt = meta.value()?.parse()?;
+        if step.base10_parse::<u128>()? == 0 {
+            return Err(
+                Error::invalid_attribute("multiple_of", "the step cannot be zero")
+                    .to_syn_error(step.span()),
+            );
+        }
 
-                match ident.as_ref() {
+        self.multiple_of = Some(step);
+        Ok(())
+    }
+
+    fn set_delimiter(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.delimiter.is_some() {
+            return Err(Error::duplicate_attribute("delimiter").to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let delimiter = str.value();
+        if delimiter.is_empty() {
+            return Err(
+                Error::invalid_attribute("delimiter", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.delimiter = Some(delimiter);
+        Ok(())
+    }
+
+    fn set_kv_delimiter(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.kv_delimiter.is_some() {
+            return Err(Error::duplicate_attribute("kv_delimiter").to_syn_error(meta.path.span()));
+        }
+
+        if !is_map(optional_inner(&field.ty).unwrap_or(&field.ty)) {
+            return Err(Error::invalid_attribute(
+                "kv_delimiter",
+                "can only be used on maps, e.g., `HashMap<String, String>`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let kv_delimiter = str.value();
+        if kv_delimiter.is_empty() {
+            return Err(
+                Error::invalid_attribute("kv_delimiter", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.kv_delimiter = Some(kv_delimiter);
+        Ok(())
+    }
+
+    fn disable_prefix(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.no_prefix {
+            return Err(Error::duplicate_attribute("no_prefix").to_syn_error(meta.path.span()));
+        }
+
+        self.no_prefix = true;
+        Ok(())
+    }
+
+    fn disable_suffix(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.no_suffix {
+            return Err(Error::duplicate_attribute("no_suffix").to_syn_error(meta.path.span()));
+        }
+
+        self.no_suffix = true;
+        Ok(())
+    }
+
+    fn set_prefix(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.prefix.is_some() {
+            return Err(Error::duplicate_attribute("prefix").to_syn_error(meta.path.span()));
+        }
+
+        let prefix: syn::LitStr = meta.value()?.parse()?;
+        self.prefix = Some(prefix.value());
+        Ok(())
+    }
+
+    fn set_suffix(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.suffix.is_some() {
+            return Err(Error::duplicate_attribute("suffix").to_syn_error(meta.path.span()));
+        }
+
+        let suffix: syn::LitStr = meta.value()?.parse()?;
+        self.suffix = Some(suffix.value());
+        Ok(())
+    }
+
+    fn set_nested(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.is_nested {
+            return Err(Error::duplicate_attribute("nested").to_syn_error(meta.path.span()));
+        }
+
+        self.is_nested = true;
+        Ok(())
+    }
+
+    fn set_ignore(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.is_nested {
+            return Err(Error::duplicate_attribute("ignore").to_syn_error(meta.path.span()));
+        }
+
+        self.is_ignore = true;
+        Ok(())
+    }
+
+    fn set_sensitive(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.sensitive {
+            return Err(Error::duplicate_attribute("sensitive").to_syn_error(meta.path.span()));
+        }
+
+        self.sensitive = true;
+        Ok(())
+    }
+
+    fn set_expand_path(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.expand_path {
+            return Err(Error::duplicate_attribute("expand_path").to_syn_error(meta.path.span()));
+        }
+
+        self.expand_path = true;
+        Ok(())
+    }
+
+    fn set_split_into(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.split_into.is_some() {
+            return Err(Error::duplicate_attribute("split_into").to_syn_error(meta.path.span()));
+        }
+
+        let mut names: Vec<syn::Ident> = Vec::new();
+        meta.parse_nested_meta(|meta| {
+            let Some(name) = meta.path.get_ident() else {
+                return Err(Error::invalid_attribute(
+                    "split_into",
+                    "expected field names, e.g., `split_into(host, port)`",
+                )
+                .to_syn_error(meta.path.span()));
+            };
+
+            if names.contains(name) {
+                return Err(Error::duplicate_attribute(format!("split_into::{name}"))
+                    .to_syn_error(meta.path.span()));
+            }
+
+            names.push(name.clone());
+            Ok(())
+        })?;
+
+        if names.len() < 2 {
+            return Err(Error::invalid_attribute(
+                "split_into",
+                "requires at least two field names, e.g., `split_into(host, port)`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.split_into = Some(names);
+        Ok(())
+    }
+
+    fn add_conflicts_with(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        let other: syn::LitStr = meta.value()?.parse()?;
+        if self
+            .conflicts_with
+            .iter()
+            .any(|o| o.value() == other.value())
+        {
+            return Err(
+                Error::duplicate_attribute(format!("conflicts_with::{}", other.value()))
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.conflicts_with.push(other);
+        Ok(())
+    }
+
+    fn set_required_if(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.required_if.is_some() {
+            return Err(Error::duplicate_attribute("required_if").to_syn_error(meta.path.span()));
+        }
+
+        self.required_if = Some(RequiredIf::from_nested_meta(meta)?);
+        Ok(())
+    }
+
+    fn set_group_separators(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.group_separators.is_some() {
+            return Err(
+                Error::duplicate_attribute("group_separators").to_syn_error(meta.path.span())
+            );
+        }
+
+        if !is_integer(optional_inner(&field.ty).unwrap_or(&field.ty)) {
+            return Err(Error::invalid_attribute(
+                "group_separators",
+                "can only be used on integer fields",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        // `_` is always a separator, additional ones can be given
+        let mut separators = String::from("_");
+        if meta.input.peek(syn::Token![=]) {
+            let str: syn::LitStr = meta.value()?.parse()?;
+            separators.extend(str.value().chars().filter(|c| *c != '_'));
+        }
+
+        self.group_separators = Some(separators);
+        Ok(())
+    }
+
+    fn set_decimal_comma(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.decimal_comma {
+            return Err(Error::duplicate_attribute("decimal_comma").to_syn_error(meta.path.span()));
+        }
+
+        let ty = optional_inner(&field.ty).unwrap_or(&field.ty);
+        if !is_type(ty, &["f32"]) && !is_type(ty, &["f64"]) {
+            return Err(Error::invalid_attribute(
+                "decimal_comma",
+                "can only be used on `f32` or `f64` fields",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.decimal_comma = true;
+        Ok(())
+    }
+
+    fn set_unique(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.unique {
+            return Err(Error::duplicate_attribute("unique").to_syn_error(meta.path.span()));
+        }
+
+        let ty = optional_inner(&field.ty).unwrap_or(&field.ty);
+        if !is_collection(ty) || is_map(ty) {
+            return Err(Error::invalid_attribute(
+                "unique",
+                "can only be used on sequences and sets, e.g., `Vec<T>` or `HashSet<T>`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.unique = true;
+        Ok(())
+    }
+
+    fn set_url(&mut self, field: &syn::Field, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.url.is_some() {
+            return Err(Error::duplicate_attribute("url").to_syn_error(meta.path.span()));
+        }
+
+        if !cfg!(feature = "url") {
+            return Err(
+                Error::invalid_attribute("url", "requires the `url` feature")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        if !is_type(
+            optional_inner(&field.ty).unwrap_or(&field.ty),
+            &["url", "Url"],
+        ) {
+            return Err(
+                Error::invalid_attribute("url", "can only be used on `url::Url` fields")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.url = Some(UrlRules::from_nested_meta(meta)?);
+        Ok(())
+    }
+
+    fn set_trim_prefix(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.trim_prefix.is_some() {
+            return Err(Error::duplicate_attribute("trim_prefix").to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let trim_prefix = str.value();
+        if trim_prefix.is_empty() {
+            return Err(
+                Error::invalid_attribute("trim_prefix", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.trim_prefix = Some(trim_prefix);
+        Ok(())
+    }
+
+    fn set_trim_suffix(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.trim_suffix.is_some() {
+            return Err(Error::duplicate_attribute("trim_suffix").to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let trim_suffix = str.value();
+        if trim_suffix.is_empty() {
+            return Err(
+                Error::invalid_attribute("trim_suffix", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.trim_suffix = Some(trim_suffix);
+        Ok(())
+    }
+
+    fn set_keep_empty(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.keep_empty {
+            return Err(Error::duplicate_attribute("keep_empty").to_syn_error(meta.path.span()));
+        }
+
+        if is_map(optional_inner(&field.ty).unwrap_or(&field.ty)) {
+            return Err(Error::invalid_attribute(
+                "keep_empty",
+                "can only be used on sequences, e.g., `Vec<String>`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.keep_empty = true;
+        Ok(())
+    }
+
+    fn set_duration(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.duration {
+            return Err(Error::duplicate_attribute("duration").to_syn_error(meta.path.span()));
+        }
+
+        self.duration = true;
+        Ok(())
+    }
+
+    fn set_datetime(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.datetime {
+            return Err(Error::duplicate_attribute("datetime").to_syn_error(meta.path.span()));
+        }
+
+        if datetime_fns(optional_inner(&field.ty).unwrap_or(&field.ty)).is_none() {
+            return Err(Error::invalid_attribute(
+                "datetime",
+                "can only be used on `chrono::DateTime` fields with the `chrono` feature, or \
+                 `time::OffsetDateTime` and `time::UtcDateTime` fields with the `time` feature",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.datetime = true;
+        Ok(())
+    }
+
+    fn set_unix_timestamp(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.unix_timestamp.is_some() {
+            return Err(Error::duplicate_attribute("unix_timestamp").to_syn_error(meta.path.span()));
+        }
+
+        if !is_type(
+            optional_inner(&field.ty).unwrap_or(&field.ty),
+            &["std", "time", "SystemTime"],
+        ) {
+            return Err(Error::invalid_attribute(
+                "unix_timestamp",
+                "can only be used on `SystemTime` fields",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.unix_timestamp = match meta.input.peek(syn::Token![=]) {
+            true => Some(meta.value()?.parse()?),
+            false => Some(TimestampUnit::Seconds),
+        };
+        Ok(())
+    }
+
+    fn set_enum_by(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.enum_by.is_some() {
+            return Err(Error::duplicate_attribute("enum_by").to_syn_error(meta.path.span()));
+        }
+
+        let enum_by: EnumBy = meta.value()?.parse()?;
+        self.enum_by = Some(enum_by);
+        Ok(())
+    }
+
+    fn set_prefer_dotenv(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.prefer_dotenv {
+            return Err(Error::duplicate_attribute("prefer_dotenv").to_syn_error(meta.path.span()));
+        }
+
+        self.prefer_dotenv = true;
+        Ok(())
+    }
+
+    fn set_global(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.global {
+            return Err(Error::duplicate_attribute("global").to_syn_error(meta.path.span()));
+        }
+
+        self.global = true;
+        Ok(())
+    }
+
+    fn set_lazy_parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.lazy_parse {
+            return Err(Error::duplicate_attribute("lazy_parse").to_syn_error(meta.path.span()));
+        }
+
+        self.lazy_parse = true;
+        Ok(())
+    }
+
+    fn set_dispatch(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if !self.dispatch.is_empty() {
+            return Err(Error::duplicate_attribute("dispatch").to_syn_error(meta.path.span()));
+        }
+
+        if !is_type(&field.ty, &["std", "boxed", "Box"]) {
+            return Err(Error::invalid_attribute(
+                "dispatch",
+                "can only be used on boxed trait objects, e.g., `Box<dyn Trait>`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        let mut dispatch = Vec::new();
+        meta.parse_nested_meta(|meta| {
+            let key = meta.path.get_ident();
+            let key = quote! { #key }.to_string();
+
+            if dispatch.iter().any(|(k, _)| *k == key) {
+                return Err(Error::duplicate_attribute(format!("dispatch::{key}"))
+                    .to_syn_error(meta.path.span()));
+            }
+
+            let ty: syn::Type = meta.value()?.parse()?;
+            dispatch.push((key, ty));
+            Ok(())
+        })?;
+
+        if dispatch.is_empty() {
+            return Err(Error::invalid_attribute(
+                "dispatch",
+                "at least one type is required, e.g., `dispatch(a = TypeA)`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.dispatch = dispatch;
+        Ok(())
+    }
+
+    fn set_present(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.present {
+            return Err(Error::duplicate_attribute("present").to_syn_error(meta.path.span()));
+        }
+
+        if !is_type(&field.ty, &["bool"]) {
+            return Err(
+                Error::invalid_attribute("present", "can only be used on `bool` fields")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.present = true;
+        Ok(())
+    }
+
+    fn set_flag(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.flag {
+            return Err(Error::duplicate_attribute("flag").to_syn_error(meta.path.span()));
+        }
+
+        if !is_type(optional_inner(&field.ty).unwrap_or(&field.ty), &["bool"]) {
+            return Err(Error::invalid_attribute(
+                "flag",
+                "can only be used on `bool` or `Option<bool>` fields",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.flag = true;
+        Ok(())
+    }
+
+    fn set_rest(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.is_rest {
+            return Err(Error::duplicate_attribute("rest").to_syn_error(meta.path.span()));
+        }
+
+        if !is_map(&field.ty) || is_optional(&field.ty) {
+            return Err(Error::invalid_attribute(
+                "rest",
+                "can only be used on map fields, e.g., `HashMap<String, String>`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.is_rest = true;
+        Ok(())
+    }
+
+    fn set_ranges(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.ranges.is_some() {
+            return Err(Error::duplicate_attribute("ranges").to_syn_error(meta.path.span()));
+        }
+
+        // Allows the user to specify both
+        // 1. `#[fill(ranges)]` - Keeps the ranges
+        // 2. `#[fill(ranges = "flatten")]` - Uses the given representation
+        let ranges = match meta.input.peek(syn::Token![=]) {
+            true => meta.value()?.parse()?,
+            false => Ranges::Keep,
+        };
+
+        let ty = optional_inner(&field.ty).unwrap_or(&field.ty);
+        let elem = last_type_arg(ty).filter(|_| is_collection(ty) && !is_map(ty));
+        let (valid, expected) = match ranges {
+            Ranges::Keep => (
+                elem.is_some_and(|elem| is_type(elem, &["std", "ops", "RangeInclusive"])),
+                "a sequence of ranges, e.g., `Vec<RangeInclusive<u16>>`",
+            ),
+            Ranges::Flatten => (
+                elem.is_some_and(is_integer),
+                "a sequence of integers, e.g., `Vec<u16>`",
+            ),
+        };
+        if !valid {
+            return Err(Error::invalid_attribute(
+                "ranges",
+                format!("can only be used on {expected}"),
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.ranges = Some(ranges);
+        Ok(())
+    }
+
+    fn set_parser(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.parser.is_some() {
+            return Err(Error::duplicate_attribute("parser").to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let parser = str.value();
+        if parser.is_empty() {
+            return Err(
+                Error::invalid_attribute("parser", "attribute cannot be empty")
+                    .to_syn_error(meta.path.span()),
+            );
+        }
+
+        self.parser = Some(parser);
+        Ok(())
+    }
+
+    fn set_radix_auto(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.radix_auto {
+            return Err(Error::duplicate_attribute("radix_auto").to_syn_error(meta.path.span()));
+        }
+
+        if !is_integer(optional_inner(&field.ty).unwrap_or(&field.ty)) {
+            return Err(Error::invalid_attribute(
+                "radix_auto",
+                "can only be used on integer fields",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.radix_auto = true;
+        Ok(())
+    }
+
+    fn set_enumerate(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.enumerate {
+            return Err(Error::duplicate_attribute("enumerate").to_syn_error(meta.path.span()));
+        }
+
+        let ty = optional_inner(&field.ty).unwrap_or(&field.ty);
+        if !is_map(ty) || !first_type_arg(ty).is_some_and(|key| is_type(key, &["usize"])) {
+            return Err(Error::invalid_attribute(
+                "enumerate",
+                "can only be used on maps with `usize` keys, e.g., `HashMap<usize, T>`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        self.enumerate = true;
+        Ok(())
+    }
+
+    fn set_pattern(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.pattern.is_some() {
+            return Err(Error::duplicate_attribute("pattern").to_syn_error(meta.path.span()));
+        }
+
+        if cfg!(not(feature = "regex")) {
+            return Err(Error::invalid_attribute(
+                "pattern",
+                "requires the `regex` feature to be enabled",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        let pattern = str.value();
+
+        // Catch invalid patterns at compile time instead of when loading
+        #[cfg(feature = "regex")]
+        if let Err(e) = regex::Regex::new(&pattern) {
+            return Err(Error::invalid_attribute("pattern", e).to_syn_error(str.span()));
+        }
+
+        self.pattern = Some(pattern);
+        Ok(())
+    }
+
+    fn set_map_prefix(
+        &mut self,
+        field: &syn::Field,
+        meta: syn::meta::ParseNestedMeta,
+    ) -> syn::Result<()> {
+        if self.map_prefix.is_some() {
+            return Err(Error::duplicate_attribute("map_prefix").to_syn_error(meta.path.span()));
+        }
+
+        if !is_map(&field.ty) {
+            return Err(Error::invalid_attribute(
+                "map_prefix",
+                "can only be used on maps, e.g., `HashMap<String, T>`",
+            )
+            .to_syn_error(meta.path.span()));
+        }
+
+        let str: syn::LitStr = meta.value()?.parse()?;
+        self.map_prefix = Some(str.value());
+        Ok(())
+    }
+
+    fn set_block(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if self.block {
+            return Err(Error::duplicate_attribute("block").to_syn_error(meta.path.span()));
+        }
+
+        self.block = true;
+        Ok(())
+    }
+}
+
+impl TryFrom<&syn::Field> for FieldAttributes {
+    type Error = syn::Error;
+
+    fn try_from(field: &syn::Field) -> Result<Self, Self::Error> {
+        let mut fa = FieldAttributes::default();
+
+        // Markers hold no value, so unless told otherwise they are constructed
+        // instead of loaded
+        let has_attrs = field.attrs.iter().any(|attr| attr.path().is_ident("fill"));
+        if !has_attrs && is_phantom_data(&field.ty) {
+            fa.is_ignore = true;
+            fa.default = Some(DefaultValue::Type(field.ty.clone()));
+            return Ok(fa);
+        }
+
+        for attr in &field.attrs {
+            if !attr.path().is_ident("fill") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                let ident = meta.path.get_ident();
+                let ident = quote! { #ident }.to_string();
+
+                match ident.as_ref() {
                     "env" => fa.add_env(field, meta),
+                    "alias" => fa.add_alias(meta),
+                    "file" => fa.set_file(meta),
+                    "split_into" => fa.set_split_into(meta),
+                    "deprecated_env" => fa.add_deprecated_env(meta),
+                    "deprecated" => fa.set_deprecated(meta),
                     "default" => fa.set_default(field, meta),
+                    "default_fn" => fa.set_default_fn(meta),
                     "parse_fn" => fa.set_parse_fn(meta),
                     "try_parse_fn" => fa.set_try_parse_fn(meta),
                     "arg_type" => fa.set_arg_type(meta),
+                    "into" => fa.set_into(meta),
+                    "deserialize_with" => fa.set_deserialize_with(meta),
+                    "item_parse_fn" => fa.set_item_parse_fn(meta),
+                    "item_arg_type" => fa.set_item_arg_type(meta),
                     "validate_fn" => fa.set_validate_fn(meta),
+                    "one_of" => fa.set_one_of(meta),
+                    "multiple_of" => fa.set_multiple_of(field, meta),
                     "delimiter" => fa.set_delimiter(meta),
+                    "kv_delimiter" => fa.set_kv_delimiter(field, meta),
+                    "rename_keys" => fa.set_rename_keys(meta),
+                    "case" => fa.set_case(meta),
                     "no_prefix" => fa.disable_prefix(meta),
                     "no_suffix" => fa.disable_suffix(meta),
+                    "prefix" => fa.set_prefix(meta),
+                    "suffix" => fa.set_suffix(meta),
                     "nested" => fa.set_nested(meta),
                     "ignore" => fa.set_ignore(meta),
+                    "sensitive" => fa.set_sensitive(meta),
+                    "expand_path" => fa.set_expand_path(meta),
+                    "pattern" => fa.set_pattern(meta),
+                    "block" => fa.set_block(meta),
+                    "map_prefix" => fa.set_map_prefix(field, meta),
+                    "conflicts_with" => fa.add_conflicts_with(meta),
+                    "required_if" => fa.set_required_if(meta),
+                    "group_separators" => fa.set_group_separators(field, meta),
+                    "decimal_comma" => fa.set_decimal_comma(field, meta),
+                    "unique" => fa.set_unique(field, meta),
+                    "url" => fa.set_url(field, meta),
+                    "trim_prefix" => fa.set_trim_prefix(meta),
+                    "trim_suffix" => fa.set_trim_suffix(meta),
+                    "keep_empty" => fa.set_keep_empty(field, meta),
+                    "duration" => fa.set_duration(meta),
+                    "datetime" => fa.set_datetime(field, meta),
+                    "unix_timestamp" => fa.set_unix_timestamp(field, meta),
+                    "enum_by" => fa.set_enum_by(meta),
+                    "prefer_dotenv" => fa.set_prefer_dotenv(meta),
+                    "global" => fa.set_global(meta),
+                    "radix_auto" => fa.set_radix_auto(field, meta),
+                    "enumerate" => fa.set_enumerate(field, meta),
+                    "lazy_parse" => fa.set_lazy_parse(meta),
+                    "dispatch" => fa.set_dispatch(field, meta),
+                    "present" => fa.set_present(field, meta),
+                    "flag" => fa.set_flag(field, meta),
+                    "ranges" => fa.set_ranges(field, meta),
+                    "parser" => fa.set_parser(meta),
+                    "rest" => fa.set_rest(field, meta),
                     _ => {
                         let closest_match = find_closest_match(&ident, Self::VARIANTS);
                         Err(Error::unexpected_attribute(ident, closest_match)
@@ -649,15 +2425,839 @@ impl TryFrom<&syn::Field> for FieldAttributes {
             );
         }
 
-        // If no envs or defaults are given, the field is not marked as nested or to be
-        // ignored we add it to the list of envs to load
-        if fa.envs.is_none() && fa.default.is_none() && !fa.is_nested && !fa.is_ignore {
-            let ident = &field.ident;
-            let env = quote! { #ident }.to_string();
+        if fa.into && fa.arg_type.is_none() {
+            return Err(
+                Error::missing_attribute("arg_type", "required if `into` is set")
+                    .to_syn_error(field.span()),
+            );
+        }
+
+        // Otherwise the value is parsed as the field type and `arg_type` is unused
+        if fa.arg_type.is_some() && fa.parse_fn.is_none() && fa.try_parse_fn.is_none() && !fa.into {
+            return Err(Error::missing_attribute(
+                "parse_fn",
+                "`parse_fn`, `try_parse_fn`, or `into` is required if `arg_type` is set",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        // The value is converted instead of parsed by a function
+        if fa.into {
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("expand_path", fa.expand_path),
+                ("group_separators", fa.group_separators.is_some()),
+                ("decimal_comma", fa.decimal_comma),
+                ("radix_auto", fa.radix_auto),
+                ("duration", fa.duration),
+                ("enum_by", fa.enum_by.is_some()),
+                ("lazy_parse", fa.lazy_parse),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "into",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // The value is deserialized instead of parsed
+        if fa.deserialize_with.is_some() {
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("into", fa.into),
+                ("item_parse_fn", fa.item_parse_fn.is_some()),
+                ("expand_path", fa.expand_path),
+                ("group_separators", fa.group_separators.is_some()),
+                ("decimal_comma", fa.decimal_comma),
+                ("radix_auto", fa.radix_auto),
+                ("duration", fa.duration),
+                ("enum_by", fa.enum_by.is_some()),
+                ("lazy_parse", fa.lazy_parse),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "deserialize_with",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // Ensure item_arg_type and item_parse_fn are used together
+        if fa.item_parse_fn.is_some() && fa.item_arg_type.is_none() {
+            return Err(Error::missing_attribute(
+                "item_arg_type",
+                "required if `item_parse_fn` is set",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.item_arg_type.is_some() && fa.item_parse_fn.is_none() {
+            return Err(Error::missing_attribute(
+                "item_parse_fn",
+                "required if `item_arg_type` is set",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.item_parse_fn.is_some() && (fa.parse_fn.is_some() || fa.try_parse_fn.is_some()) {
+            return Err(Error::invalid_attribute(
+                "item_parse_fn",
+                "cannot be used together with `parse_fn` or `try_parse_fn`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.group_separators.is_some() && (fa.parse_fn.is_some() || fa.try_parse_fn.is_some()) {
+            return Err(Error::invalid_attribute(
+                "group_separators",
+                "cannot be used together with `parse_fn` or `try_parse_fn`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.decimal_comma && (fa.parse_fn.is_some() || fa.try_parse_fn.is_some()) {
+            return Err(Error::invalid_attribute(
+                "decimal_comma",
+                "cannot be used together with `parse_fn` or `try_parse_fn`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.radix_auto && (fa.parse_fn.is_some() || fa.try_parse_fn.is_some()) {
+            return Err(Error::invalid_attribute(
+                "radix_auto",
+                "cannot be used together with `parse_fn` or `try_parse_fn`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.radix_auto && fa.group_separators.is_some() {
+            return Err(Error::invalid_attribute(
+                "radix_auto",
+                "cannot be used together with `group_separators`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        let has_parse_fn =
+            fa.parse_fn.is_some() || fa.try_parse_fn.is_some() || fa.item_parse_fn.is_some();
+        if fa.duration && has_parse_fn {
+            return Err(Error::invalid_attribute(
+                "duration",
+                "cannot be used together with `parse_fn`, `try_parse_fn`, or `item_parse_fn`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        // Entries could not be told apart from key-value pairs
+        let kv_delimiter = fa.kv_delimiter.as_deref().unwrap_or("=");
+        if is_map(optional_inner(&field.ty).unwrap_or(&field.ty))
+            && fa.delimiter.as_deref().unwrap_or(",") == kv_delimiter
+        {
+            return Err(Error::invalid_attribute(
+                "delimiter",
+                format!("cannot be the same as the key-value delimiter `{kv_delimiter}`"),
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.rename_keys.is_some() {
+            let ty = optional_inner(&field.ty).unwrap_or(&field.ty);
+            if !is_map(ty) || !first_type_arg(ty).is_some_and(|key| is_type(key, &["String"])) {
+                return Err(Error::invalid_attribute(
+                    "rename_keys",
+                    "can only be used on maps with `String` keys",
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        if fa.prefix.is_some() && fa.no_prefix {
+            return Err(Error::invalid_attribute(
+                "prefix",
+                "cannot be used together with `no_prefix`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.suffix.is_some() && fa.no_suffix {
+            return Err(Error::invalid_attribute(
+                "suffix",
+                "cannot be used together with `no_suffix`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.enum_by.is_some() && (has_parse_fn || fa.duration) {
+            return Err(Error::invalid_attribute(
+                "enum_by",
+                "cannot be used together with `parse_fn`, `try_parse_fn`, `item_parse_fn`, or \
+                 `duration`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.expand_path && (fa.parse_fn.is_some() || fa.try_parse_fn.is_some()) {
+            return Err(Error::invalid_attribute(
+                "expand_path",
+                "cannot be used together with `parse_fn` or `try_parse_fn`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.pattern.is_some() && !fa.is_nested {
+            return Err(
+                Error::missing_attribute("nested", "required if `pattern` is set")
+                    .to_syn_error(field.span()),
+            );
+        }
+
+        if fa.pattern.is_some() && is_optional(&field.ty) {
+            return Err(Error::invalid_attribute(
+                "pattern",
+                "cannot be used on optional nested fields",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.pattern.is_some() && fa.envs.is_none() {
+            return Err(
+                Error::missing_attribute("env", "required if `pattern` is set")
+                    .to_syn_error(field.span()),
+            );
+        }
+
+        if fa.lazy_parse {
+            let ty = optional_inner(&field.ty).unwrap_or(&field.ty);
+            if !is_type(ty, &["envoke", "Lazy"]) {
+                return Err(Error::invalid_attribute(
+                    "lazy_parse",
+                    "can only be used on `envoke::Lazy<T>` fields",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            // The value is parsed by `Lazy` so nothing else may parse it
+            let conflicts = [
+                ("default", fa.default.is_some()),
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("item_parse_fn", fa.item_parse_fn.is_some()),
+                (
+                    "validate_fn",
+                    fa.validate_fn.before.is_some() || fa.validate_fn.after.is_some(),
+                ),
+                ("expand_path", fa.expand_path),
+                ("group_separators", fa.group_separators.is_some()),
+                ("decimal_comma", fa.decimal_comma),
+                ("radix_auto", fa.radix_auto),
+                ("duration", fa.duration),
+                ("enum_by", fa.enum_by.is_some()),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "lazy_parse",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // The selected type loads itself, so nothing else may load or parse the value
+        if !fa.dispatch.is_empty() {
+            let conflicts = [
+                ("default", fa.default.is_some()),
+                ("file", fa.file.is_some()),
+                ("deprecated_env", !fa.deprecated_envs.is_empty()),
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                (
+                    "validate_fn",
+                    fa.validate_fn.before.is_some() || fa.validate_fn.after.is_some(),
+                ),
+                ("one_of", fa.one_of.is_some()),
+                ("split_into", fa.split_into.is_some()),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+                ("prefer_dotenv", fa.prefer_dotenv),
+                ("global", fa.global),
+                ("lazy_parse", fa.lazy_parse),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "dispatch",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // A nested struct is loaded by its own derive, so there is no raw value
+        if fa.is_nested && fa.validate_fn.before.is_some() {
+            return Err(Error::invalid_attribute(
+                "validate_fn",
+                "`before` cannot be used on nested fields, use `after` to validate the loaded \
+                 struct",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.map_prefix.is_some() && !fa.is_nested {
+            return Err(
+                Error::missing_attribute("nested", "required if `map_prefix` is set")
+                    .to_syn_error(field.span()),
+            );
+        }
+
+        if fa.map_prefix.is_some() && (fa.pattern.is_some() || fa.block || fa.envs.is_some()) {
+            return Err(Error::invalid_attribute(
+                "map_prefix",
+                "cannot be used together with `env`, `pattern`, or `block`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.block && !fa.is_nested {
+            return Err(
+                Error::missing_attribute("nested", "required if `block` is set")
+                    .to_syn_error(field.span()),
+            );
+        }
+
+        if fa.block && fa.pattern.is_some() {
+            return Err(Error::invalid_attribute(
+                "block",
+                "cannot be used together with `pattern`",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.block && is_optional(&field.ty) {
+            return Err(Error::invalid_attribute(
+                "block",
+                "cannot be used on optional nested fields",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.block && fa.envs.is_none() {
+            return Err(
+                Error::missing_attribute("env", "required if `block` is set")
+                    .to_syn_error(field.span()),
+            );
+        }
+
+        if fa.deprecated.is_some() && fa.deprecated_envs.is_empty() {
+            return Err(Error::missing_attribute(
+                "deprecated_env",
+                "required if `deprecated` is set",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if let Some(names) = &fa.split_into {
+            if fa.parse_fn.is_none() && fa.try_parse_fn.is_none() {
+                return Err(Error::missing_attribute(
+                    "parse_fn",
+                    "`parse_fn` or `try_parse_fn` is required if `split_into` is set",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            if !field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| names.contains(ident))
+            {
+                return Err(Error::invalid_attribute(
+                    "split_into",
+                    "must include the field it is set on",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            // The field is assigned an element of the parsed tuple, so its own
+            // type cannot be used to load or convert the value
+            let conflicts = [
+                ("default", fa.default.is_some()),
+                ("into", fa.into),
+                ("rename_keys", fa.rename_keys.is_some()),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "split_into",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        if !fa.aliases.is_empty() && (fa.is_nested || fa.is_ignore) {
+            return Err(Error::invalid_attribute(
+                "alias",
+                "cannot be used on nested or ignored fields",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if !fa.deprecated_envs.is_empty() && (fa.is_nested || fa.is_ignore) {
+            return Err(Error::invalid_attribute(
+                "deprecated_env",
+                "cannot be used on nested or ignored fields",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        // The variables are only known when loading, so the field is not loaded
+        // from any of its own
+        if fa.is_rest {
+            let conflicts = [
+                ("env", fa.envs.is_some()),
+                ("alias", !fa.aliases.is_empty()),
+                ("deprecated_env", !fa.deprecated_envs.is_empty()),
+                ("file", fa.file.is_some()),
+                ("split_into", fa.split_into.is_some()),
+                ("default", fa.default.is_some()),
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("arg_type", fa.arg_type.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                ("item_parse_fn", fa.item_parse_fn.is_some()),
+                ("delimiter", fa.delimiter.is_some()),
+                ("kv_delimiter", fa.kv_delimiter.is_some()),
+                ("pattern", fa.pattern.is_some()),
+                ("block", fa.block),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "rest",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // If no envs or defaults are given, the field is not marked as nested, to be
+        // ignored or to collect the rest we add it to the list of envs to load. The
+        // same applies if only deprecated envs are given as the current name is
+        // needed in the warning, and if only aliases are given as they are added
+        // after the current name
+        let needs_env =
+            fa.default.is_none() || !fa.deprecated_envs.is_empty() || !fa.aliases.is_empty();
+        if fa.envs.is_none() && needs_env && !fa.is_nested && !fa.is_ignore && !fa.is_rest {
+            let Some(ident) = &field.ident else {
+                return Err(Error::missing_attribute(
+                    "env",
+                    "a name is required for fields of tuple structs, e.g., `env = \"PORT\"`",
+                )
+                .to_syn_error(field.span()));
+            };
+            let env = ident.to_string();
 
             fa.envs.get_or_insert(Vec::new()).push(env);
         }
 
+        // Aliases are loaded like any other name, after the canonical ones
+        if !fa.aliases.is_empty() {
+            let envs = fa.envs.get_or_insert(Vec::new());
+            if let Some(alias) = fa.aliases.iter().find(|alias| envs.contains(alias)) {
+                return Err(
+                    Error::duplicate_attribute(format!("env::{alias}")).to_syn_error(field.span())
+                );
+            }
+
+            envs.extend(fa.aliases.iter().cloned());
+        }
+
+        if fa.default.as_ref().is_some_and(DefaultValue::uses_env_name) && fa.envs.is_none() {
+            return Err(Error::invalid_attribute(
+                "default",
+                "`env_name` and `env_names` can only be used on fields loaded from environment \
+                 variables",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.prefer_dotenv && (fa.envs.is_none() || fa.is_nested || fa.is_ignore) {
+            return Err(Error::invalid_attribute(
+                "prefer_dotenv",
+                "can only be used on fields loaded from environment variables",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.file.is_some() && (fa.envs.is_none() || fa.is_nested || fa.is_ignore) {
+            return Err(Error::invalid_attribute(
+                "file",
+                "can only be used on fields loaded from environment variables",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.global && (fa.envs.is_none() || fa.is_nested || fa.is_ignore) {
+            return Err(Error::invalid_attribute(
+                "global",
+                "can only be used on fields loaded from environment variables",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.one_of.is_some() {
+            let inner = optional_inner(&field.ty).unwrap_or(&field.ty);
+            if fa.envs.is_none() || fa.is_nested || fa.is_ignore || fa.lazy_parse {
+                return Err(Error::invalid_attribute(
+                    "one_of",
+                    "can only be used on fields loaded from environment variables",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            if is_std_collection(inner) {
+                return Err(Error::invalid_attribute(
+                    "one_of",
+                    "cannot be used on collections, use `validate_fn` to validate the elements",
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // The value is split into a list rather than parsed as a map
+        if fa.enumerate {
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                ("kv_delimiter", fa.kv_delimiter.is_some()),
+                ("rename_keys", fa.rename_keys.is_some()),
+                ("lazy_parse", fa.lazy_parse),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "enumerate",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // The value is never read, only whether any of the variables are set
+        if fa.present {
+            if fa.envs.is_none() {
+                return Err(Error::invalid_attribute(
+                    "present",
+                    "can only be used on fields loaded from environment variables",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            let conflicts = [
+                ("default", fa.default.is_some()),
+                ("file", fa.file.is_some()),
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                (
+                    "validate_fn",
+                    fa.validate_fn.before.is_some() || fa.validate_fn.after.is_some(),
+                ),
+                ("one_of", fa.one_of.is_some()),
+                ("split_into", fa.split_into.is_some()),
+                ("sensitive", fa.sensitive),
+                ("pattern", fa.pattern.is_some()),
+                ("prefer_dotenv", fa.prefer_dotenv),
+                ("lazy_parse", fa.lazy_parse),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "present",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // The value is stripped before the builtin parser, custom parse
+        // functions receive the value already parsed as `arg_type`
+        if fa.trim_prefix.is_some() || fa.trim_suffix.is_some() {
+            let attr = match fa.trim_prefix.is_some() {
+                true => "trim_prefix",
+                false => "trim_suffix",
+            };
+
+            if fa.envs.is_none() {
+                return Err(Error::invalid_attribute(
+                    attr,
+                    "can only be used on fields loaded from environment variables",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("into", fa.into),
+                ("split_into", fa.split_into.is_some()),
+                ("present", fa.present),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    attr,
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // An empty value is parsed as `true` by the builtin parser
+        if fa.flag {
+            if fa.envs.is_none() {
+                return Err(Error::invalid_attribute(
+                    "flag",
+                    "can only be used on fields loaded from environment variables",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("arg_type", fa.arg_type.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                ("lazy_parse", fa.lazy_parse),
+                ("present", fa.present),
+                ("parser", fa.parser.is_some()),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "flag",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // Each element is parsed as a range by the builtin parser
+        if fa.ranges.is_some() {
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                ("item_parse_fn", fa.item_parse_fn.is_some()),
+                ("keep_empty", fa.keep_empty),
+                ("duration", fa.duration),
+                ("enum_by", fa.enum_by.is_some()),
+                ("lazy_parse", fa.lazy_parse),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "ranges",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // The date-time is parsed by the builtin parser of its crate
+        if fa.datetime {
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("arg_type", fa.arg_type.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                ("duration", fa.duration),
+                ("lazy_parse", fa.lazy_parse),
+                ("parser", fa.parser.is_some()),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "datetime",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // The timestamp is parsed by the builtin parser
+        if fa.unix_timestamp.is_some() {
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("arg_type", fa.arg_type.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                ("lazy_parse", fa.lazy_parse),
+                ("parser", fa.parser.is_some()),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "unix_timestamp",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // The registered parser replaces any other way of parsing the value
+        if fa.parser.is_some() {
+            if fa.envs.is_none() {
+                return Err(Error::invalid_attribute(
+                    "parser",
+                    "can only be used on fields loaded from environment variables",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("arg_type", fa.arg_type.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                ("item_parse_fn", fa.item_parse_fn.is_some()),
+                ("expand_path", fa.expand_path),
+                ("group_separators", fa.group_separators.is_some()),
+                ("decimal_comma", fa.decimal_comma),
+                ("radix_auto", fa.radix_auto),
+                ("duration", fa.duration),
+                ("enum_by", fa.enum_by.is_some()),
+                ("ranges", fa.ranges.is_some()),
+                ("enumerate", fa.enumerate),
+                ("lazy_parse", fa.lazy_parse),
+                ("present", fa.present),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "parser",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // The scheme is checked on the `Url` parsed by its `FromStr`
+        if fa.url.is_some() {
+            if fa.envs.is_none() {
+                return Err(Error::invalid_attribute(
+                    "url",
+                    "can only be used on fields loaded from environment variables",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("arg_type", fa.arg_type.is_some()),
+                ("into", fa.into),
+                ("lazy_parse", fa.lazy_parse),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "url",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        // Duplicates are found among the elements parsed by the loader
+        if fa.unique {
+            if fa.envs.is_none() {
+                return Err(Error::invalid_attribute(
+                    "unique",
+                    "can only be used on fields loaded from environment variables",
+                )
+                .to_syn_error(field.span()));
+            }
+
+            let conflicts = [
+                ("parse_fn", fa.parse_fn.is_some()),
+                ("try_parse_fn", fa.try_parse_fn.is_some()),
+                ("arg_type", fa.arg_type.is_some()),
+                ("into", fa.into),
+                ("deserialize_with", fa.deserialize_with.is_some()),
+                ("enumerate", fa.enumerate),
+                ("ranges", fa.ranges.is_some()),
+                ("lazy_parse", fa.lazy_parse),
+                ("parser", fa.parser.is_some()),
+                ("nested", fa.is_nested),
+                ("ignore", fa.is_ignore),
+            ];
+            if let Some((conflict, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(Error::invalid_attribute(
+                    "unique",
+                    format!("cannot be used together with `{conflict}`"),
+                )
+                .to_syn_error(field.span()));
+            }
+        }
+
+        if fa.case.is_some() && (fa.envs.is_none() || fa.is_ignore) {
+            return Err(Error::invalid_attribute(
+                "case",
+                "can only be used on fields loaded from environment variables",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        if fa.multiple_of.is_some() && (fa.envs.is_none() || fa.is_ignore) {
+            return Err(Error::invalid_attribute(
+                "multiple_of",
+                "can only be used on fields loaded from environment variables",
+            )
+            .to_syn_error(field.span()));
+        }
+
+        // Fields parsed by the builtin loaders must have a shape they can handle,
+        // custom parse functions are free to produce any type
+        let has_parse_fn = fa.parse_fn.is_some()
+            || fa.try_parse_fn.is_some()
+            || fa.item_parse_fn.is_some()
+            || fa.into
+            || fa.deserialize_with.is_some()
+            || fa.split_into.is_some()
+            || fa.parser.is_some();
+        if fa.envs.is_some() && !fa.is_nested && !fa.is_ignore && !fa.lazy_parse && !has_parse_fn {
+            if let Some(reason) = unsupported_shape(&field.ty) {
+                return Err(Error::unsupported_field_type(reason).to_syn_error(field.ty.span()));
+            }
+        }
+
         Ok(fa)
     }
 }
@@ -1,39 +1,242 @@
 use attrs::{ContainerAttributes, FieldAttributes};
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use syn::{spanned::Spanned, Data, DeriveInput, Fields, FieldsNamed, Ident, Type};
-use utils::generate_field_calls;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Fields, Member, Type};
+use utils::{
+    generate_capture_calls, generate_capture_split_calls, generate_check_calls,
+    generate_config_json_call, generate_diff_calls, generate_env_key_calls,
+    generate_env_line_calls, generate_field_calls, generate_json_calls, generate_split_calls,
+    template_fields,
+};
 
-use crate::errors::Error;
+use crate::{
+    errors::Error,
+    utils::{is_optional, result_types},
+};
 
 mod attrs;
 mod utils;
 
 #[derive(Debug)]
 pub struct Field {
-    ident: Option<Ident>,
+    /// The field name, or its index in a tuple struct
+    member: Member,
     ty: Type,
     attrs: FieldAttributes,
+    /// The error type of a `Result` field, whose `ty` is then the value type
+    result_err: Option<Type>,
+    /// The position of the field among those loaded before the others as
+    /// they are, or their environment variable names are, templated
+    template_order: Option<usize>,
 }
 
-impl TryFrom<syn::Field> for Field {
-    type Error = syn::Error;
+impl Field {
+    fn new(index: usize, mut field: syn::Field) -> syn::Result<Self> {
+        // Errors of a `Result` field are stored instead of returned, so it is
+        // otherwise loaded like a field of the value type
+        let result_err = match result_types(&field.ty) {
+            Some((ok, err)) => {
+                let (ok, err) = (ok.clone(), err.clone());
+                field.ty = ok;
+                Some(err)
+            }
+            None => None,
+        };
 
-    fn try_from(field: syn::Field) -> Result<Self, Self::Error> {
         let attrs = FieldAttributes::try_from(&field)?;
+        let single_value = attrs.envs.is_some()
+            && !attrs.is_nested
+            && !attrs.block
+            && attrs.pattern.is_none()
+            && attrs.dispatch.is_empty()
+            && attrs.split_into.is_none();
+        if result_err.is_some() && !single_value {
+            return Err(Error::unsupported_field_type(
+                "a `Result` field can only be parsed from the value of an environment variable",
+            )
+            .to_syn_error(field.ty.span()));
+        }
+
+        let member = match field.ident {
+            Some(ident) => Member::Named(ident),
+            None => Member::Unnamed(syn::Index {
+                index: index as u32,
+                span: field.ty.span(),
+            }),
+        };
+
         Ok(Self {
-            ident: field.ident,
+            member,
             ty: field.ty,
             attrs,
+            result_err,
+            template_order: None,
         })
     }
 }
 
-fn get_struct_data(span: Span, data: Data) -> syn::Result<FieldsNamed> {
+/// Marks the fields assigned by another field's `split_into`, which are then
+/// only loaded through that field. `has_attrs` tells which fields have their
+/// own `fill` attributes, which would be ignored
+fn resolve_split_targets(fields: &mut [Field], has_attrs: &[bool]) -> syn::Result<()> {
+    let anchors: Vec<(syn::Ident, Vec<syn::Ident>)> = fields
+        .iter()
+        .filter_map(|field| match (&field.member, &field.attrs.split_into) {
+            (Member::Named(ident), Some(names)) => Some((ident.clone(), names.clone())),
+            _ => None,
+        })
+        .collect();
+
+    for (anchor, names) in anchors {
+        for name in names.iter().filter(|name| **name != anchor) {
+            let target = fields
+                .iter()
+                .position(|field| matches!(&field.member, Member::Named(ident) if ident == name))
+                .ok_or_else(|| {
+                    Error::invalid_attribute("split_into", format!("no field named `{name}`"))
+                        .to_syn_error(name.span())
+                })?;
+
+            if has_attrs[target] {
+                return Err(Error::invalid_attribute(
+                    "split_into",
+                    format!("field `{name}` is assigned by `{anchor}` and cannot have attributes"),
+                )
+                .to_syn_error(name.span()));
+            }
+
+            let attrs = &mut fields[target].attrs;
+            if let Some(other) = &attrs.split_from {
+                return Err(Error::invalid_attribute(
+                    "split_into",
+                    format!("field `{name}` is already assigned by `{other}`"),
+                )
+                .to_syn_error(name.span()));
+            }
+
+            // The field would otherwise be loaded from its own name
+            attrs.envs = None;
+            attrs.split_from = Some(anchor.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// Orders the fields whose environment variable names contain placeholders,
+/// e.g., `SERVICE_{region}_ENDPOINT`, after the fields they refer to, which
+/// are then loaded first so the names can be formatted with their values
+fn resolve_templates(fields: &mut [Field]) -> syn::Result<()> {
+    let mut deps: Vec<Vec<usize>> = Vec::with_capacity(fields.len());
+    for field in fields.iter() {
+        let mut field_deps = Vec::new();
+        let envs = field.attrs.envs.iter().flatten();
+        for name in envs
+            .chain(&field.attrs.deprecated_envs)
+            .flat_map(|env| template_fields(env))
+        {
+            let Some(index) = fields
+                .iter()
+                .position(|other| matches!(&other.member, Member::Named(ident) if ident == name))
+            else {
+                return Err(Error::invalid_attribute(
+                    "env",
+                    format!("no field named `{name}` to fill `{{{name}}}` with"),
+                )
+                .to_syn_error(field.member.span()));
+            };
+
+            // The value must always be there and is part of the key, which
+            // ends up in error messages
+            let other = &fields[index];
+            let unsupported = if is_optional(&other.ty) {
+                Some("an optional")
+            } else if other.attrs.sensitive {
+                Some("a sensitive")
+            } else if other.attrs.is_nested {
+                Some("a nested")
+            } else if other.attrs.is_rest {
+                Some("a rest")
+            } else if other.attrs.split_into.is_some() || other.attrs.split_from.is_some() {
+                Some("a split")
+            } else {
+                None
+            };
+            if let Some(reason) = unsupported {
+                return Err(Error::invalid_attribute(
+                    "env",
+                    format!("`{{{name}}}` cannot refer to {reason} field"),
+                )
+                .to_syn_error(field.member.span()));
+            }
+
+            field_deps.push(index);
+        }
+
+        // Only the plain loaders format the names
+        let single_value = field.attrs.split_into.is_none()
+            && field.attrs.pattern.is_none()
+            && !field.attrs.block
+            && field.attrs.dispatch.is_empty();
+        if !field_deps.is_empty() && !single_value {
+            return Err(Error::invalid_attribute(
+                "env",
+                "placeholders cannot be used together with `split_into`, `pattern`, `block` or \
+                 `dispatch`",
+            )
+            .to_syn_error(field.member.span()));
+        }
+
+        deps.push(field_deps);
+    }
+
+    // Depth-first, so every field comes after the fields it refers to
+    fn visit(
+        index: usize,
+        deps: &[Vec<usize>],
+        visiting: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) -> Result<(), usize> {
+        if order.contains(&index) {
+            return Ok(());
+        }
+        if visiting.contains(&index) {
+            return Err(index);
+        }
+
+        visiting.push(index);
+        for dep in &deps[index] {
+            visit(*dep, deps, visiting, order)?;
+        }
+        visiting.pop();
+        order.push(index);
+        Ok(())
+    }
+
+    let mut order = Vec::new();
+    for index in (0..fields.len()).filter(|index| !deps[*index].is_empty()) {
+        if let Err(cycle) = visit(index, &deps, &mut Vec::new(), &mut order) {
+            let field = &fields[cycle];
+            return Err(Error::invalid_attribute(
+                "env",
+                "placeholders cannot refer back to the field, directly or through other fields",
+            )
+            .to_syn_error(field.member.span()));
+        }
+    }
+
+    for (position, index) in order.into_iter().enumerate() {
+        fields[index].template_order = Some(position);
+    }
+
+    Ok(())
+}
+
+fn get_struct_data(span: Span, data: Data) -> syn::Result<Fields> {
     match data {
         Data::Struct(data_struct) => match data_struct.fields {
-            Fields::Named(fields) => Ok(fields),
-            _ => return Err(Error::UnsupportedStructType.to_syn_error(span)),
+            Fields::Unit => Err(Error::UnsupportedStructType.to_syn_error(span)),
+            fields => Ok(fields),
         },
         _ => unreachable!(),
     }
@@ -46,11 +249,51 @@ pub fn derive_for(input: DeriveInput) -> syn::Result<TokenStream> {
 
     let struct_name = &input.ident;
     let struct_data = get_struct_data(input.span(), input.data)?;
-    let fields: Vec<Field> = struct_data
-        .named
+    let has_attrs: Vec<bool> = struct_data
+        .iter()
+        .map(|field| field.attrs.iter().any(|attr| attr.path().is_ident("fill")))
+        .collect();
+    let mut fields: Vec<Field> = struct_data
         .into_iter()
-        .map(Field::try_from)
+        .enumerate()
+        .map(|(index, field)| Field::new(index, field))
         .collect::<syn::Result<_>>()?;
+    resolve_split_targets(&mut fields, &has_attrs)?;
+    resolve_templates(&mut fields)?;
+
+    // The variables of a map of nested structs are only known when loading
+    if c_attrs.deny_unknown {
+        if let Some(field) = fields.iter().find(|field| field.attrs.map_prefix.is_some()) {
+            return Err(Error::invalid_attribute(
+                "deny_unknown",
+                "cannot be used on structs with a `map_prefix` field",
+            )
+            .to_syn_error(field.ty.span()));
+        }
+    }
+
+    // The rest is collected from the variables starting with the prefix which
+    // are not read by any other field
+    let mut rest_fields = fields.iter().filter(|field| field.attrs.is_rest);
+    if let Some(field) = rest_fields.next() {
+        if c_attrs.get_prefix().is_empty() {
+            return Err(
+                Error::missing_attribute("prefix", "required if a field is marked `rest`")
+                    .to_syn_error(field.ty.span()),
+            );
+        }
+
+        if c_attrs.deny_unknown {
+            return Err(Error::invalid_attribute(
+                "deny_unknown",
+                "cannot be used on structs with a `rest` field",
+            )
+            .to_syn_error(field.ty.span()));
+        }
+    }
+    if let Some(field) = rest_fields.next() {
+        return Err(Error::duplicate_attribute("rest").to_syn_error(field.ty.span()));
+    }
 
     // Create the dotenv call here but it will be used when generating the field
     // calls below
@@ -66,20 +309,334 @@ pub fn derive_for(input: DeriveInput) -> syn::Result<TokenStream> {
         },
     };
 
-    let field_calls = generate_field_calls(c_attrs, fields)?;
+    let json_impl = match c_attrs.json {
+        true => {
+            let json_calls = generate_json_calls(&fields);
+            quote! {
+                impl #impl_generics #struct_name #type_generics #where_clause {
+                    /// Serializes the struct to a JSON value, replacing the
+                    /// value of sensitive fields with `"***"`. Fails with the
+                    /// name of the field whose value could not be serialized
+                    pub fn to_json_value_redacted(&self) -> envoke::Result<envoke::serde_json::Value> {
+                        let mut map = envoke::serde_json::Map::new();
+                        #(#json_calls)*
+                        Ok(envoke::serde_json::Value::Object(map))
+                    }
+
+                    /// Serializes the struct to a JSON string, replacing the
+                    /// value of sensitive fields with `"***"`
+                    pub fn to_json_redacted(&self) -> envoke::Result<String> {
+                        self.to_json_value_redacted().map(|value| value.to_string())
+                    }
+                }
+            }
+        }
+        false => quote! {},
+    };
+
+    let env_line_impl = match c_attrs.env_line {
+        true => {
+            let (arms, nested_calls) = generate_env_line_calls(&c_attrs, &fields);
+            quote! {
+                impl #impl_generics #struct_name #type_generics #where_clause {
+                    /// Returns the `KEY=value` line of the given field, using
+                    /// its resolved environment variable name
+                    ///
+                    /// Returns `None` if there is no such field or if the value
+                    /// is not set
+                    pub fn env_line(&self, field: &str) -> Option<String> {
+                        match field {
+                            #(#arms,)*
+                            _ => {
+                                #(#nested_calls)*
+                                None
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        false => quote! {},
+    };
+
+    let diff_impl = match c_attrs.diff {
+        true => {
+            let diff_calls = generate_diff_calls(&fields);
+            quote! {
+                impl #impl_generics #struct_name #type_generics #where_clause {
+                    /// Returns the fields whose values differ from `other`,
+                    /// e.g., to log what changed when reloading. Values of
+                    /// sensitive fields are replaced with `"***"`
+                    pub fn diff(&self, other: &Self) -> Vec<envoke::FieldDiff> {
+                        let mut diffs = Vec::new();
+                        #(#diff_calls)*
+                        diffs
+                    }
+                }
+            }
+        }
+        false => quote! {},
+    };
+
+    let reload_impl = match c_attrs.reload {
+        true => {
+            let ignored = fields
+                .iter()
+                .filter(|field| field.attrs.is_ignore)
+                .map(|field| &field.member);
+            quote! {
+                impl #impl_generics #struct_name #type_generics #where_clause {
+                    /// Loads a new instance from the current environment,
+                    /// keeping the values of ignored fields, e.g., runtime
+                    /// state such as connection pools, instead of resetting
+                    /// them
+                    pub fn reload(&self) -> envoke::Result<Self> {
+                        let mut value = <Self as envoke::Envoke>::try_envoke()?;
+                        #(value.#ignored = self.#ignored.clone();)*
+                        Ok(value)
+                    }
+                }
+            }
+        }
+        false => quote! {},
+    };
+
+    let global_impl = match c_attrs.global {
+        true => quote! {
+            impl #struct_name {
+                /// Returns the instance shared by the whole program, which is
+                /// loaded from the environment on first access
+                ///
+                /// # Panics
+                ///
+                /// Panics on first access if the struct cannot be loaded, and
+                /// on every access after that. Changes to the environment
+                /// after the first access are not picked up
+                pub fn global() -> &'static #struct_name {
+                    static GLOBAL: std::sync::LazyLock<#struct_name> =
+                        std::sync::LazyLock::new(<#struct_name as envoke::Envoke>::envoke);
+
+                    &GLOBAL
+                }
+            }
+        },
+        false => quote! {},
+    };
+
+    let from_map_impl = match c_attrs.from_map {
+        true => quote! {
+            impl #impl_generics TryFrom<std::collections::HashMap<String, String>> for #struct_name #type_generics #where_clause {
+                type Error = envoke::Error;
+
+                fn try_from(source: std::collections::HashMap<String, String>) -> envoke::Result<#struct_name #type_generics> {
+                    envoke::with_source(source, <Self as envoke::Envoke>::try_envoke)
+                }
+            }
+        },
+        false => quote! {},
+    };
+
+    // Any struct can be the target of a nested field's `pattern`, so the impl
+    // is generated whenever the feature is enabled
+    let captures_impl = match cfg!(feature = "regex") {
+        true => {
+            let split_calls = generate_capture_split_calls(&fields);
+            let capture_calls = generate_capture_calls(&fields);
+            quote! {
+                impl #impl_generics envoke::FromCaptures for #struct_name #type_generics #where_clause {
+                    fn from_captures(
+                        captures: &std::collections::HashMap<String, String>,
+                    ) -> envoke::Result<#struct_name #type_generics> {
+                        use envoke::{Envloader, OptEnvloader, FromMap, FromMapOpt, FromSetOpt, FromSet};
+
+                        #(#split_calls)*
+
+                        Ok(#struct_name {
+                            #(#capture_calls),*
+                        })
+                    }
+                }
+            }
+        }
+        false => quote! {},
+    };
+
+    let env_key_calls = generate_env_key_calls(&c_attrs, &fields);
+    let check_calls = generate_check_calls(&c_attrs, &fields)?;
+
+    // The struct moves the values templated names are formatted with, so the
+    // names checked by `conflicts_with` and `required_if` are formatted with
+    // references to the loaded fields instead
+    let template_rebinds: Vec<TokenStream> = match check_calls.is_empty() {
+        true => Vec::new(),
+        false => fields
+            .iter()
+            .filter(|field| field.template_order.is_some())
+            .map(|field| {
+                let member = &field.member;
+                let binding = format_ident!("template_{}", quote! { #member }.to_string());
+                quote! {
+                    #[allow(unused_variables)]
+                    let #binding = &value.#member;
+                }
+            })
+            .collect(),
+    };
+    let config_json_call = generate_config_json_call(struct_name, &c_attrs, &fields);
+    let schema_call = match &c_attrs.schema {
+        Some(schema) => {
+            // Embedded from the manifest directory, so loading does not depend
+            // on the working directory of the process
+            let file = std::env::var("CARGO_MANIFEST_DIR")
+                .map(|dir| std::path::Path::new(&dir).join(schema))
+                .unwrap_or_else(|_| schema.into());
+            let file = file.to_string_lossy();
+            quote! {
+                static SCHEMA: envoke::SchemaCache = envoke::SchemaCache::new();
+                envoke::validate_schema(&value, #schema, include_str!(#file), &SCHEMA)?;
+            }
+        }
+        None => quote! {},
+    };
+    let split_calls = generate_split_calls(&c_attrs, &fields);
+    let deny_unknown_call = match c_attrs.deny_unknown {
+        true => {
+            let prefix = c_attrs.unknown_prefix();
+            quote! {
+                envoke::deny_unknown(#prefix, &<Self as envoke::Envoke>::env_keys())?;
+            }
+        }
+        false => quote! {},
+    };
+    let deny_removed_call = match c_attrs.removed.is_empty() {
+        true => quote! {},
+        false => {
+            let removed = c_attrs.removed.iter().map(|(env, note)| match note {
+                Some(note) => quote! { (#env, Some(#note)) },
+                None => quote! { (#env, None) },
+            });
+            quote! {
+                envoke::deny_removed(&[#(#removed),*])?;
+            }
+        }
+    };
+    let hydrate = c_attrs.hydrate.clone();
+    let fuzzy_names = c_attrs.fuzzy_names;
+    let (template_calls, load_calls, field_calls) = generate_field_calls(c_attrs, fields)?;
+
+    // Without fields to collect the errors of, e.g., if all are split, there
+    // is nothing to collect
+    let load_calls = match load_calls.is_empty() {
+        true => quote! {
+            let _ = collect;
+        },
+        false => quote! {
+            let mut errors = envoke::Errors::default();
+            #(#load_calls)*
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        },
+    };
+
+    let load_call = quote! {
+        #deny_unknown_call
+        #dotenv_call
+        #config_json_call
+        #(#split_calls)*
+        #(#template_calls)*
+        #load_calls
+
+        let value = #struct_name {
+            #(#field_calls),*
+        };
+
+        #(#template_rebinds)*
+        #(#check_calls)*
+        #schema_call
+
+        Ok(value)
+    };
+
+    // The whole load is retried once after hydrating the environment, so
+    // the dotenv file is read again as well
+    let load_call = match hydrate {
+        Some(hydrate) => quote! {
+            let load = || -> std::result::Result<#struct_name #type_generics, envoke::Errors> {
+                #load_call
+            };
+
+            match load() {
+                Err(errors) if errors.iter().any(envoke::Error::is_retrieve_error) => {
+                    #hydrate().map_err(|e| envoke::RetrieveError::Hydrate { err: e.into() })?;
+                    load()
+                }
+                result => result,
+            }
+        },
+        None => load_call,
+    };
+
+    // Checked before hydrating as a removed variable is not fixed by retrying
+    let load_call = quote! {
+        #deny_removed_call
+        #load_call
+    };
+
+    let load_call = match fuzzy_names {
+        true => quote! {
+            envoke::with_fuzzy_names(|| -> std::result::Result<#struct_name #type_generics, envoke::Errors> {
+                #load_call
+            })
+        },
+        false => load_call,
+    };
+
+    // Covers the whole load so the field events are grouped by struct
+    #[cfg(feature = "tracing")]
+    let load_call = {
+        let name = struct_name.to_string();
+        quote! {
+            let _span = envoke::tracing::debug_span!("try_envoke", name = #name).entered();
+            #load_call
+        }
+    };
 
     let expanded = quote! {
         impl #impl_generics envoke::Envoke for #struct_name #type_generics #where_clause {
             fn try_envoke() -> envoke::Result<#struct_name #type_generics> {
+                <Self as envoke::Envoke>::try_envoke_collect(false).map_err(envoke::Errors::into_first)
+            }
+
+            fn try_envoke_collect(collect: bool) -> std::result::Result<#struct_name #type_generics, envoke::Errors> {
                 use envoke::{Envloader, OptEnvloader, FromMap, FromMapOpt, FromSetOpt, FromSet, load_dotenv};
 
-                #dotenv_call
+                #load_call
+            }
+
+            fn env_keys() -> Vec<String> {
+                let mut keys: Vec<String> = Vec::new();
+                #(#env_key_calls)*
 
-                Ok(#struct_name {
-                    #(#field_calls),*
-                })
+                // The same variable may be read by several fields
+                let mut seen = std::collections::HashSet::new();
+                keys.retain(|key| seen.insert(key.clone()));
+                keys
             }
         }
+
+        #json_impl
+
+        #env_line_impl
+
+        #diff_impl
+        #reload_impl
+
+        #global_impl
+
+        #from_map_impl
+
+        #captures_impl
     };
 
     Ok(expanded)
@@ -1,29 +1,61 @@
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::spanned::Spanned;
+use quote::{format_ident, quote};
+use syn::{parse_quote, spanned::Spanned, Type};
 
-use crate::{errors::Error, utils::is_optional};
+use crate::{
+    derive::common::{EnumBy, EnvPriority, Ranges, TimestampUnit},
+    errors::Error,
+    utils::{
+        is_bound_pair, is_collection, is_map, is_net_addr, is_optional, is_type, last_type_arg,
+        optional_inner, wrapper_inner,
+    },
+};
 
 use super::{
-    attrs::{ContainerAttributes, DefaultValue},
+    attrs::{is_arg, ContainerAttributes, DefaultValue, ENV_NAMES_ARG, ENV_NAME_ARG},
     Field,
 };
 
-fn generate_default_call(default: &DefaultValue, field: &Field) -> proc_macro2::TokenStream {
-    let ident = &field.ident;
+/// Generates the default value of the field. The `envs` are the resolved names
+/// the field is loaded from, which are passed to a default function taking
+/// `env_name` or `env_names` as an argument
+fn generate_default_call(
+    default: &DefaultValue,
+    envs: &[String],
+    field: &Field,
+) -> proc_macro2::TokenStream {
+    let ident = &field.member;
     let ident = quote! { #ident }.to_string();
 
     let ty = &field.ty;
     let ty = quote! { #ty }.to_string();
 
     let is_optional = is_optional(&field.ty);
+
+    #[cfg(feature = "system-defaults")]
+    if let Some(call) = system_default_call(default) {
+        let mut call = quote! {
+            #call.try_into().map_err(|_| envoke::Error::ConvertError {
+                field: #ident.to_string(),
+                ty: #ty.to_string()
+            })?
+        };
+        if is_optional {
+            call = quote! { Some(#call) }
+        }
+
+        return call;
+    }
+
     match default {
+        // Not wrapped in `Some` as the default of an optional field is `None`,
+        // not the default of the wrapped type
         DefaultValue::Type(ty) => {
             quote! { <#ty>::default() }
         }
         DefaultValue::Path(path) => {
             let mut call = quote! { #path };
-            if is_optional {
+            if is_optional && !path.path.is_ident("None") {
                 call = quote! { Some(#call) }
             }
 
@@ -43,6 +75,17 @@ fn generate_default_call(default: &DefaultValue, field: &Field) -> proc_macro2::
             call
         }
         DefaultValue::Call { path, args } => {
+            let args = args.iter().map(|arg| {
+                if is_arg(arg, ENV_NAME_ARG) {
+                    let env = &envs[0];
+                    quote! { #env }
+                } else if is_arg(arg, ENV_NAMES_ARG) {
+                    quote! { &[#(#envs),*] }
+                } else {
+                    quote! { #arg }
+                }
+            });
+
             let mut call = quote! { #path(#(#args),*) };
             if is_optional {
                 call = quote! { Some(#call) }
@@ -50,12 +93,227 @@ fn generate_default_call(default: &DefaultValue, field: &Field) -> proc_macro2::
 
             call
         }
+        DefaultValue::Fn(path) => {
+            let mut call = quote! {
+                #path(&envoke::DefaultCtx {
+                    field: #ident,
+                    envs: &[#(#envs),*],
+                })
+            };
+            if is_optional {
+                call = quote! { Some(#call) }
+            }
+
+            call
+        }
+    }
+}
+
+/// Returns the call to a builtin default computed from the system, i.e.,
+/// `cpus` or `cpus_times(n)`, if the default is one of them
+#[cfg(feature = "system-defaults")]
+fn system_default_call(default: &DefaultValue) -> Option<TokenStream> {
+    match default {
+        DefaultValue::Path(path) if path.path.is_ident("cpus") => Some(quote! { envoke::cpus() }),
+        DefaultValue::Call { path, args } if path.path.is_ident("cpus_times") => {
+            Some(quote! { envoke::cpus_times(#(#args),*) })
+        }
+        _ => None,
+    }
+}
+
+/// Returns the function parsing a duration into `ty`, if it is a supported
+/// duration type. A bare `Duration` is always `std::time::Duration`, so the
+/// `time` and `chrono` types must be written with their crate name
+fn duration_parse_fn(ty: &Type) -> Option<TokenStream> {
+    #[cfg(feature = "time")]
+    if is_qualified(ty) && is_type(ty, &["time", "Duration"]) {
+        return Some(quote! { envoke::parse_time_duration });
+    }
+
+    #[cfg(feature = "chrono")]
+    if (is_qualified(ty) && is_type(ty, &["chrono", "Duration"]))
+        || is_type(ty, &["chrono", "TimeDelta"])
+    {
+        return Some(quote! { envoke::parse_chrono_duration });
+    }
+
+    is_type(ty, &["std", "time", "Duration"]).then(|| quote! { envoke::parse_duration })
+}
+
+/// Returns the functions parsing an RFC 3339 date-time into `ty` and
+/// formatting it back, if it is a supported date-time type
+#[cfg_attr(
+    not(any(feature = "chrono", feature = "time")),
+    allow(unused_variables)
+)]
+pub fn datetime_fns(ty: &Type) -> Option<(TokenStream, TokenStream)> {
+    #[cfg(feature = "chrono")]
+    if is_type(ty, &["chrono", "DateTime"]) {
+        return Some((
+            quote! { envoke::parse_chrono_datetime },
+            quote! { envoke::format_chrono_datetime },
+        ));
+    }
+
+    #[cfg(feature = "time")]
+    if is_type(ty, &["time", "OffsetDateTime"]) || is_type(ty, &["time", "UtcDateTime"]) {
+        return Some((
+            quote! { envoke::parse_time_datetime },
+            quote! { envoke::format_time_datetime },
+        ));
+    }
+
+    None
+}
+
+/// Checks if the type is written with at least one leading path segment,
+/// e.g. `time::Duration` but not `Duration`
+#[cfg(any(
+    feature = "time",
+    feature = "chrono",
+    feature = "bytes",
+    feature = "glob",
+    feature = "secrecy"
+))]
+fn is_qualified(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.segments.len() > 1)
+}
+
+/// Checks if the type is `secrecy::SecretString` or its alias
+/// `secrecy::SecretBox<str>`. Bare names may be any type named so, only the
+/// crate path is unambiguous
+#[cfg(feature = "secrecy")]
+fn is_secret_string(ty: &Type) -> bool {
+    if !is_qualified(ty) {
+        return false;
+    }
+
+    if is_type(ty, &["secrecy", "SecretString"]) {
+        return true;
+    }
+
+    is_type(ty, &["secrecy", "SecretBox"])
+        && crate::utils::first_type_arg(ty).is_some_and(|inner| is_type(inner, &["str"]))
+}
+
+/// Returns the parse function for fields that envoke parses itself instead of
+/// relying on the type's `FromStr` implementation, e.g. to give better error
+/// messages or to transform the value first
+fn builtin_parse_fn(field: &Field) -> Option<TokenStream> {
+    if field.attrs.lazy_parse {
+        let ident = &field.member;
+        let ident = quote! { #ident }.to_string();
+        let sensitive = field.attrs.sensitive;
+        return Some(quote! { envoke::parse_lazy(#ident, #sensitive) });
+    }
+
+    if let Some(deserialize_with) = &field.attrs.deserialize_with {
+        return Some(quote! { |value| envoke::deserialize_with(value, #deserialize_with) });
+    }
+
+    if let Some(parser) = &field.attrs.parser {
+        return Some(quote! { |value: String| envoke::parse_registered(#parser, &value) });
+    }
+
+    if field.attrs.flag {
+        return Some(quote! { envoke::parse_flag });
+    }
+
+    if field.attrs.expand_path {
+        return Some(quote! { envoke::expand_path });
+    }
+
+    if field.attrs.radix_auto {
+        return Some(quote! { envoke::parse_radix });
+    }
+
+    if let Some(separators) = &field.attrs.group_separators {
+        return Some(quote! { |value| envoke::parse_grouped(value, #separators) });
+    }
+
+    if field.attrs.decimal_comma {
+        return Some(quote! { envoke::parse_decimal_comma });
+    }
+
+    let ty = optional_inner(&field.ty).unwrap_or(&field.ty);
+    if field.attrs.duration {
+        if let Some(parse_fn) = duration_parse_fn(ty) {
+            return Some(parse_fn);
+        }
+    }
+
+    if field.attrs.datetime {
+        if let Some((parse_fn, _)) = datetime_fns(ty) {
+            return Some(parse_fn);
+        }
+    }
+
+    if let Some(unit) = &field.attrs.unix_timestamp {
+        let millis = *unit == TimestampUnit::Millis;
+        return Some(quote! { |value| envoke::parse_unix_timestamp(value, #millis) });
+    }
+
+    if field.attrs.enum_by == Some(EnumBy::NameOrIndex) && !is_collection(ty) {
+        return Some(quote! { envoke::parse_name_or_index });
+    }
+
+    if is_bound_pair(ty) {
+        return Some(quote! { envoke::parse_interval });
+    }
+
+    if is_net_addr(ty) {
+        return Some(quote! { envoke::parse_addr });
+    }
+
+    if let Some((wrapper, inner)) = wrapper_inner(ty) {
+        let wrapper = format_ident!("{wrapper}");
+        return Some(quote! {
+            |value: String| envoke::parse_str::<#inner>(value).map(std::num::#wrapper)
+        });
+    }
+
+    #[cfg(feature = "secrecy")]
+    if is_secret_string(ty) {
+        return Some(quote! { envoke::parse_secret });
+    }
+
+    // Bare `Bytes` and `Pattern` may be any types named so, only the crate
+    // paths are unambiguous
+    #[cfg(feature = "bytes")]
+    if is_qualified(ty) && is_type(ty, &["bytes", "Bytes"]) {
+        return Some(quote! { envoke::parse_bytes });
+    }
+
+    #[cfg(feature = "glob")]
+    if is_qualified(ty) && is_type(ty, &["glob", "Pattern"]) {
+        return Some(quote! { envoke::parse_glob });
+    }
+
+    None
+}
+
+/// Scrubs the loaded value from parse errors of sensitive fields
+fn redact_call(field: &Field) -> TokenStream {
+    match field.attrs.sensitive {
+        true => quote! { .redacted() },
+        false => quote! {},
+    }
+}
+
+/// Scrubs the parts of a sensitive field's value from the parse errors of a
+/// nested type loaded from it, e.g., a password captured by a `pattern`
+fn redact_nested_call(field: &Field) -> TokenStream {
+    match field.attrs.sensitive {
+        true => quote! { .map_err(envoke::Error::redacted) },
+        false => quote! {},
     }
 }
 
-fn process_call(field: &Field) -> proc_macro2::TokenStream {
-    let ident = &field.ident;
+fn process_call(field: &Field, builtin: Option<&TokenStream>) -> proc_macro2::TokenStream {
+    let ident = &field.member;
     let ident = quote! { #ident }.to_string();
+    let redact = redact_call(field);
     let mut call = quote! {};
 
     if let Some(validate_fn) = &field.attrs.validate_fn.before {
@@ -67,21 +325,162 @@ fn process_call(field: &Field) -> proc_macro2::TokenStream {
         };
     }
 
+    let maps_inner = maps_inner_arg(field);
     if let Some(parse_fn) = &field.attrs.parse_fn {
+        let parse_call = match maps_inner {
+            true => quote! { value.map(#parse_fn) },
+            false => quote! { #parse_fn(value) },
+        };
+
         call = quote! {
             #call
-            let value = #parse_fn(value);
+            let value = #parse_call;
         }
     } else if let Some(try_parse_fn) = &field.attrs.try_parse_fn {
+        let parse_call = match maps_inner {
+            true => quote! { value.map(#try_parse_fn).transpose() },
+            false => quote! { #try_parse_fn(value) },
+        };
+
         call = quote! {
             #call
-            let value = #try_parse_fn(value).map_err(|e| envoke::ParseError::Failed {
-                field: #ident.to_string(),
-                err: e.into()
-            })?;
+            let value = #parse_call.map_err(|e| envoke::ParseError::failed(#ident, e)#redact)?;
+        }
+    } else if field.attrs.into {
+        // Annotated so the conversion may go through any `From` impl which
+        // reaches the field type
+        let ty = &field.ty;
+        let into_call = match is_optional(ty) {
+            true => quote! { value.map(Into::into) },
+            false => quote! { value.into() },
+        };
+
+        call = quote! {
+            #call
+            let value: #ty = #into_call;
+        }
+    } else if let Some(builtin) = builtin {
+        let parse_call = match is_optional(&field.ty) {
+            true => quote! { value.map(#builtin).transpose() },
+            false => quote! { (#builtin)(value) },
+        };
+
+        call = quote! {
+            #call
+            let value = #parse_call.map_err(|e| envoke::ParseError::failed(#ident, e)#redact)?;
+        }
+    }
+
+    if field.attrs.unique {
+        let ty = &field.ty;
+        let check = quote! { envoke::deny_duplicates(#ident, value.iter())?; };
+        let check = match is_optional(ty) {
+            true => quote! { if let Some(value) = &value { #check } },
+            false => check,
+        };
+
+        // Collected into the field type unless a builtin already did so,
+        // e.g., for an `ArrayVec`
+        let collect_call = match (builtin, is_optional(ty)) {
+            (Some(_), _) => quote! {},
+            (None, true) => quote! {
+                let value: #ty = value.map(|value| value.into_iter().collect());
+            },
+            (None, false) => quote! { let value: #ty = value.into_iter().collect(); },
+        };
+
+        call = quote! {
+            #call
+            #check
+            #collect_call
+        };
+    }
+
+    if let Some(case) = &field.attrs.rename_keys {
+        let ty = &field.ty;
+        let rename_call = case.rename_call(quote! { k });
+        let collect_call = quote! {
+            value.into_iter().map(|(k, v)| (#rename_call, v)).collect()
+        };
+        let rename_call = match is_optional(ty) {
+            true => quote! { value.map(|value| #collect_call) },
+            false => collect_call,
+        };
+
+        call = quote! {
+            #call
+            let value: #ty = #rename_call;
         }
     }
 
+    if let Some(one_of) = &field.attrs.one_of {
+        let allowed: Vec<String> = one_of
+            .iter()
+            .map(|value| match value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(str),
+                    ..
+                }) => str.value(),
+                value => quote! { #value }.to_string().replace(" :: ", "::"),
+            })
+            .collect();
+        let check = quote! {
+            if ![#(#one_of),*].iter().any(|allowed| *value == *allowed) {
+                return Err(envoke::ValidationError::NotOneOf {
+                    field: #ident.to_string(),
+                    allowed: vec![#(#allowed.to_string()),*],
+                }
+                .into());
+            }
+        };
+        let check = match is_optional(&field.ty) {
+            true => quote! { if let Some(value) = &value { #check } },
+            false => quote! { { let value = &value; #check } },
+        };
+
+        call = quote! {
+            #call
+            #check
+        };
+    }
+
+    if let Some(url) = &field.attrs.url {
+        let schemes = &url.schemes;
+        let check = quote! {
+            envoke::check_url_scheme(#ident, value, &[#(#schemes),*])?;
+        };
+        let check = match is_optional(&field.ty) {
+            true => quote! { if let Some(value) = &value { #check } },
+            false => quote! { { let value = &value; #check } },
+        };
+
+        call = quote! {
+            #call
+            #check
+        };
+    }
+
+    if let Some(step) = &field.attrs.multiple_of {
+        let check = quote! {
+            if *value % #step != 0 {
+                return Err(envoke::ValidationError::NotMultipleOf {
+                    field: #ident.to_string(),
+                    step: #step.to_string(),
+                }
+                .into());
+            }
+        };
+        let check = match is_optional(&field.ty) {
+            true => quote! { if let Some(value) = &value { #check } },
+            false => quote! { { let value = &value; #check } },
+        };
+
+        call = quote! {
+            #call
+            #check
+        };
+    }
+
     if let Some(validate_fn) = &field.attrs.validate_fn.after {
         call = quote! {
             #call
@@ -95,101 +494,1435 @@ fn process_call(field: &Field) -> proc_macro2::TokenStream {
     call
 }
 
-fn generate_env_call(
-    envs: &Vec<String>,
-    c_attrs: &ContainerAttributes,
-    field: &Field,
-) -> proc_macro2::TokenStream {
-    let ty = match (
-        field.attrs.parse_fn.is_some() || field.attrs.try_parse_fn.is_some(),
-        &field.attrs.arg_type,
-    ) {
-        (true, Some(ty)) => ty,
-        _ => &field.ty,
+/// Splits a templated environment variable name into a format string and the
+/// placeholders it is formatted with, e.g., `SERVICE_{}_ENDPOINT` and
+/// `region` for `SERVICE_{region}_ENDPOINT`
+fn split_template(env: &str) -> (String, Vec<&str>) {
+    let mut format = String::new();
+    let mut names = Vec::new();
+
+    let mut rest = env;
+    while let Some((start, end)) = rest
+        .find('{')
+        .and_then(|start| Some((start, start + rest[start..].find('}')?)))
+    {
+        format.push_str(&rest[..start]);
+        format.push_str("{}");
+        names.push(&rest[start + 1..end]);
+        rest = &rest[end + 1..];
+    }
+    format.push_str(rest);
+
+    (format, names)
+}
+
+/// Returns the names of the fields an environment variable name is templated
+/// with, e.g., `region` in `SERVICE_{region}_ENDPOINT`
+pub fn template_fields(env: &str) -> Vec<&str> {
+    split_template(env).1
+}
+
+/// Generates the environment variable names, formatting templated names with
+/// the values of the fields they refer to. The placeholders of the renamed
+/// names may have changed case, so they are matched to the placeholders of
+/// the original names by position
+fn env_name_calls(envs: &[String], renamed: &[String]) -> Vec<TokenStream> {
+    envs.iter()
+        .zip(renamed)
+        .map(|(env, renamed)| {
+            let names = template_fields(env);
+            if names.is_empty() {
+                return quote! { #renamed };
+            }
+
+            let (format, _) = split_template(renamed);
+            let bindings = names.iter().map(|name| format_ident!("template_{name}"));
+            quote! { format!(#format, #(#bindings),*).as_str() }
+        })
+        .collect()
+}
+
+fn rename_envs(envs: &[String], c_attrs: &ContainerAttributes, field: &Field) -> Vec<String> {
+    envs.iter()
+        .map(|env| {
+            c_attrs.rename(
+                env.to_owned(),
+                field.attrs.no_prefix,
+                field.attrs.no_suffix,
+                field.attrs.prefix.as_deref(),
+                field.attrs.suffix.as_deref(),
+                field.attrs.case.as_ref(),
+            )
+        })
+        .collect()
+}
+
+/// Checks if the field is optional while `arg_type` is not, in which case the
+/// value is loaded as an optional `arg_type` and the parse function is only
+/// applied to a value which is set
+fn maps_inner_arg(field: &Field) -> bool {
+    is_optional(&field.ty)
+        && field.attrs.split_into.is_none()
+        && field
+            .attrs
+            .arg_type
+            .as_ref()
+            .is_some_and(|ty| !is_optional(ty))
+}
+
+/// Strips `trim_prefix` and `trim_suffix` from the value, if any is set
+fn trim_call(field: &Field, value: TokenStream) -> Option<TokenStream> {
+    if field.attrs.trim_prefix.is_none() && field.attrs.trim_suffix.is_none() {
+        return None;
+    }
+
+    let prefix = field.attrs.trim_prefix.as_deref().unwrap_or_default();
+    let suffix = field.attrs.trim_suffix.as_deref().unwrap_or_default();
+    Some(quote! { envoke::trim_affixes(#value, #prefix, #suffix) })
+}
+
+/// Returns the type the field value is loaded as together with the builtin
+/// parse function to apply afterwards, if any
+fn load_type(field: &Field) -> (Type, Option<TokenStream>) {
+    let has_parse_fn =
+        field.attrs.parse_fn.is_some() || field.attrs.try_parse_fn.is_some() || field.attrs.into;
+    let builtin = match has_parse_fn {
+        true => None,
+        false => builtin_parse_fn(field),
     };
 
-    let envs: Vec<String> = envs
-        .iter()
-        .map(|env| c_attrs.rename(env.to_owned(), field.attrs.no_prefix, field.attrs.no_suffix))
-        .collect();
+    // Single values are stripped before they are parsed, elements of a
+    // collection are stripped one at a time by the loader instead
+    let builtin = match trim_call(field, quote! { &value }) {
+        Some(trim) if !is_collection(optional_inner(&field.ty).unwrap_or(&field.ty)) => {
+            Some(match builtin {
+                Some(builtin) => quote! { |value: String| (#builtin)(#trim.to_string()) },
+                None => quote! { |value: String| envoke::parse_str(#trim) },
+            })
+        }
+        _ => builtin,
+    };
 
-    let delim = field.attrs.delimiter.as_deref().unwrap_or(",");
-    let is_optional = is_optional(ty);
-    let base_call = match is_optional {
-        true => {
-            quote! { envoke::OptEnvloader::<#ty>::load_once(&[#(#envs),*], #delim, dotenv.as_ref()) }
+    // Types parsed by a builtin function are loaded as strings first
+    let string_ty: Type = match is_optional(&field.ty) {
+        true => parse_quote! { Option<String> },
+        false => parse_quote! { String },
+    };
+
+    // Enumerated maps are loaded as a sequence of their values first
+    if field.attrs.enumerate && !has_parse_fn {
+        let inner = optional_inner(&field.ty).unwrap_or(&field.ty);
+        if let Some(value) = last_type_arg(inner) {
+            let vec_ty: Type = match is_optional(&field.ty) {
+                true => parse_quote! { Option<Vec<#value>> },
+                false => parse_quote! { Vec<#value> },
+            };
+
+            return (vec_ty, Some(quote! { envoke::collect_enumerated }));
         }
-        false => {
-            quote! { envoke::Envloader::<#ty>::load_once(&[#(#envs),*], #delim, dotenv.as_ref()) }
+    }
+
+    // Flattened ranges are loaded as a sequence of ranges first
+    if field.attrs.ranges == Some(Ranges::Flatten) {
+        let inner = optional_inner(&field.ty).unwrap_or(&field.ty);
+        if let Some(value) = last_type_arg(inner) {
+            let vec_ty: Type = match is_optional(&field.ty) {
+                true => parse_quote! { Option<Vec<std::ops::RangeInclusive<#value>>> },
+                false => parse_quote! { Vec<std::ops::RangeInclusive<#value>> },
+            };
+
+            return (vec_ty, Some(quote! { envoke::flatten_ranges }));
         }
-    };
+    }
 
-    let process_call = process_call(field);
-    match &field.attrs.default {
-        Some(default) => {
-            let default_call = generate_default_call(default, field);
-            quote! {
-                {
-                    match #base_call {
-                        Ok(value) => {
-                            #process_call
-                            value
-                        },
-                        Err(_) => #default_call,
-                    }
-                }
+    // Bounded sequences are loaded into a `Vec` first so the capacity can be
+    // checked instead of panicking while collecting
+    #[cfg(feature = "arrayvec")]
+    if !has_parse_fn && builtin.is_none() {
+        let inner = optional_inner(&field.ty).unwrap_or(&field.ty);
+        if is_type(inner, &["arrayvec", "ArrayVec"]) {
+            if let Some(elem) = crate::utils::first_type_arg(inner) {
+                let vec_ty: Type = match is_optional(&field.ty) {
+                    true => parse_quote! { Option<Vec<#elem>> },
+                    false => parse_quote! { Vec<#elem> },
+                };
+
+                return (vec_ty, Some(quote! { envoke::collect_array_vec }));
             }
         }
-        None => quote! {
-            {
-                let value = #base_call?;
-                #process_call
-                value
-            }
-        },
     }
+
+    // Unique collections are loaded into a `Vec` first so duplicates are
+    // still there to be found, e.g., for sets
+    if field.attrs.unique && builtin.is_none() {
+        let inner = optional_inner(&field.ty).unwrap_or(&field.ty);
+        if let Some(elem) = crate::utils::first_type_arg(inner) {
+            let vec_ty: Type = match is_optional(&field.ty) {
+                true => parse_quote! { Option<Vec<#elem>> },
+                false => parse_quote! { Vec<#elem> },
+            };
+
+            return (vec_ty, None);
+        }
+    }
+
+    let ty = match (has_parse_fn, &field.attrs.arg_type, &builtin) {
+        (true, Some(ty), _) if maps_inner_arg(field) => parse_quote! { Option<#ty> },
+        (true, Some(ty), _) => ty.clone(),
+        (false, _, Some(_)) => string_ty,
+        _ => field.ty.clone(),
+    };
+
+    (ty, builtin)
 }
 
-pub fn generate_field_calls(
-    c_attrs: ContainerAttributes,
-    fields: Vec<Field>,
-) -> syn::Result<Vec<TokenStream>> {
-    let mut calls = Vec::new();
+/// Generates a call to `method` on the loader for `ty`, or to its `_with`
+/// counterpart if the elements of the field are parsed differently. The
+/// `source` is the environment variables or value to load from, followed by
+/// the fallback if the method takes one
+fn generate_loader_call(
+    field: &Field,
+    ty: &Type,
+    method: &str,
+    source: TokenStream,
+    fallback: Option<TokenStream>,
+) -> TokenStream {
+    let loader = match is_optional(ty) {
+        true => quote! { envoke::OptEnvloader::<#ty> },
+        false => quote! { envoke::Envloader::<#ty> },
+    };
 
-    for field in fields {
-        let ident = &field.ident;
-        let ty = &field.ty;
+    // Only maps take the separator between keys and values
+    let collection = optional_inner(ty).unwrap_or(ty);
+    let delim = field.attrs.delimiter.as_deref().unwrap_or(",");
+    let mut args = quote! { #source, #delim };
+    if is_map(collection) {
+        let kv_delim = field.attrs.kv_delimiter.as_deref().unwrap_or("=");
+        args = quote! { #args, #kv_delim };
+    }
+    if let Some(fallback) = fallback {
+        args = quote! { #args, #fallback };
+    }
 
-        let value_call = if field.attrs.is_nested {
-            quote! {
-                <#ty as envoke::Envoke>::try_envoke()?
-            }
-        } else if field.attrs.is_ignore {
-            if !is_optional(ty) {
-                return Err(Error::invalid_attribute(
-                    "ignore",
-                    "ignore can only be used on optional fields",
-                )
-                .to_syn_error(ident.span()));
-            }
+    // Empty elements are rejected unless they are explicitly kept
+    let parse_item = match field.attrs.keep_empty {
+        true => quote! { envoke::parse_str },
+        false => quote! { envoke::parse_element },
+    };
 
-            quote! {
-                None
-            }
-        } else if let Some(envs) = &field.attrs.envs {
-            generate_env_call(&envs, &c_attrs, &field)
-        } else if let Some(default) = &field.attrs.default {
-            generate_default_call(&default, &field)
-        } else {
-            // Caught by another check
-            unreachable!()
-        };
+    // Wrapped elements are parsed as the wrapped type, which is what
+    // implements `FromStr`
+    let wrapped_item = last_type_arg(collection)
+        .filter(|_| is_collection(collection))
+        .and_then(wrapper_inner)
+        .map(|(wrapper, inner)| {
+            let wrapper = format_ident!("{wrapper}");
+            quote! { |value: &str| #parse_item::<#inner>(value).map(std::num::#wrapper) }
+        });
 
-        let call = quote! {
-            #ident: #value_call
-        };
+    let item_parse = match (&field.attrs.item_parse_fn, &field.attrs.item_arg_type) {
+        (Some(item_parse_fn), Some(item_arg_type)) => Some(quote! {
+            |value: &str| #parse_item::<#item_arg_type>(value).map(#item_parse_fn)
+        }),
+        // Scalar durations are handled as a builtin, only collections are
+        // parsed element-wise
+        _ if field.attrs.duration
+            && duration_parse_fn(optional_inner(&field.ty).unwrap_or(&field.ty)).is_none() =>
+        {
+            let parse_fn = last_type_arg(collection)
+                .and_then(duration_parse_fn)
+                .unwrap_or_else(|| quote! { envoke::parse_duration });
+            Some(quote! { |value: &str| #parse_fn(value) })
+        }
+        _ if field.attrs.enum_by == Some(EnumBy::NameOrIndex)
+            && is_collection(optional_inner(&field.ty).unwrap_or(&field.ty)) =>
+        {
+            Some(quote! { |value: &str| envoke::parse_name_or_index(value) })
+        }
+        // Network addresses keep the reason an element is invalid
+        _ if is_collection(collection) && last_type_arg(collection).is_some_and(is_net_addr) => {
+            Some(quote! { |value: &str| envoke::parse_addr(value) })
+        }
+        _ if field.attrs.ranges.is_some() => {
+            Some(quote! { |value: &str| envoke::parse_range(value) })
+        }
+        _ if wrapped_item.is_some() => wrapped_item,
+        _ if field.attrs.keep_empty => Some(quote! { |value: &str| #parse_item(value) }),
+        _ => None,
+    };
 
-        calls.push(call);
+    // Elements are stripped before they are parsed
+    let item_parse = match trim_call(field, quote! { value }) {
+        Some(trim) if is_collection(collection) => {
+            let item_parse =
+                item_parse.unwrap_or_else(|| quote! { |value: &str| #parse_item(value) });
+            Some(quote! { |value: &str| (#item_parse)(#trim) })
+        }
+        _ => item_parse,
+    };
+
+    match item_parse {
+        Some(item_parse) => {
+            // Element parsing is opaque to the loader, so pick the map or
+            // sequence variant from the collection type directly
+            let with_trait = match (is_map(collection), is_optional(ty)) {
+                (true, true) => quote! { envoke::FromMapOptWith<_, _, _> },
+                (true, false) => quote! { envoke::FromMapWith<_, _, _> },
+                (false, true) => quote! { envoke::FromSetOptWith<_, _> },
+                (false, false) => quote! { envoke::FromSetWith<_, _> },
+            };
+
+            let method = format_ident!("{method}_with");
+            quote! { <#loader as #with_trait>::#method(#args, #item_parse) }
+        }
+        None => {
+            let method = format_ident!("{method}");
+            quote! { #loader::#method(#args) }
+        }
+    }
+}
+
+fn generate_env_call(
+    envs: &[String],
+    c_attrs: &ContainerAttributes,
+    field: &Field,
+) -> proc_macro2::TokenStream {
+    let ident = &field.member;
+    let ident = quote! { #ident }.to_string();
+    let (ty, builtin) = load_type(field);
+
+    let env_calls = env_name_calls(envs, &rename_envs(envs, c_attrs, field));
+    let envs = rename_envs(envs, c_attrs, field);
+    let deprecated_envs = env_name_calls(
+        &field.attrs.deprecated_envs,
+        &rename_envs(&field.attrs.deprecated_envs, c_attrs, field),
+    );
+
+    // Deprecated names are only used if none of the current ones are set, no
+    // matter which of the current ones takes precedence
+    let mut all_envs: Vec<&TokenStream> = env_calls.iter().collect();
+    if c_attrs.env_priority == Some(EnvPriority::Last) {
+        all_envs.reverse();
+    }
+    all_envs.extend(&deprecated_envs);
+    let warn_call = match deprecated_envs.is_empty() {
+        true => quote! {},
+        false => {
+            let hint = match &field.attrs.deprecated {
+                Some(hint) => hint.to_owned(),
+                None => format!("use `{}` instead", envs[0]),
+            };
+            quote! {
+                if let Some(key) = envoke::find_source(&[#(#all_envs),*], dotenv.as_ref()) {
+                    if [#(#deprecated_envs),*].contains(&key) {
+                        envoke::warn_deprecated(#ident, key, #hint);
+                    }
+                }
+            }
+        }
+    };
+
+    // The value is never parsed, so an empty or invalid value counts as well
+    if field.attrs.present {
+        let call = quote! {
+            {
+                #warn_call
+                envoke::find_source(&[#(#all_envs),*], dotenv.as_ref()).is_some()
+            }
+        };
+
+        return match field.attrs.global {
+            true => quote! {
+                envoke::load_global(|| -> envoke::Result<_> { Ok(#call) })?
+            },
+            false => call,
+        };
+    }
+
+    let base_call = generate_loader_call(
+        field,
+        &ty,
+        "load_once",
+        quote! { &[#(#all_envs),*] },
+        Some(quote! { dotenv.as_ref() }),
+    );
+
+    // The dotenv file is normally only a fallback, so look it up first to
+    // give it precedence over the process environment
+    let base_call = match field.attrs.prefer_dotenv {
+        true => {
+            let parse_call = generate_loader_call(field, &ty, "parse_once", quote! { value }, None);
+            quote! {
+                match dotenv.as_ref().and_then(|d| [#(#all_envs),*].iter().find_map(|e| d.get(*e))) {
+                    Some(value) => #parse_call,
+                    None => #base_call,
+                }
+            }
+        }
+        false => base_call,
+    };
+
+    // The file is only read if none of the variables are set, including in
+    // the dotenv file
+    let base_call = match &field.attrs.file {
+        Some(path) => {
+            let parse_call =
+                generate_loader_call(field, &ty, "parse_once", quote! { &value }, None);
+            quote! {
+                match envoke::find_source(&[#(#all_envs),*], dotenv.as_ref()) {
+                    Some(_) => #base_call,
+                    None => match envoke::load_file(#path) {
+                        Ok(Some(value)) => #parse_call,
+                        Ok(None) => #base_call,
+                        Err(e) => Err(e),
+                    },
+                }
+            }
+        }
+        None => base_call,
+    };
+
+    let base_call = match field.attrs.sensitive {
+        true => quote! { (#base_call).map_err(envoke::Error::redacted) },
+        false => base_call,
+    };
+
+    let process_call = process_call(field, builtin.as_ref());
+    let call = match &field.attrs.default {
+        Some(default) => {
+            let default_call = generate_default_call(default, &envs, field);
+
+            // The default of an optional field is only evaluated if none of
+            // the variables are set, a value which is set but cannot be
+            // loaded is still an error rather than replaced by the default
+            match is_optional(&field.ty) {
+                true => quote! {
+                    {
+                        #warn_call
+                        match #base_call? {
+                            value @ Some(_) => {
+                                #process_call
+                                value
+                            },
+                            None => #default_call,
+                        }
+                    }
+                },
+                false => quote! {
+                    {
+                        #warn_call
+                        match #base_call {
+                            Ok(value) => {
+                                #process_call
+                                value
+                            },
+                            _ => #default_call,
+                        }
+                    }
+                },
+            }
+        }
+        None => quote! {
+            {
+                #warn_call
+                let value = #base_call?;
+                #process_call
+                value
+            }
+        },
+    };
+
+    // Skips any runtime prefix scope, the value is loaded as usual otherwise
+    match field.attrs.global {
+        true => quote! {
+            envoke::load_global(|| -> envoke::Result<_> { Ok(#call) })?
+        },
+        false => call,
+    }
+}
+
+/// Loads a nested field. An optional nested field is `None` if none of the
+/// nested type's environment variables are set in the process environment,
+/// otherwise it is loaded as usual and all of its required variables must be
+/// set. A map of nested structs is loaded from the variables with its prefix
+fn generate_nested_call(field: &Field, collect: bool) -> TokenStream {
+    let ty = &field.ty;
+    if let Some(prefix) = &field.attrs.map_prefix {
+        return validate_nested_call(
+            field,
+            quote! { envoke::load_nested_map::<#ty, _, _>(#prefix)? },
+        );
+    }
+
+    // Only the fields of the struct itself collect the errors of the nested
+    // struct, the `collect` flag is not passed to captures
+    let try_envoke = match collect {
+        true => quote! { try_envoke_collect(collect) },
+        false => quote! { try_envoke() },
+    };
+    let call = match optional_inner(ty) {
+        Some(inner) => quote! {
+            match envoke::find_source(&<#inner as envoke::Envoke>::env_keys(), None) {
+                Some(_) => Some(<#inner as envoke::Envoke>::#try_envoke?),
+                None => None,
+            }
+        },
+        None => quote! { <#ty as envoke::Envoke>::#try_envoke? },
+    };
+
+    validate_nested_call(field, call)
+}
+
+/// Runs the `after` validation function of a nested field on the loaded
+/// struct. There is no raw value to run a `before` validation function on
+fn validate_nested_call(field: &Field, call: TokenStream) -> TokenStream {
+    let Some(validate_fn) = &field.attrs.validate_fn.after else {
+        return call;
+    };
+
+    let ident = &field.member;
+    let ident = quote! { #ident }.to_string();
+    quote! {
+        {
+            let value = #call;
+            #validate_fn(&value).map_err(|e| envoke::ValidationError::Failed {
+                field: #ident.to_string(),
+                err: e.into()
+            })?;
+            value
+        }
+    }
+}
+
+/// Loads the value of a nested field from a single environment variable by
+/// matching it against the field's pattern
+fn generate_pattern_call(
+    envs: &[String],
+    pattern: &str,
+    c_attrs: &ContainerAttributes,
+    field: &Field,
+) -> proc_macro2::TokenStream {
+    let ident = &field.member;
+    let ident = quote! { #ident }.to_string();
+    let ty = &field.ty;
+
+    let envs = rename_envs(envs, c_attrs, field);
+    let redact = redact_nested_call(field);
+
+    let call = quote! {
+        {
+            let value = envoke::Envloader::<String>::load_once(&[#(#envs),*], ",", dotenv.as_ref())?;
+            let captures = envoke::match_captures(&value, #pattern, #ident)?;
+            <#ty as envoke::FromCaptures>::from_captures(&captures)#redact?
+        }
+    };
+
+    validate_nested_call(field, call)
+}
+
+/// Loads the value of a nested field from the `KEY=VALUE` lines of a single
+/// environment variable
+fn generate_block_call(
+    envs: &[String],
+    c_attrs: &ContainerAttributes,
+    field: &Field,
+) -> proc_macro2::TokenStream {
+    let ty = &field.ty;
+    let envs = rename_envs(envs, c_attrs, field);
+    let redact = redact_nested_call(field);
+
+    let call = quote! {
+        {
+            let value = envoke::Envloader::<String>::load_once(&[#(#envs),*], ",", dotenv.as_ref())?;
+            envoke::load_block::<#ty>(&value)#redact?
+        }
+    };
+
+    validate_nested_call(field, call)
+}
+
+/// Generates a match loading the boxed trait object from the type selected by
+/// `value`
+fn generate_dispatch_match(field: &Field, value: TokenStream) -> TokenStream {
+    let ident = &field.member;
+    let ident = quote! { #ident }.to_string();
+    let ty = &field.ty;
+
+    let (keys, tys): (Vec<&String>, Vec<&Type>) = field
+        .attrs
+        .dispatch
+        .iter()
+        .map(|(key, ty)| (key, ty))
+        .unzip();
+
+    quote! {
+        match #value {
+            #(#keys => Box::new(<#tys as envoke::Envoke>::try_envoke()?) as #ty,)*
+            _ => {
+                return Err(envoke::ValidationError::NotOneOf {
+                    field: #ident.to_string(),
+                    allowed: vec![#(#keys.to_string()),*],
+                }
+                .into())
+            }
+        }
+    }
+}
+
+/// Loads a boxed trait object from the type selected by the value of the
+/// field's environment variable
+fn generate_dispatch_call(
+    envs: &[String],
+    c_attrs: &ContainerAttributes,
+    field: &Field,
+) -> proc_macro2::TokenStream {
+    let envs = rename_envs(envs, c_attrs, field);
+    let match_call = generate_dispatch_match(field, quote! { value.as_str() });
+
+    quote! {
+        {
+            let value = envoke::Envloader::<String>::load_once(&[#(#envs),*], ",", dotenv.as_ref())?;
+            #match_call
+        }
+    }
+}
+
+/// Loads the value of a field from the capture group with the same name
+fn generate_capture_call(field: &Field) -> proc_macro2::TokenStream {
+    let ident = &field.member;
+    let ident = quote! { #ident }.to_string();
+    let (ty, builtin) = load_type(field);
+
+    if field.attrs.present {
+        return quote! { captures.get(#ident).is_some() };
+    }
+
+    let base_call = generate_loader_call(field, &ty, "parse_once", quote! { value }, None);
+
+    let missing_call = match &field.attrs.default {
+        Some(default) => generate_default_call(default, std::slice::from_ref(&ident), field),
+        None if is_optional(&field.ty) => quote! { None },
+        None => quote! {
+            return Err(envoke::ParseError::MissingCapture { field: #ident.to_string() }.into())
+        },
+    };
+
+    let redact = redact_call(field);
+    let process_call = process_call(field, builtin.as_ref());
+    quote! {
+        match captures.get(#ident) {
+            Some(value) => {
+                let value = #base_call.map_err(|e| envoke::ParseError::failed(#ident, e)#redact)?;
+                #process_call
+                value
+            },
+            None => #missing_call,
+        }
+    }
+}
+
+pub fn generate_json_calls(fields: &[Field]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .map(|field| {
+            let ident = &field.member;
+            let key = quote! { #ident }.to_string();
+
+            let value_call = if field.attrs.sensitive {
+                quote! { envoke::serde_json::Value::String("***".to_string()) }
+            } else if field.attrs.map_prefix.is_some() {
+                quote! {
+                    envoke::serde_json::Value::Object(
+                        self.#ident
+                            .iter()
+                            .map(|(key, value)| Ok((key.to_string(), value.to_json_value_redacted()?)))
+                            .collect::<envoke::Result<_>>()?,
+                    )
+                }
+            } else if field.attrs.is_nested {
+                // Nested types generate their own method so their sensitive
+                // fields are redacted as well
+                match is_optional(&field.ty) {
+                    true => quote! {
+                        self.#ident
+                            .as_ref()
+                            .map(|value| value.to_json_value_redacted())
+                            .transpose()?
+                            .unwrap_or_default()
+                    },
+                    false => quote! { self.#ident.to_json_value_redacted()? },
+                }
+            } else {
+                quote! {
+                    envoke::serde_json::to_value(&self.#ident).map_err(|err| {
+                        envoke::Error::SerializeError {
+                            field: #key.to_string(),
+                            err: err.into(),
+                        }
+                    })?
+                }
+            };
+
+            quote! {
+                map.insert(#key.to_string(), #value_call);
+            }
+        })
+        .collect()
+}
+
+/// Generates an expression formatting the field value `value` as an
+/// `Option<String>`, which is `None` if an optional field is not set.
+/// Sensitive values are replaced with `***`
+fn generate_to_string_call(field: &Field, value: TokenStream) -> TokenStream {
+    // Formatted as an empty value, which is loaded as set, and unset otherwise
+    if field.attrs.present {
+        return quote! { #value.then(String::new) };
+    }
+
+    // Sequences and maps are formatted the same way they are parsed
+    let delim = field.attrs.delimiter.as_deref().unwrap_or(",");
+    let inner = optional_inner(&field.ty).unwrap_or(&field.ty);
+    let format_call = if field.attrs.sensitive {
+        quote! { "***".to_string() }
+    } else if field.attrs.enumerate {
+        // Formatted as the list it is parsed from, in the order of the keys
+        quote! {
+            {
+                let mut values: Vec<_> = value.iter().collect();
+                values.sort_by_key(|(k, _)| **k);
+                values
+                    .into_iter()
+                    .map(|(_, v)| v.to_string())
+                    .collect::<Vec<String>>()
+                    .join(#delim)
+            }
+        }
+    } else if let (true, Some((_, format_fn))) = (field.attrs.datetime, datetime_fns(inner)) {
+        // Formatted as RFC 3339 as `Display` is not what is parsed
+        quote! { #format_fn(value) }
+    } else if let Some(unit) = &field.attrs.unix_timestamp {
+        // `SystemTime` does not implement `Display`
+        let millis = *unit == TimestampUnit::Millis;
+        quote! { envoke::format_unix_timestamp(value, #millis) }
+    } else if field.attrs.ranges == Some(Ranges::Keep) {
+        // Formatted the same way they are parsed, i.e., `80,8000-8100`
+        quote! {
+            value
+                .iter()
+                .map(|range| match range.start() == range.end() {
+                    true => range.start().to_string(),
+                    false => format!("{}-{}", range.start(), range.end()),
+                })
+                .collect::<Vec<String>>()
+                .join(#delim)
+        }
+    } else if is_map(inner) {
+        quote! {
+            value
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<String>>()
+                .join(#delim)
+        }
+    } else if is_collection(inner) {
+        quote! {
+            value
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>()
+                .join(#delim)
+        }
+    } else {
+        quote! { value.to_string() }
+    };
+
+    match is_optional(&field.ty) {
+        true => quote! { #value.as_ref().map(|value| #format_call) },
+        false => quote! {
+            {
+                let value = &#value;
+                Some(#format_call)
+            }
+        },
+    }
+}
+
+/// Generates the comparisons of `diff`, recursing into nested fields
+pub fn generate_diff_calls(fields: &[Field]) -> Vec<TokenStream> {
+    let mut calls = Vec::new();
+
+    for field in fields {
+        let ident = &field.member;
+        let name = quote! { #ident }.to_string();
+
+        // Ignored fields are never loaded and the values of a map of nested
+        // structs, a trait object or a captured error cannot be formatted
+        if field.attrs.is_ignore
+            || field.attrs.map_prefix.is_some()
+            || !field.attrs.dispatch.is_empty()
+            || field.result_err.is_some()
+        {
+            continue;
+        }
+
+        let call = if field.attrs.is_nested {
+            let nested_call = quote! {
+                diffs.extend(old.diff(new).into_iter().map(|diff| envoke::FieldDiff {
+                    field: format!("{}.{}", #name, diff.field),
+                    ..diff
+                }));
+            };
+
+            match is_optional(&field.ty) {
+                true => quote! {
+                    match (&self.#ident, &other.#ident) {
+                        (Some(old), Some(new)) => #nested_call,
+                        (None, None) => {}
+                        (old, new) => diffs.push(envoke::FieldDiff {
+                            field: #name.to_string(),
+                            old: old.as_ref().map(|_| "set".to_string()),
+                            new: new.as_ref().map(|_| "set".to_string()),
+                        }),
+                    }
+                },
+                false => quote! {
+                    {
+                        let (old, new) = (&self.#ident, &other.#ident);
+                        #nested_call
+                    }
+                },
+            }
+        } else {
+            let old_call = generate_to_string_call(field, quote! { self.#ident });
+            let new_call = generate_to_string_call(field, quote! { other.#ident });
+            quote! {
+                if self.#ident != other.#ident {
+                    diffs.push(envoke::FieldDiff {
+                        field: #name.to_string(),
+                        old: #old_call,
+                        new: #new_call,
+                    });
+                }
+            }
+        };
+
+        calls.push(call);
+    }
+
+    calls
+}
+
+/// Generates the match arms of `env_line` for the fields loaded from an
+/// environment variable, and the lookups into nested fields for other names
+pub fn generate_env_line_calls(
+    c_attrs: &ContainerAttributes,
+    fields: &[Field],
+) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    let mut arms = Vec::new();
+    let mut nested_calls = Vec::new();
+
+    for field in fields {
+        let ident = &field.member;
+
+        // The variable names of a map of nested structs depend on its keys,
+        // the selected type of a trait object is not stored, a captured error
+        // has no value to write and templated names depend on other fields
+        let is_templated = field
+            .attrs
+            .envs
+            .iter()
+            .flatten()
+            .any(|env| !template_fields(env).is_empty());
+        if field.attrs.map_prefix.is_some()
+            || !field.attrs.dispatch.is_empty()
+            || field.result_err.is_some()
+            || is_templated
+        {
+            continue;
+        }
+
+        if field.attrs.is_nested {
+            let line_call = match is_optional(&field.ty) {
+                true => quote! { self.#ident.as_ref().and_then(|value| value.env_line(field)) },
+                false => quote! { self.#ident.env_line(field) },
+            };
+            nested_calls.push(quote! {
+                if let Some(line) = #line_call {
+                    return Some(line);
+                }
+            });
+            continue;
+        }
+
+        // The variable holds the values of several fields
+        let (Some(envs), None) = (&field.attrs.envs, &field.attrs.split_into) else {
+            continue;
+        };
+
+        let name = quote! { #ident }.to_string();
+        let key = &rename_envs(envs, c_attrs, field)[0];
+        let value_call = generate_to_string_call(field, quote! { self.#ident });
+
+        arms.push(quote! {
+            #name => #value_call.map(|value| format!("{}={value}", #key))
+        });
+    }
+
+    (arms, nested_calls)
+}
+
+/// Returns the variable holding the value of a field assigned by `split_into`
+fn split_binding(field: &Field) -> Option<syn::Ident> {
+    if field.attrs.split_into.is_none() && field.attrs.split_from.is_none() {
+        return None;
+    }
+
+    let ident = &field.member;
+    let name = quote! { #ident }.to_string();
+    Some(format_ident!("split_{name}"))
+}
+
+/// Generates the bindings of the tuples parsed by fields with `split_into`,
+/// which the listed fields are then assigned from. A tuple of the wrong
+/// length fails to compile
+fn split_calls(fields: &[Field], value_call: impl Fn(&Field) -> TokenStream) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let names = field.attrs.split_into.as_ref()?;
+            let bindings = names.iter().map(|name| format_ident!("split_{name}"));
+            let value_call = value_call(field);
+
+            Some(quote! {
+                let (#(#bindings),*) = #value_call;
+            })
+        })
+        .collect()
+}
+
+pub fn generate_split_calls(c_attrs: &ContainerAttributes, fields: &[Field]) -> Vec<TokenStream> {
+    split_calls(fields, |field| {
+        let envs = field.attrs.envs.as_deref().unwrap_or_default();
+        generate_env_call(envs, c_attrs, field)
+    })
+}
+
+pub fn generate_capture_split_calls(fields: &[Field]) -> Vec<TokenStream> {
+    split_calls(fields, generate_capture_call)
+}
+
+/// Emits an event with the key the field is loaded from, if any, before
+/// loading it. The value itself is never recorded, so `sensitive` fields are
+/// kept out of the logs like any other field
+#[cfg(feature = "tracing")]
+fn generate_trace_call(
+    c_attrs: &ContainerAttributes,
+    field: &Field,
+    value_call: TokenStream,
+) -> TokenStream {
+    let Some(envs) = &field.attrs.envs else {
+        return value_call;
+    };
+
+    let ident = &field.member;
+    let name = quote! { #ident }.to_string();
+    let envs = env_name_calls(envs, &rename_envs(envs, c_attrs, field));
+    let deprecated_envs = env_name_calls(
+        &field.attrs.deprecated_envs,
+        &rename_envs(&field.attrs.deprecated_envs, c_attrs, field),
+    );
+    let has_default = field.attrs.default.is_some();
+    quote! {
+        {
+            let key = envoke::find_source(&[#(#envs,)* #(#deprecated_envs),*], dotenv.as_ref())
+                .map(str::to_string);
+            envoke::tracing::debug!(
+                field = #name,
+                key = key.as_deref().unwrap_or_default(),
+                default = key.is_none() && #has_default,
+                "resolved field"
+            );
+            #value_call
+        }
+    }
+}
+
+/// Attaches the name of the field to retrieve and parse errors of loading it,
+/// so every error of a field can be traced back to it with `Error::field`
+fn generate_field_error_call(field: &Field, value_call: TokenStream) -> TokenStream {
+    let ident = &field.member;
+    let name = quote! { #ident }.to_string();
+    let ty = &field.ty;
+    quote! {
+        {
+            // The value call usually ends in `?` itself
+            #[allow(clippy::needless_question_mark)]
+            let load = || -> std::result::Result<#ty, envoke::Errors> { Ok(#value_call) };
+            load().map_err(|e| e.in_field(#name))?
+        }
+    }
+}
+
+/// Stores the error of loading a `Result` field in the field instead of
+/// returning it. A `String` error holds the message, any other error type is
+/// converted from `envoke::Error`
+fn generate_result_call(field: &Field, value_call: TokenStream) -> TokenStream {
+    let Some(err) = &field.result_err else {
+        return value_call;
+    };
+
+    // A single value is loaded, so there is only one error
+    let map_err = match is_type(err, &["std", "string", "String"]) {
+        true => quote! { |e| e.into_first().to_string() },
+        false => quote! { |e| e.into_first().into() },
+    };
+    quote! {
+        (|| -> std::result::Result<_, envoke::Errors> { Ok(#value_call) })().map_err(#map_err)
+    }
+}
+
+/// Generates the field values of the struct, together with the bindings of
+/// the fields which are loaded first, in order, as templated environment
+/// variable names depend on them, and the bindings of the other fields, which
+/// collect their errors in `errors`
+pub fn generate_field_calls(
+    c_attrs: ContainerAttributes,
+    fields: Vec<Field>,
+) -> syn::Result<(Vec<TokenStream>, Vec<TokenStream>, Vec<TokenStream>)> {
+    let mut template_calls = Vec::new();
+    let mut load_calls = Vec::new();
+    let mut calls = Vec::new();
+
+    for field in fields {
+        let ident = &field.member;
+        let ty = &field.ty;
+
+        // Assigned from the tuple parsed by `split_into`, which is not read
+        // from the config JSON
+        if let Some(binding) = split_binding(&field) {
+            calls.push(quote! { #ident: #binding });
+            continue;
+        }
+
+        let value_call = if let (Some(pattern), Some(envs)) =
+            (&field.attrs.pattern, &field.attrs.envs)
+        {
+            generate_pattern_call(envs, pattern, &c_attrs, &field)
+        } else if let (true, Some(envs)) = (field.attrs.block, &field.attrs.envs) {
+            generate_block_call(envs, &c_attrs, &field)
+        } else if let (false, Some(envs)) = (field.attrs.dispatch.is_empty(), &field.attrs.envs) {
+            generate_dispatch_call(envs, &c_attrs, &field)
+        } else if field.attrs.is_nested {
+            generate_nested_call(&field, true)
+        } else if field.attrs.is_ignore {
+            // Non-optional fields are left at their default value
+            match &field.attrs.default {
+                Some(default) => generate_default_call(default, &[], &field),
+                None if is_optional(ty) => quote! { None },
+                None => {
+                    return Err(Error::invalid_attribute(
+                        "ignore",
+                        "ignore can only be used on optional fields or together with `default`",
+                    )
+                    .to_syn_error(ident.span()));
+                }
+            }
+        } else if field.attrs.is_rest {
+            let prefix = c_attrs.unknown_prefix();
+            quote! {
+                envoke::load_rest(#prefix, &<Self as envoke::Envoke>::env_keys())?
+            }
+        } else if let Some(envs) = &field.attrs.envs {
+            if field.attrs.prefer_dotenv && c_attrs.dotenv.is_none() {
+                return Err(Error::invalid_attribute(
+                    "prefer_dotenv",
+                    "requires the container attribute `dotenv` to be set",
+                )
+                .to_syn_error(ident.span()));
+            }
+
+            generate_env_call(envs, &c_attrs, &field)
+        } else if let Some(default) = &field.attrs.default {
+            generate_default_call(default, &[], &field)
+        } else {
+            // Caught by another check
+            unreachable!()
+        };
+
+        let value_call = generate_field_error_call(&field, value_call);
+        let value_call = generate_result_call(&field, value_call);
+
+        #[cfg(feature = "tracing")]
+        let value_call = generate_trace_call(&c_attrs, &field, value_call);
+
+        // Values from the config JSON are only used if none of the field's own
+        // environment variables are set
+        let value_call = match &c_attrs.config_json {
+            Some(_) => {
+                let name = quote! { #ident }.to_string();
+                let config_value = format_ident!("config_{name}");
+                match generate_presence_call(&c_attrs, &field) {
+                    Some(is_present) => quote! {
+                        match #config_value {
+                            Some(value) if !#is_present => value,
+                            _ => #value_call,
+                        }
+                    },
+                    None => quote! {
+                        match #config_value {
+                            Some(value) => value,
+                            None => #value_call,
+                        }
+                    },
+                }
+            }
+            _ => value_call,
+        };
+
+        let value_call = match field.template_order {
+            Some(order) => {
+                let name = quote! { #ident }.to_string();
+                let binding = format_ident!("template_{name}");
+
+                // Annotated as the type is otherwise inferred from the struct
+                let ty = match &field.result_err {
+                    Some(err) => quote! { std::result::Result<#ty, #err> },
+                    None => quote! { #ty },
+                };
+                template_calls.push((order, quote! { let #binding: #ty = #value_call; }));
+                calls.push(quote! { #ident: #binding });
+                continue;
+            }
+            None => value_call,
+        };
+
+        // Loaded on its own so the remaining fields are still loaded if it
+        // fails and all errors are collected
+        let name = quote! { #ident }.to_string();
+        let binding = format_ident!("field_{name}");
+        let ty = match &field.result_err {
+            Some(err) => quote! { std::result::Result<#ty, #err> },
+            None => quote! { #ty },
+        };
+        load_calls.push(quote! {
+            let #binding = {
+                // The value call usually ends in `?` itself
+                #[allow(clippy::needless_question_mark)]
+                let load = || -> std::result::Result<#ty, envoke::Errors> { Ok(#value_call) };
+                match load() {
+                    Ok(value) => Some(value),
+                    Err(err) => {
+                        errors.extend(err);
+                        if !collect {
+                            return Err(errors);
+                        }
+                        None
+                    }
+                }
+            };
+        });
+
+        calls.push(quote! {
+            #ident: match #binding {
+                Some(value) => value,
+                None => unreachable!("the errors of the fields are returned first"),
+            }
+        });
+    }
+
+    template_calls.sort_by_key(|(order, _)| *order);
+    let template_calls = template_calls.into_iter().map(|(_, call)| call).collect();
+
+    Ok((template_calls, load_calls, calls))
+}
+
+/// The variable is named like any other, i.e., with the container's prefix,
+/// suffix and case
+fn config_json_name(c_attrs: &ContainerAttributes, config_json: &str) -> String {
+    c_attrs.rename(config_json.to_owned(), false, false, None, None, None)
+}
+
+/// Loads the struct from the config JSON, if any, into one optional value per
+/// field for the field calls to fall back on
+pub fn generate_config_json_call(
+    struct_name: &syn::Ident,
+    c_attrs: &ContainerAttributes,
+    fields: &[Field],
+) -> TokenStream {
+    let Some(config_json) = &c_attrs.config_json else {
+        return quote! {};
+    };
+
+    let idents: Vec<&syn::Member> = fields.iter().map(|f| &f.member).collect();
+    let config_values: Vec<syn::Ident> = idents
+        .iter()
+        .map(|ident| {
+            let name = quote! { #ident }.to_string();
+            format_ident!("config_{name}")
+        })
+        .collect();
+    let nones = idents.iter().map(|_| quote! { None });
+
+    let config_json = config_json_name(c_attrs, config_json);
+
+    // Only an unset variable falls back to loading the fields as usual, other
+    // errors such as invalid Unicode are reported
+    quote! {
+        let (#(#config_values,)*) = match Envloader::<String>::load_once(&[#config_json], ",", dotenv.as_ref()) {
+            Ok(json) => {
+                let #struct_name { #(#idents: #config_values),* } = envoke::serde_json::from_str::<Self>(&json)
+                    .map_err(|e| envoke::ParseError::failed(#config_json, e))?;
+                (#(Some(#config_values),)*)
+            }
+            Err(envoke::Error::RetrieveError(envoke::RetrieveError::NotFound { .. })) => (#(#nones,)*),
+            Err(e) => return Err(e.into()),
+        };
+    }
+}
+
+pub fn generate_capture_calls(fields: &[Field]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .map(|field| {
+            let ident = &field.member;
+
+            // Nested types are loaded as usual, only the field values come
+            // from the captures
+            let value_call = if let Some(binding) = split_binding(field) {
+                quote! { #binding }
+            } else if field.attrs.is_nested {
+                generate_nested_call(field, false)
+            } else if field.attrs.is_ignore {
+                match &field.attrs.default {
+                    Some(default) => generate_default_call(default, &[], field),
+                    None => quote! { None },
+                }
+            } else if field.attrs.is_rest {
+                // Captures have no prefix to collect the rest by
+                quote! { Default::default() }
+            } else if !field.attrs.dispatch.is_empty() {
+                let name = quote! { #ident }.to_string();
+                let match_call = generate_dispatch_match(field, quote! { value.as_str() });
+                quote! {
+                    match captures.get(#name) {
+                        Some(value) => #match_call,
+                        None => {
+                            return Err(envoke::ParseError::MissingCapture { field: #name.to_string() }.into())
+                        }
+                    }
+                }
+            } else if field.attrs.envs.is_some() {
+                generate_result_call(field, generate_capture_call(field))
+            } else if let Some(default) = &field.attrs.default {
+                generate_default_call(default, &[], field)
+            } else {
+                // Caught by another check
+                unreachable!()
+            };
+
+            quote! {
+                #ident: #value_call
+            }
+        })
+        .collect()
+}
+
+/// Generates the calls collecting the names of the environment variables read
+/// by each field, recursing into nested fields
+pub fn generate_env_key_calls(c_attrs: &ContainerAttributes, fields: &[Field]) -> Vec<TokenStream> {
+    let mut calls = Vec::new();
+
+    if let Some(config_json) = &c_attrs.config_json {
+        let config_json = config_json_name(c_attrs, config_json);
+        calls.push(quote! { keys.push(#config_json.to_string()); });
+    }
+
+    for field in fields {
+        let ty = &field.ty;
+
+        // Nested fields loaded from a single variable only read that variable
+        let single_env = field.attrs.pattern.is_some() || field.attrs.block;
+        let call = if let (true, Some(envs)) = (single_env, &field.attrs.envs) {
+            let envs = rename_envs(envs, c_attrs, field);
+            quote! { keys.extend([#(#envs),*].map(String::from)); }
+        } else if field.attrs.map_prefix.is_some() {
+            // The variable names depend on the keys, which are only known
+            // when loading
+            continue;
+        } else if let (false, Some(envs)) = (field.attrs.dispatch.is_empty(), &field.attrs.envs) {
+            // Any of the types may be selected
+            let envs = rename_envs(envs, c_attrs, field);
+            let tys = field.attrs.dispatch.iter().map(|(_, ty)| ty);
+            quote! {
+                keys.extend([#(#envs),*].map(String::from));
+                #(keys.extend(<#tys as envoke::Envoke>::env_keys());)*
+            }
+        } else if field.attrs.is_nested {
+            let ty = optional_inner(ty).unwrap_or(ty);
+            quote! { keys.extend(<#ty as envoke::Envoke>::env_keys()); }
+        } else if let Some(envs) = &field.attrs.envs {
+            // Templated names depend on the values of other fields, which are
+            // only known when loading
+            let envs = rename_envs(envs, c_attrs, field)
+                .into_iter()
+                .zip(envs)
+                .chain(
+                    rename_envs(&field.attrs.deprecated_envs, c_attrs, field)
+                        .into_iter()
+                        .zip(&field.attrs.deprecated_envs),
+                )
+                .filter(|(_, env)| template_fields(env).is_empty())
+                .map(|(renamed, _)| renamed)
+                .collect::<Vec<_>>();
+            if envs.is_empty() {
+                continue;
+            }
+
+            quote! { keys.extend([#(#envs),*].map(String::from)); }
+        } else {
+            continue;
+        };
+
+        calls.push(call);
+    }
+
+    calls
+}
+
+/// Generates a check whether any of the field's environment variables is set
+fn generate_presence_call(c_attrs: &ContainerAttributes, field: &Field) -> Option<TokenStream> {
+    let envs = field.attrs.envs.as_ref()?;
+    let envs = env_name_calls(envs, &rename_envs(envs, c_attrs, field));
+    let deprecated_envs = env_name_calls(
+        &field.attrs.deprecated_envs,
+        &rename_envs(&field.attrs.deprecated_envs, c_attrs, field),
+    );
+
+    Some(quote! {
+        envoke::find_source(&[#(#envs,)* #(#deprecated_envs),*], dotenv.as_ref()).is_some()
+    })
+}
+
+/// Checks if the field has the given name, fields of tuple structs have none
+fn is_named(field: &Field, name: &str) -> bool {
+    matches!(&field.member, syn::Member::Named(ident) if ident == name)
+}
+
+/// Renders the expression as written for error messages, i.e. string literals
+/// without their quotes and paths without the spaces of the token stream
+fn display_expr(expr: &syn::Expr) -> String {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => lit.value(),
+        syn::Expr::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::"),
+        _ => quote! { #expr }.to_string(),
+    }
+}
+
+/// Generates the checks between fields which run after the struct is loaded
+pub fn generate_check_calls(
+    c_attrs: &ContainerAttributes,
+    fields: &[Field],
+) -> syn::Result<Vec<TokenStream>> {
+    let find_field = |name: &syn::LitStr, attr: &str| {
+        let other = fields.iter().find(|f| is_named(f, &name.value()));
+
+        match other {
+            Some(other) if other.attrs.envs.is_some() => Ok(other),
+            Some(_) => Err(Error::invalid_attribute(
+                attr,
+                format!(
+                    "field `{}` is not loaded from the environment",
+                    name.value()
+                ),
+            )
+            .to_syn_error(name.span())),
+            None => Err(Error::invalid_attribute(
+                attr,
+                format!("no field named `{}`", name.value()),
+            )
+            .to_syn_error(name.span())),
+        }
+    };
+
+    let mut calls = Vec::new();
+    for field in fields {
+        let ident = &field.member;
+        let ident = quote! { #ident }.to_string();
+
+        for other_name in &field.attrs.conflicts_with {
+            let other = find_field(other_name, "conflicts_with")?;
+            let Some(is_present) = generate_presence_call(c_attrs, field) else {
+                return Err(Error::invalid_attribute(
+                    "conflicts_with",
+                    "can only be used on fields loaded from the environment",
+                )
+                .to_syn_error(other_name.span()));
+            };
+            let is_other_present = generate_presence_call(c_attrs, other);
+
+            let other = other_name.value();
+            calls.push(quote! {
+                if #is_present && #is_other_present {
+                    return Err(envoke::ValidationError::Conflict {
+                        field: #ident.to_string(),
+                        other: #other.to_string(),
+                    }
+                    .into());
+                }
+            });
+        }
+
+        if let Some(required_if) = &field.attrs.required_if {
+            let other_name = &required_if.field;
+            let other = fields
+                .iter()
+                .find(|f| is_named(f, &other_name.value()))
+                .map(|f| &f.member)
+                .ok_or_else(|| {
+                    Error::invalid_attribute(
+                        "required_if",
+                        format!("no field named `{}`", other_name.value()),
+                    )
+                    .to_syn_error(other_name.span())
+                })?;
+
+            let is_present = match (
+                is_optional(&field.ty),
+                generate_presence_call(c_attrs, field),
+            ) {
+                (true, Some(is_present)) => is_present,
+                _ => {
+                    return Err(Error::invalid_attribute(
+                        "required_if",
+                        "can only be used on optional fields loaded from the environment",
+                    )
+                    .to_syn_error(other_name.span()))
+                }
+            };
+
+            let equals = &required_if.equals;
+            let value = display_expr(equals);
+            let other_name = other_name.value();
+            calls.push(quote! {
+                if value.#other == #equals && !#is_present {
+                    return Err(envoke::ValidationError::Required {
+                        field: #ident.to_string(),
+                        other: #other_name.to_string(),
+                        value: #value.to_string(),
+                    }
+                    .into());
+                }
+            });
+        }
     }
 
     Ok(calls)
@@ -32,7 +32,7 @@ pub enum Error {
     #[error("Error: unsupported target, fill can only be derived for structs and enums")]
     UnsupportedTarget,
 
-    #[error("Error: unsupported struct type, fill can only be derived for named structs")]
+    #[error("Error: unsupported struct type, fill can only be derived for named or tuple structs")]
     UnsupportedStructType,
 
     #[error("Error: unsupported enum type, fill can only be derived for unnamed-/unit enums")]
@@ -41,6 +41,9 @@ pub enum Error {
     #[error("Error: unsupported variant type, fill can only be derived for path variants")]
     UnsupportedVariantType,
 
+    #[error("Error: unsupported field type, {reason}")]
+    UnsupportedFieldType { reason: String },
+
     #[error("Error: {0}")]
     Attribute(#[from] AttributeError),
 }
@@ -79,6 +82,12 @@ impl Error {
         })
     }
 
+    pub fn unsupported_field_type(reason: impl ToString) -> Self {
+        Error::UnsupportedFieldType {
+            reason: reason.to_string(),
+        }
+    }
+
     pub fn to_syn_error(&self, span: proc_macro2::Span) -> syn::Error {
         syn::Error::new(span, self)
     }
@@ -1,19 +1,382 @@
-use syn::Type;
+use syn::{GenericArgument, PathArguments, Type};
 
+/// Returns the variant closest to `input`, if any is close enough to be a
+/// likely typo. The allowed distance scales with the length of `input` so
+/// short inputs don't match unrelated variants
 pub fn find_closest_match(input: &str, variants: &'static [&'static str]) -> Option<&'static str> {
-    for variant in variants {
-        let distance = strsim::levenshtein(input, &variant);
-        if distance <= 5 {
-            return Some(variant);
+    let threshold = (input.chars().count() / 3).max(1);
+
+    variants
+        .iter()
+        .map(|variant| (strsim::levenshtein(input, variant), *variant))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, variant)| variant)
+}
+
+/// Checks if the type is an `Option`, e.g., `Option<T>` or
+/// `std::option::Option<T>`
+pub fn is_optional(ty: &Type) -> bool {
+    let is_option =
+        is_type(ty, &["std", "option", "Option"]) || is_type(ty, &["core", "option", "Option"]);
+
+    is_option && first_type_arg(ty).is_some()
+}
+
+/// Checks if the type is a `PhantomData` marker, e.g., `PhantomData<T>` or
+/// `std::marker::PhantomData<T>`
+pub fn is_phantom_data(ty: &Type) -> bool {
+    is_type(ty, &["std", "marker", "PhantomData"])
+        || is_type(ty, &["core", "marker", "PhantomData"])
+}
+
+/// Returns the type wrapped by `Option`, e.g. `T` in `Option<T>`
+pub fn optional_inner(ty: &Type) -> Option<&Type> {
+    if !is_optional(ty) {
+        return None;
+    }
+
+    first_type_arg(ty)
+}
+
+/// Returns the value and error types of a `Result`, e.g., `T` and `E` in
+/// `Result<T, E>` or `std::result::Result<T, E>`. Aliases with a fixed error
+/// type, e.g., `io::Result<T>`, are not recognized
+pub fn result_types(ty: &Type) -> Option<(&Type, &Type)> {
+    let is_result =
+        is_type(ty, &["std", "result", "Result"]) || is_type(ty, &["core", "result", "Result"]);
+    if !is_result {
+        return None;
+    }
+
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(args) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+
+    let mut types = args.args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    match (types.next(), types.next(), types.next()) {
+        (Some(ok), Some(err), None) => Some((ok, err)),
+        _ => None,
+    }
+}
+
+/// Returns the first generic type argument, e.g. `T` in `ArrayVec<T, 8>`
+pub fn first_type_arg(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    match &path.path.segments.last()?.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the last generic type argument, e.g. `V` in `HashMap<K, V>` or `T`
+/// in `Vec<T>`
+pub fn last_type_arg(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    match &path.path.segments.last()?.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().rev().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// Checks if the type matches the given path, e.g. both `Pattern` and
+/// `glob::Pattern` matches `["glob", "Pattern"]`
+pub fn is_type(ty: &Type, expected: &[&str]) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    let segments = &path.path.segments;
+    if segments.is_empty() || segments.len() > expected.len() {
+        return false;
+    }
+
+    expected[expected.len() - segments.len()..]
+        .iter()
+        .zip(segments)
+        .all(|(e, s)| s.ident == e)
+}
+
+/// Checks if the type is a primitive integer, e.g., `u32` or `i64`
+pub fn is_integer(ty: &Type) -> bool {
+    const INTEGERS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+
+    INTEGERS.iter().any(|int| is_type(ty, &[int]))
+}
+
+/// Checks if the type is a map-like collection, e.g. `HashMap` or `BTreeMap`
+pub fn is_map(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident.to_string().ends_with("Map")),
+        _ => false,
+    }
+}
+
+/// Checks if the type is one of the collections from the standard library,
+/// e.g., `Vec<T>` or `HashMap<K, V>`
+pub fn is_std_collection(ty: &Type) -> bool {
+    const COLLECTIONS: &[&str] = &[
+        "Vec",
+        "VecDeque",
+        "LinkedList",
+        "BinaryHeap",
+        "HashSet",
+        "BTreeSet",
+        "HashMap",
+        "BTreeMap",
+    ];
+
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    path.path.segments.last().is_some_and(|segment| {
+        COLLECTIONS.iter().any(|c| segment.ident == c)
+            && matches!(segment.arguments, PathArguments::AngleBracketed(_))
+    })
+}
+
+/// Returns why a field of the given type cannot be loaded from a single
+/// value, if it cannot. The field may be optional and a collection, but the
+/// elements of a collection are parsed from a single item each, so they
+/// cannot be optional or collections themselves
+pub fn unsupported_shape(ty: &Type) -> Option<&'static str> {
+    let ty = match optional_inner(ty) {
+        Some(inner) if is_optional(inner) => {
+            return Some("an `Option` cannot contain another `Option`, e.g., `Option<Option<T>>`")
+        }
+        Some(inner) => inner,
+        None => ty,
+    };
+
+    if !is_std_collection(ty) {
+        return None;
+    }
+
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(args) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(elem) if is_optional(elem) => Some(
+            "elements of a collection cannot be optional, e.g., `Vec<Option<T>>`, use \
+             `item_parse_fn` to parse them",
+        ),
+        GenericArgument::Type(elem) if is_std_collection(elem) => Some(
+            "elements of a collection cannot be collections, e.g., `Vec<Vec<T>>`, use \
+             `item_parse_fn` to parse them",
+        ),
+        _ => None,
+    })
+}
+
+/// Returns the wrapper and the wrapped type of a transparent wrapper from
+/// `std::num`, e.g., `Saturating` and `u32` in `Saturating<u32>`
+pub fn wrapper_inner(ty: &Type) -> Option<(&'static str, &Type)> {
+    const WRAPPERS: &[&str] = &["Saturating", "Wrapping"];
+
+    let wrapper = WRAPPERS
+        .iter()
+        .find(|wrapper| is_type(ty, &["std", "num", wrapper]))?;
+    first_type_arg(ty).map(|inner| (*wrapper, inner))
+}
+
+/// Checks if the type is a generic collection, e.g., `Vec<T>` or `HashMap<K,
+/// V>`. Transparent wrappers such as `Saturating<T>` hold a single value and
+/// are not collections
+pub fn is_collection(ty: &Type) -> bool {
+    if wrapper_inner(ty).is_some() {
+        return false;
+    }
+
+    match ty {
+        Type::Path(path) => {
+            path.path.segments.last().is_some_and(|segment| {
+                matches!(segment.arguments, PathArguments::AngleBracketed(_))
+            })
         }
+        _ => false,
     }
+}
+
+/// Checks if the type is a network address from `std::net`, e.g., `IpAddr` or
+/// `SocketAddr`
+pub fn is_net_addr(ty: &Type) -> bool {
+    const ADDRS: &[&str] = &[
+        "IpAddr",
+        "Ipv4Addr",
+        "Ipv6Addr",
+        "SocketAddr",
+        "SocketAddrV4",
+        "SocketAddrV6",
+    ];
 
-    None
+    ADDRS.iter().any(|addr| is_type(ty, &["std", "net", addr]))
 }
 
-pub fn is_optional(ty: &Type) -> bool {
+/// Checks if the type is a pair of bounds, e.g., `(Bound<u64>, Bound<u64>)`
+pub fn is_bound_pair(ty: &Type) -> bool {
     match ty {
-        Type::Path(path) => path.path.segments[0].ident == "Option",
+        Type::Tuple(tuple) => {
+            tuple.elems.len() == 2
+                && tuple.elems.iter().all(|elem| match elem {
+                    Type::Path(path) => path
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "Bound"),
+                    _ => false,
+                })
+        }
         _ => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    const VARIANTS: &[&str] = &[
+        "env",
+        "default",
+        "parse_fn",
+        "try_parse_fn",
+        "arg_type",
+        "delimiter",
+        "no_prefix",
+        "no_suffix",
+        "nested",
+        "ignore",
+    ];
+
+    #[test]
+    fn test_find_closest_match_typo() {
+        assert_eq!(find_closest_match("en", VARIANTS), Some("env"));
+        assert_eq!(find_closest_match("defualt", VARIANTS), Some("default"));
+        assert_eq!(find_closest_match("delimeter", VARIANTS), Some("delimiter"));
+        assert_eq!(find_closest_match("nestd", VARIANTS), Some("nested"));
+    }
+
+    #[test]
+    fn test_find_closest_match_picks_closest() {
+        assert_eq!(
+            find_closest_match("try_parse_f", VARIANTS),
+            Some("try_parse_fn")
+        );
+        assert_eq!(find_closest_match("parse_f", VARIANTS), Some("parse_fn"));
+        assert_eq!(find_closest_match("no_sufix", VARIANTS), Some("no_suffix"));
+    }
+
+    #[test]
+    fn test_is_optional() {
+        assert!(is_optional(&parse_quote!(Option<u16>)));
+        assert!(is_optional(&parse_quote!(std::option::Option<u16>)));
+        assert!(is_optional(&parse_quote!(core::option::Option<Vec<u16>>)));
+        assert!(!is_optional(&parse_quote!(u16)));
+        assert!(!is_optional(&parse_quote!(Vec<Option<u16>>)));
+        assert!(!is_optional(&parse_quote!(my::Option<u16>)));
+    }
+
+    #[test]
+    fn test_is_phantom_data() {
+        assert!(is_phantom_data(&parse_quote!(PhantomData<T>)));
+        assert!(is_phantom_data(&parse_quote!(std::marker::PhantomData<T>)));
+        assert!(is_phantom_data(&parse_quote!(
+            core::marker::PhantomData<u8>
+        )));
+        assert!(!is_phantom_data(&parse_quote!(Option<T>)));
+        assert!(!is_phantom_data(&parse_quote!(my::PhantomData<T>)));
+    }
+
+    #[test]
+    fn test_result_types() {
+        let ty: Type = parse_quote!(Result<u16, String>);
+        assert!(result_types(&ty).is_some_and(|(ok, err)| {
+            is_type(ok, &["u16"]) && is_type(err, &["std", "string", "String"])
+        }));
+        assert!(result_types(&parse_quote!(std::result::Result<u16, envoke::Error>)).is_some());
+        assert!(result_types(&parse_quote!(io::Result<u16>)).is_none());
+        assert!(result_types(&parse_quote!(Option<u16>)).is_none());
+    }
+
+    #[test]
+    fn test_unsupported_shape_supported() {
+        let supported: [Type; 7] = [
+            parse_quote!(u16),
+            parse_quote!(Option<u16>),
+            parse_quote!(Vec<u16>),
+            parse_quote!(Option<Vec<u16>>),
+            parse_quote!(std::option::Option<HashMap<String, u16>>),
+            parse_quote!(Vec<NonZero<u16>>),
+            parse_quote!(Option<Lazy<Vec<u16>>>),
+        ];
+
+        for ty in supported {
+            assert_eq!(unsupported_shape(&ty), None, "{}", quote::quote!(#ty));
+        }
+    }
+
+    #[test]
+    fn test_unsupported_shape_unsupported() {
+        let unsupported: [Type; 5] = [
+            parse_quote!(Option<Option<u16>>),
+            parse_quote!(Vec<Option<u16>>),
+            parse_quote!(Option<Vec<Option<u16>>>),
+            parse_quote!(HashMap<String, Vec<u16>>),
+            parse_quote!(std::collections::BTreeSet<Option<u16>>),
+        ];
+
+        for ty in unsupported {
+            assert!(unsupported_shape(&ty).is_some(), "{}", quote::quote!(#ty));
+        }
+    }
+
+    #[test]
+    fn test_wrapper_inner() {
+        let ty: Type = parse_quote!(std::num::Saturating<u32>);
+        assert!(matches!(wrapper_inner(&ty), Some(("Saturating", _))));
+
+        let ty: Type = parse_quote!(Wrapping<i8>);
+        assert!(matches!(wrapper_inner(&ty), Some(("Wrapping", _))));
+        assert!(!is_collection(&ty));
+
+        assert!(wrapper_inner(&parse_quote!(Vec<u32>)).is_none());
+        assert!(wrapper_inner(&parse_quote!(Saturating)).is_none());
+    }
+
+    #[test]
+    fn test_find_closest_match_unrelated() {
+        assert_eq!(find_closest_match("foo", VARIANTS), None);
+        assert_eq!(find_closest_match("rename", VARIANTS), None);
+        assert_eq!(find_closest_match("x", VARIANTS), None);
+    }
+}
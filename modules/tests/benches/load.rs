@@ -0,0 +1,251 @@
+//! Loads a struct with 100 fields, a third each of integers, sequences and
+//! maps, from the environment and from the dotenv fallback
+
+use std::collections::HashMap;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use envoke::{Envoke, Fill};
+
+const DOTENV: &str = "/tmp/envoke_bench_load.env";
+
+#[derive(Fill)]
+#[fill(dotenv = "/tmp/envoke_bench_load.env")]
+#[allow(dead_code)]
+struct Config {
+    #[fill(env = "BENCH_F000")]
+    f000: u64,
+    #[fill(env = "BENCH_F001")]
+    f001: Vec<u32>,
+    #[fill(env = "BENCH_F002")]
+    f002: HashMap<String, u32>,
+    #[fill(env = "BENCH_F003")]
+    f003: u64,
+    #[fill(env = "BENCH_F004")]
+    f004: Vec<u32>,
+    #[fill(env = "BENCH_F005")]
+    f005: HashMap<String, u32>,
+    #[fill(env = "BENCH_F006")]
+    f006: u64,
+    #[fill(env = "BENCH_F007")]
+    f007: Vec<u32>,
+    #[fill(env = "BENCH_F008")]
+    f008: HashMap<String, u32>,
+    #[fill(env = "BENCH_F009")]
+    f009: u64,
+    #[fill(env = "BENCH_F010")]
+    f010: Vec<u32>,
+    #[fill(env = "BENCH_F011")]
+    f011: HashMap<String, u32>,
+    #[fill(env = "BENCH_F012")]
+    f012: u64,
+    #[fill(env = "BENCH_F013")]
+    f013: Vec<u32>,
+    #[fill(env = "BENCH_F014")]
+    f014: HashMap<String, u32>,
+    #[fill(env = "BENCH_F015")]
+    f015: u64,
+    #[fill(env = "BENCH_F016")]
+    f016: Vec<u32>,
+    #[fill(env = "BENCH_F017")]
+    f017: HashMap<String, u32>,
+    #[fill(env = "BENCH_F018")]
+    f018: u64,
+    #[fill(env = "BENCH_F019")]
+    f019: Vec<u32>,
+    #[fill(env = "BENCH_F020")]
+    f020: HashMap<String, u32>,
+    #[fill(env = "BENCH_F021")]
+    f021: u64,
+    #[fill(env = "BENCH_F022")]
+    f022: Vec<u32>,
+    #[fill(env = "BENCH_F023")]
+    f023: HashMap<String, u32>,
+    #[fill(env = "BENCH_F024")]
+    f024: u64,
+    #[fill(env = "BENCH_F025")]
+    f025: Vec<u32>,
+    #[fill(env = "BENCH_F026")]
+    f026: HashMap<String, u32>,
+    #[fill(env = "BENCH_F027")]
+    f027: u64,
+    #[fill(env = "BENCH_F028")]
+    f028: Vec<u32>,
+    #[fill(env = "BENCH_F029")]
+    f029: HashMap<String, u32>,
+    #[fill(env = "BENCH_F030")]
+    f030: u64,
+    #[fill(env = "BENCH_F031")]
+    f031: Vec<u32>,
+    #[fill(env = "BENCH_F032")]
+    f032: HashMap<String, u32>,
+    #[fill(env = "BENCH_F033")]
+    f033: u64,
+    #[fill(env = "BENCH_F034")]
+    f034: Vec<u32>,
+    #[fill(env = "BENCH_F035")]
+    f035: HashMap<String, u32>,
+    #[fill(env = "BENCH_F036")]
+    f036: u64,
+    #[fill(env = "BENCH_F037")]
+    f037: Vec<u32>,
+    #[fill(env = "BENCH_F038")]
+    f038: HashMap<String, u32>,
+    #[fill(env = "BENCH_F039")]
+    f039: u64,
+    #[fill(env = "BENCH_F040")]
+    f040: Vec<u32>,
+    #[fill(env = "BENCH_F041")]
+    f041: HashMap<String, u32>,
+    #[fill(env = "BENCH_F042")]
+    f042: u64,
+    #[fill(env = "BENCH_F043")]
+    f043: Vec<u32>,
+    #[fill(env = "BENCH_F044")]
+    f044: HashMap<String, u32>,
+    #[fill(env = "BENCH_F045")]
+    f045: u64,
+    #[fill(env = "BENCH_F046")]
+    f046: Vec<u32>,
+    #[fill(env = "BENCH_F047")]
+    f047: HashMap<String, u32>,
+    #[fill(env = "BENCH_F048")]
+    f048: u64,
+    #[fill(env = "BENCH_F049")]
+    f049: Vec<u32>,
+    #[fill(env = "BENCH_F050")]
+    f050: HashMap<String, u32>,
+    #[fill(env = "BENCH_F051")]
+    f051: u64,
+    #[fill(env = "BENCH_F052")]
+    f052: Vec<u32>,
+    #[fill(env = "BENCH_F053")]
+    f053: HashMap<String, u32>,
+    #[fill(env = "BENCH_F054")]
+    f054: u64,
+    #[fill(env = "BENCH_F055")]
+    f055: Vec<u32>,
+    #[fill(env = "BENCH_F056")]
+    f056: HashMap<String, u32>,
+    #[fill(env = "BENCH_F057")]
+    f057: u64,
+    #[fill(env = "BENCH_F058")]
+    f058: Vec<u32>,
+    #[fill(env = "BENCH_F059")]
+    f059: HashMap<String, u32>,
+    #[fill(env = "BENCH_F060")]
+    f060: u64,
+    #[fill(env = "BENCH_F061")]
+    f061: Vec<u32>,
+    #[fill(env = "BENCH_F062")]
+    f062: HashMap<String, u32>,
+    #[fill(env = "BENCH_F063")]
+    f063: u64,
+    #[fill(env = "BENCH_F064")]
+    f064: Vec<u32>,
+    #[fill(env = "BENCH_F065")]
+    f065: HashMap<String, u32>,
+    #[fill(env = "BENCH_F066")]
+    f066: u64,
+    #[fill(env = "BENCH_F067")]
+    f067: Vec<u32>,
+    #[fill(env = "BENCH_F068")]
+    f068: HashMap<String, u32>,
+    #[fill(env = "BENCH_F069")]
+    f069: u64,
+    #[fill(env = "BENCH_F070")]
+    f070: Vec<u32>,
+    #[fill(env = "BENCH_F071")]
+    f071: HashMap<String, u32>,
+    #[fill(env = "BENCH_F072")]
+    f072: u64,
+    #[fill(env = "BENCH_F073")]
+    f073: Vec<u32>,
+    #[fill(env = "BENCH_F074")]
+    f074: HashMap<String, u32>,
+    #[fill(env = "BENCH_F075")]
+    f075: u64,
+    #[fill(env = "BENCH_F076")]
+    f076: Vec<u32>,
+    #[fill(env = "BENCH_F077")]
+    f077: HashMap<String, u32>,
+    #[fill(env = "BENCH_F078")]
+    f078: u64,
+    #[fill(env = "BENCH_F079")]
+    f079: Vec<u32>,
+    #[fill(env = "BENCH_F080")]
+    f080: HashMap<String, u32>,
+    #[fill(env = "BENCH_F081")]
+    f081: u64,
+    #[fill(env = "BENCH_F082")]
+    f082: Vec<u32>,
+    #[fill(env = "BENCH_F083")]
+    f083: HashMap<String, u32>,
+    #[fill(env = "BENCH_F084")]
+    f084: u64,
+    #[fill(env = "BENCH_F085")]
+    f085: Vec<u32>,
+    #[fill(env = "BENCH_F086")]
+    f086: HashMap<String, u32>,
+    #[fill(env = "BENCH_F087")]
+    f087: u64,
+    #[fill(env = "BENCH_F088")]
+    f088: Vec<u32>,
+    #[fill(env = "BENCH_F089")]
+    f089: HashMap<String, u32>,
+    #[fill(env = "BENCH_F090")]
+    f090: u64,
+    #[fill(env = "BENCH_F091")]
+    f091: Vec<u32>,
+    #[fill(env = "BENCH_F092")]
+    f092: HashMap<String, u32>,
+    #[fill(env = "BENCH_F093")]
+    f093: u64,
+    #[fill(env = "BENCH_F094")]
+    f094: Vec<u32>,
+    #[fill(env = "BENCH_F095")]
+    f095: HashMap<String, u32>,
+    #[fill(env = "BENCH_F096")]
+    f096: u64,
+    #[fill(env = "BENCH_F097")]
+    f097: Vec<u32>,
+    #[fill(env = "BENCH_F098")]
+    f098: HashMap<String, u32>,
+    #[fill(env = "BENCH_F099")]
+    f099: u64,
+}
+
+fn values() -> Vec<(String, String)> {
+    (0..100)
+        .map(|index| {
+            let value = match index % 3 {
+                0 => "12345",
+                1 => "1, 2, 3, 4, 5, 6, 7, 8",
+                _ => "a=1, b=2, c=3, d=4",
+            };
+            (format!("BENCH_F{index:03}"), value.to_string())
+        })
+        .collect()
+}
+
+fn bench_load(c: &mut Criterion) {
+    let values = values();
+
+    let dotenv: String = values
+        .iter()
+        .map(|(key, value)| format!("{key}=\"{value}\"\n"))
+        .collect();
+    std::fs::write(DOTENV, dotenv).expect("failed to write dotenv file");
+    c.bench_function("load_100_fields_dotenv", |b| {
+        b.iter(|| Config::try_envoke().expect("failed to load config"))
+    });
+
+    for (key, value) in &values {
+        std::env::set_var(key, value);
+    }
+    c.bench_function("load_100_fields_env", |b| {
+        b.iter(|| Config::try_envoke().expect("failed to load config"))
+    });
+}
+
+criterion_group!(benches, bench_load);
+criterion_main!(benches);
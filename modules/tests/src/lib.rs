@@ -2,13 +2,41 @@
 #[allow(dead_code)]
 mod tests {
     use std::{
-        collections::{BTreeMap, HashMap},
+        collections::{BTreeMap, HashMap, HashSet},
+        marker::PhantomData,
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
         str::FromStr,
         time::Duration,
     };
 
     use envoke::{Envoke, Fill};
-    use secrecy::Secret;
+    use secrecy::ExposeSecret;
+
+    /// Unwraps the parse error attached to `field`, panicking on any other
+    /// error
+    fn parse_error_of(err: envoke::Error, field: &str) -> envoke::ParseError {
+        match err {
+            envoke::Error::ParseError(envoke::ParseError::Failed { field: name, err })
+                if name == field =>
+            {
+                *err.downcast().expect("expected a parse error")
+            }
+            err => panic!("expected a parse error of `{field}`, got {err:?}"),
+        }
+    }
+
+    /// Unwraps the retrieve error attached to `field`, panicking on any other
+    /// error
+    fn retrieve_error_of(err: envoke::Error, field: &str) -> envoke::RetrieveError {
+        match err {
+            envoke::Error::RetrieveError(envoke::RetrieveError::Failed { field: name, err })
+                if name == field =>
+            {
+                *err
+            }
+            err => panic!("expected a retrieve error of `{field}`, got {err:?}"),
+        }
+    }
 
     #[test]
     fn test_no_env_given() {
@@ -581,7 +609,7 @@ mod tests {
         }
 
         fn add_ten_opt(amount: Option<u64>) -> Option<u64> {
-            amount.and_then(|x| Some(x + 10))
+            amount.map(|x| x + 10)
         }
 
         fn add_ten(amount: u64) -> u64 {
@@ -956,8 +984,63 @@ mod tests {
         #[derive(Fill)]
         struct Test {
             #[fill(env, env = "ENV1", env = "ENV2")]
-            field: Secret<String>,
+            field: secrecy::SecretString,
+
+            #[fill(env = "SECRET_TOKEN")]
+            token: Option<secrecy::SecretBox<str>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("field", None),
+                ("ENV1", Some("hunter2")),
+                ("SECRET_TOKEN", Some("abc123")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.field.expose_secret(), "hunter2");
+                assert_eq!(test.token.unwrap().expose_secret(), "abc123");
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("field", None),
+                ("ENV1", None),
+                ("ENV2", Some("swordfish")),
+                ("SECRET_TOKEN", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.field.expose_secret(), "swordfish");
+                assert!(test.token.is_none());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_user_secret_string_type() {
+        #[derive(Debug, PartialEq)]
+        struct SecretString(String);
+
+        impl std::str::FromStr for SecretString {
+            type Err = std::convert::Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(SecretString(s.chars().rev().collect()))
+            }
         }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "USER_SECRET")]
+            secret: SecretString,
+        }
+
+        temp_env::with_var("USER_SECRET", Some("abc"), || {
+            let test = Test::envoke();
+            assert_eq!(test.secret, SecretString("cba".to_string()));
+        });
     }
 
     #[test]
@@ -992,4 +1075,5438 @@ mod tests {
             assert_eq!(test.field, 11);
         });
     }
+
+    #[test]
+    fn test_load_glob_pattern() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env)]
+            log_glob: glob::Pattern,
+
+            #[fill(env)]
+            opt_glob: Option<glob::Pattern>,
+        }
+
+        temp_env::with_vars([("log_glob", Some("*.log")), ("opt_glob", None)], || {
+            let test = Test::envoke();
+            assert!(test.log_glob.matches("app.log"));
+            assert!(!test.log_glob.matches("app.txt"));
+            assert!(test.opt_glob.is_none());
+        });
+
+        temp_env::with_var("log_glob", Some("[.log"), || {
+            let err = Test::try_envoke().err().unwrap();
+            assert!(matches!(
+                err,
+                envoke::Error::ParseError(envoke::ParseError::Failed { ref field, .. }) if field == "log_glob"
+            ));
+        });
+    }
+
+    #[test]
+    fn test_load_user_pattern_type() {
+        #[derive(Debug, PartialEq)]
+        struct Pattern(String);
+
+        impl std::str::FromStr for Pattern {
+            type Err = std::convert::Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Pattern(s.to_uppercase()))
+            }
+        }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "USER_PATTERN")]
+            pattern: Pattern,
+        }
+
+        temp_env::with_var("USER_PATTERN", Some("[.log"), || {
+            let test = Test::envoke();
+            assert_eq!(test.pattern, Pattern("[.LOG".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_to_json_redacted() {
+        #[derive(Fill)]
+        #[fill(json)]
+        struct Database {
+            #[fill(env = "DB_USER")]
+            user: String,
+
+            #[fill(env = "DB_PASSWORD", sensitive)]
+            password: String,
+        }
+
+        #[derive(Fill)]
+        #[fill(json)]
+        enum Mode {
+            Production,
+            Development,
+        }
+
+        #[derive(Fill)]
+        #[fill(json)]
+        struct Test {
+            #[fill(env = "PORT")]
+            port: u16,
+
+            #[fill(env = "TAGS")]
+            tags: Vec<String>,
+
+            #[fill(nested)]
+            db: Database,
+
+            #[fill(nested)]
+            mode: Mode,
+        }
+
+        temp_env::with_vars(
+            [
+                ("PORT", Some("8080")),
+                ("TAGS", Some("a,b")),
+                ("DB_USER", Some("admin")),
+                ("DB_PASSWORD", Some("hunter2")),
+                ("Mode", Some("Production")),
+            ],
+            || {
+                let test = Test::envoke();
+                let json = test.to_json_redacted().unwrap();
+                assert_eq!(
+                    json,
+                    r#"{"db":{"password":"***","user":"admin"},"mode":"Production","port":8080,"tags":["a","b"]}"#
+                );
+                assert!(!json.contains("hunter2"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_to_json_redacted_serialize_error() {
+        #[derive(Default)]
+        struct Opaque;
+
+        impl serde::Serialize for Opaque {
+            fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("opaque value"))
+            }
+        }
+
+        #[derive(Fill)]
+        #[fill(json)]
+        struct Inner {
+            #[fill(default)]
+            opaque: Opaque,
+        }
+
+        #[derive(Fill)]
+        #[fill(json)]
+        struct Test {
+            #[fill(default)]
+            port: u16,
+
+            #[fill(nested)]
+            inner: Inner,
+        }
+
+        let err = Test::envoke().to_json_redacted().unwrap_err();
+        assert_eq!(err.field(), Some("opaque"));
+        assert_eq!(err.category(), envoke::ErrorCategory::Serialize);
+        assert_eq!(
+            err.to_string(),
+            "Failed to serialize field `opaque`: opaque value"
+        );
+    }
+
+    #[test]
+    fn test_load_env_expand_path() {
+        use std::path::PathBuf;
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "CONFIG_PATH", expand_path)]
+            config: PathBuf,
+
+            #[fill(env = "DATA_PATH", expand_path)]
+            data: Option<PathBuf>,
+
+            #[fill(env = "RAW_PATH")]
+            raw: PathBuf,
+        }
+
+        temp_env::with_vars(
+            [
+                ("HOME", Some("/home/user")),
+                ("APP", Some("envoke")),
+                ("CONFIG_PATH", Some("~/config/${APP}.toml")),
+                ("DATA_PATH", Some("$HOME/data/$APP-${APP}")),
+                ("RAW_PATH", Some("~/$APP")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.config, PathBuf::from("/home/user/config/envoke.toml"));
+                assert_eq!(
+                    test.data,
+                    Some(PathBuf::from("/home/user/data/envoke-envoke"))
+                );
+                assert_eq!(test.raw, PathBuf::from("~/$APP"));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("CONFIG_PATH", Some("$MISSING_VAR/config")),
+                ("MISSING_VAR", None),
+                ("RAW_PATH", Some("raw")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(err.is_parse_error());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_item_parse_fn() {
+        use std::collections::BTreeMap;
+
+        fn to_duration(secs: u64) -> Duration {
+            Duration::from_secs(secs)
+        }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "TIMEOUTS", item_parse_fn = to_duration, item_arg_type = u64)]
+            timeouts: Vec<Duration>,
+
+            #[fill(env = "OPT_TIMEOUTS", item_parse_fn = to_duration, item_arg_type = u64)]
+            opt_timeouts: Option<Vec<Duration>>,
+
+            #[fill(env = "NAMED_TIMEOUTS", item_parse_fn = to_duration, item_arg_type = u64)]
+            named_timeouts: BTreeMap<String, Duration>,
+
+            #[fill(env = "OPT_NAMED_TIMEOUTS", item_parse_fn = to_duration, item_arg_type = u64)]
+            opt_named_timeouts: Option<HashMap<String, Duration>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("TIMEOUTS", Some("1,2,3")),
+                ("NAMED_TIMEOUTS", Some("read=5,write=10")),
+                ("OPT_TIMEOUTS", None),
+                ("OPT_NAMED_TIMEOUTS", Some("connect=1")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(
+                    test.timeouts,
+                    vec![
+                        Duration::from_secs(1),
+                        Duration::from_secs(2),
+                        Duration::from_secs(3)
+                    ]
+                );
+                assert_eq!(test.opt_timeouts, None);
+                assert_eq!(
+                    test.named_timeouts,
+                    BTreeMap::from([
+                        ("read".to_string(), Duration::from_secs(5)),
+                        ("write".to_string(), Duration::from_secs(10))
+                    ])
+                );
+                assert_eq!(
+                    test.opt_named_timeouts,
+                    Some(HashMap::from([(
+                        "connect".to_string(),
+                        Duration::from_secs(1)
+                    )]))
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("TIMEOUTS", Some("1,two,3")),
+                ("NAMED_TIMEOUTS", Some("read=5")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(err.is_parse_error());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_nested_pattern() {
+        #[derive(Fill)]
+        struct Dsn {
+            user: String,
+            pass: Option<String>,
+            host: String,
+
+            #[fill(env, default = 5432)]
+            port: u16,
+
+            db: String,
+        }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(
+                env = "DATABASE_URL",
+                nested,
+                pattern = r"^(?P<user>\w+)(:(?P<pass>\w+))?@(?P<host>[\w.]+)(:(?P<port>\d+))?/(?P<db>\w+)$"
+            )]
+            dsn: Dsn,
+        }
+
+        temp_env::with_var(
+            "DATABASE_URL",
+            Some("admin:secret@localhost:6543/app"),
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.dsn.user, "admin");
+                assert_eq!(test.dsn.pass, Some("secret".to_string()));
+                assert_eq!(test.dsn.host, "localhost");
+                assert_eq!(test.dsn.port, 6543);
+                assert_eq!(test.dsn.db, "app");
+            },
+        );
+
+        temp_env::with_var("DATABASE_URL", Some("admin@db.internal/app"), || {
+            let test = Test::envoke();
+            assert_eq!(test.dsn.pass, None);
+            assert_eq!(test.dsn.host, "db.internal");
+            assert_eq!(test.dsn.port, 5432);
+        });
+
+        temp_env::with_var("DATABASE_URL", Some("not a dsn"), || {
+            let err = Test::try_envoke().err().unwrap();
+            assert!(matches!(
+                err,
+                envoke::Error::ParseError(envoke::ParseError::PatternMismatch { field, .. }) if field == "dsn"
+            ));
+        });
+
+        temp_env::with_var("DATABASE_URL", Some("admin@localhost:99999/app"), || {
+            let err = Test::try_envoke().err().unwrap();
+            assert!(matches!(
+                err,
+                envoke::Error::ParseError(envoke::ParseError::Failed { field, .. }) if field == "port"
+            ));
+        });
+    }
+
+    #[test]
+    fn test_load_nested_pattern_missing_capture() {
+        #[derive(Fill)]
+        struct Inner {
+            name: String,
+            version: String,
+        }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(
+                env = "PACKAGE",
+                nested,
+                pattern = r"^(?P<name>\w+)(@(?P<version>[\d.]+))?$"
+            )]
+            package: Inner,
+        }
+
+        temp_env::with_var("PACKAGE", Some("envoke"), || {
+            let err = Test::try_envoke().err().unwrap();
+            assert!(matches!(
+                err,
+                envoke::Error::ParseError(envoke::ParseError::MissingCapture { field }) if field == "version"
+            ));
+        });
+    }
+
+    thread_local! {
+        static WARNINGS: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
+    }
+
+    fn record_warning(message: &str) {
+        WARNINGS.with(|w| w.borrow_mut().push(message.to_string()));
+    }
+
+    fn take_warnings() -> Vec<String> {
+        WARNINGS.with(|w| std::mem::take(&mut *w.borrow_mut()))
+    }
+
+    #[test]
+    fn test_load_nested_pattern_sensitive() {
+        #[derive(Debug, Fill)]
+        struct Credentials {
+            user: String,
+            pin: u32,
+        }
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(
+                env = "CREDENTIALS",
+                nested,
+                sensitive,
+                pattern = r"^(?P<user>\w+):(?P<pin>\w+)$"
+            )]
+            credentials: Credentials,
+        }
+
+        temp_env::with_var("CREDENTIALS", Some("admin:hunter2"), || {
+            let err = Test::try_envoke().unwrap_err();
+            assert_eq!(err.field(), Some("pin"));
+            assert!(!format!("{err} {err:?}").contains("hunter2"));
+        });
+
+        #[derive(Debug, Fill)]
+        struct Block {
+            #[fill(env = "CREDENTIALS", nested, sensitive, block)]
+            credentials: Credentials,
+        }
+
+        temp_env::with_var("CREDENTIALS", Some("user=admin\npin=hunter2"), || {
+            let err = Block::try_envoke().unwrap_err();
+            assert_eq!(err.field(), Some("pin"));
+            assert!(!format!("{err} {err:?}").contains("hunter2"));
+        });
+    }
+
+    #[test]
+    fn test_load_env_deprecated_optional() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "LOG_DIR", deprecated_env = "LOG_PATH")]
+            log_dir: Option<String>,
+        }
+
+        envoke::set_warning_handler(record_warning);
+        take_warnings();
+
+        temp_env::with_vars([("LOG_DIR", Some("/var/log")), ("LOG_PATH", None)], || {
+            let test = Test::envoke();
+            assert_eq!(test.log_dir, Some("/var/log".to_string()));
+            assert!(take_warnings().is_empty());
+        });
+
+        temp_env::with_vars([("LOG_DIR", None), ("LOG_PATH", Some("/tmp/log"))], || {
+            let test = Test::envoke();
+            assert_eq!(test.log_dir, Some("/tmp/log".to_string()));
+
+            let warnings = take_warnings();
+            assert_eq!(warnings.len(), 1);
+            assert!(warnings[0].contains("`LOG_PATH`"));
+            assert!(warnings[0].contains("`LOG_DIR`"));
+        });
+
+        temp_env::with_vars(
+            [
+                ("LOG_DIR", Some("/var/log")),
+                ("LOG_PATH", Some("/tmp/log")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.log_dir, Some("/var/log".to_string()));
+                assert!(take_warnings().is_empty());
+            },
+        );
+
+        temp_env::with_vars([("LOG_DIR", None::<&str>), ("LOG_PATH", None)], || {
+            let test = Test::envoke();
+            assert_eq!(test.log_dir, None);
+            assert!(take_warnings().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_load_env_deprecated() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(deprecated_env = "WORKERS", default = 1)]
+            num_workers: u32,
+        }
+
+        envoke::set_warning_handler(record_warning);
+        take_warnings();
+
+        temp_env::with_vars([("num_workers", None), ("WORKERS", Some("4"))], || {
+            let test = Test::envoke();
+            assert_eq!(test.num_workers, 4);
+
+            let warnings = take_warnings();
+            assert_eq!(warnings.len(), 1);
+            assert!(warnings[0].contains("`WORKERS`"));
+            assert!(warnings[0].contains("`num_workers`"));
+        });
+
+        temp_env::with_vars([("num_workers", None::<&str>), ("WORKERS", None)], || {
+            let test = Test::envoke();
+            assert_eq!(test.num_workers, 1);
+            assert!(take_warnings().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_load_env_conflicts_with() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "TLS_CERT_PATH", conflicts_with = "cert_inline")]
+            cert_path: Option<String>,
+
+            #[fill(env = "TLS_CERT_INLINE")]
+            cert_inline: Option<String>,
+
+            #[fill(env = "TLS_PORT", default = 443)]
+            port: u16,
+        }
+
+        temp_env::with_vars(
+            [
+                ("TLS_CERT_PATH", Some("/etc/cert.pem")),
+                ("TLS_CERT_INLINE", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.cert_path, Some("/etc/cert.pem".to_string()));
+                assert_eq!(test.cert_inline, None);
+                assert_eq!(test.port, 443);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("TLS_CERT_PATH", Some("/etc/cert.pem")),
+                ("TLS_CERT_INLINE", Some("-----BEGIN CERTIFICATE-----")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ValidationError(envoke::ValidationError::Conflict { field, other })
+                        if field == "cert_path" && other == "cert_inline"
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_required_if() {
+        #[derive(Debug, PartialEq, Fill)]
+        #[fill(env = "PROTOCOL", rename_all = "UPPERCASE")]
+        enum Protocol {
+            Http,
+            Https,
+        }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "AUTH_MODE", default = "none")]
+            auth_mode: String,
+
+            #[fill(
+                env = "CLIENT_SECRET",
+                required_if(field = "auth_mode", equals = "oauth")
+            )]
+            client_secret: Option<String>,
+
+            #[fill(nested)]
+            protocol: Protocol,
+
+            #[fill(
+                env = "CERT_PATH",
+                required_if(field = "protocol", equals = Protocol::Https)
+            )]
+            cert_path: Option<String>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("AUTH_MODE", Some("oauth")),
+                ("CLIENT_SECRET", Some("secret")),
+                ("PROTOCOL", Some("HTTP")),
+                ("CERT_PATH", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.client_secret, Some("secret".to_string()));
+                assert_eq!(test.cert_path, None);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("AUTH_MODE", None),
+                ("CLIENT_SECRET", None),
+                ("PROTOCOL", Some("HTTP")),
+                ("CERT_PATH", None::<&str>),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.auth_mode, "none");
+                assert_eq!(test.client_secret, None);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("AUTH_MODE", Some("oauth")),
+                ("CLIENT_SECRET", None),
+                ("PROTOCOL", Some("HTTP")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ValidationError(envoke::ValidationError::Required { ref field, ref other, ref value })
+                        if field == "client_secret" && other == "auth_mode" && value == "oauth"
+                ));
+                assert_eq!(
+                    err.to_string(),
+                    "Validation error occurred: `client_secret` is required when `auth_mode` is \
+                     `oauth`"
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("AUTH_MODE", None),
+                ("PROTOCOL", Some("HTTPS")),
+                ("CERT_PATH", None::<&str>),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ValidationError(envoke::ValidationError::Required { ref field, ref value, .. })
+                        if field == "cert_path" && value == "Protocol::Https"
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_group_separators() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "MAX_ROWS", group_separators)]
+            max_rows: u64,
+
+            #[fill(env = "MAX_BYTES", group_separators = ",")]
+            max_bytes: usize,
+
+            #[fill(env = "MIN_OFFSET", group_separators = " ")]
+            min_offset: Option<i32>,
+
+            #[fill(env = "LIMITS", delimiter = ";")]
+            limits: Vec<u32>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("MAX_ROWS", Some("1_000_000")),
+                ("MAX_BYTES", Some("1,048,576")),
+                ("MIN_OFFSET", Some("-2 000")),
+                ("LIMITS", Some("1;2;3")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.max_rows, 1_000_000);
+                assert_eq!(test.max_bytes, 1_048_576);
+                assert_eq!(test.min_offset, Some(-2_000));
+                assert_eq!(test.limits, vec![1, 2, 3]);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("MAX_ROWS", Some("1,000")),
+                ("MAX_BYTES", Some("1_000")),
+                ("MIN_OFFSET", None),
+                ("LIMITS", Some("1")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ParseError(envoke::ParseError::Failed { field, .. }) if field == "max_rows"
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_schema() {
+        #[derive(Fill, serde::Serialize)]
+        #[fill(schema = "schemas/server.schema.json")]
+        struct Test {
+            #[fill(env = "SERVER_HOST")]
+            host: String,
+
+            #[fill(env = "SERVER_PORT")]
+            port: u16,
+        }
+
+        temp_env::with_vars(
+            [
+                ("SERVER_HOST", Some("localhost")),
+                ("SERVER_PORT", Some("8080")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.port, 8080);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("SERVER_HOST", Some("localhost")),
+                ("SERVER_PORT", Some("80")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ValidationError(envoke::ValidationError::Schema { instance_path, schema_path, .. })
+                        if instance_path == "/port" && schema_path == "/properties/port/minimum"
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_schema_unserializable() {
+        struct Opaque;
+
+        impl std::str::FromStr for Opaque {
+            type Err = std::convert::Infallible;
+
+            fn from_str(_: &str) -> Result<Self, Self::Err> {
+                Ok(Opaque)
+            }
+        }
+
+        impl serde::Serialize for Opaque {
+            fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("opaque value"))
+            }
+        }
+
+        #[derive(Fill, serde::Serialize)]
+        #[fill(schema = "schemas/server.schema.json")]
+        struct Test {
+            #[fill(env = "SERVER_HOST")]
+            host: Opaque,
+        }
+
+        temp_env::with_var("SERVER_HOST", Some("localhost"), || {
+            let err = Test::try_envoke().err().unwrap();
+            assert_eq!(err.field(), None);
+            assert!(matches!(
+                err,
+                envoke::Error::ValidationError(envoke::ValidationError::Unserializable { path, .. })
+                    if path == "schemas/server.schema.json"
+            ));
+        });
+    }
+
+    #[test]
+    fn test_load_config_json() {
+        #[derive(Fill, serde::Deserialize)]
+        struct Database {
+            #[fill(env = "DB_URL")]
+            url: String,
+        }
+
+        #[derive(Fill, serde::Deserialize)]
+        #[fill(config_json = "APP_CONFIG_JSON")]
+        struct Test {
+            #[fill(env = "APP_HOST")]
+            host: String,
+
+            #[fill(env = "APP_PORT", default = 8080)]
+            port: u16,
+
+            #[fill(env = "APP_TAGS")]
+            tags: Option<Vec<String>>,
+
+            #[fill(nested)]
+            database: Database,
+        }
+
+        let json = r#"{
+            "host": "example.com",
+            "port": 443,
+            "tags": ["a", "b"],
+            "database": { "url": "postgres://json" }
+        }"#;
+
+        temp_env::with_vars(
+            [
+                ("APP_CONFIG_JSON", Some(json)),
+                ("APP_HOST", None),
+                ("APP_PORT", Some("9000")),
+                ("APP_TAGS", None),
+                ("DB_URL", Some("postgres://env")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.host, "example.com");
+                assert_eq!(test.port, 9000);
+                assert_eq!(test.tags, Some(vec!["a".to_string(), "b".to_string()]));
+                assert_eq!(test.database.url, "postgres://json");
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("APP_CONFIG_JSON", None),
+                ("APP_HOST", Some("localhost")),
+                ("APP_PORT", None),
+                ("APP_TAGS", None),
+                ("DB_URL", Some("postgres://env")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.host, "localhost");
+                assert_eq!(test.port, 8080);
+                assert_eq!(test.tags, None);
+                assert_eq!(test.database.url, "postgres://env");
+            },
+        );
+
+        temp_env::with_var("APP_CONFIG_JSON", Some("{ not json"), || {
+            let err = Test::try_envoke().err().unwrap();
+            assert!(matches!(
+                err,
+                envoke::Error::ParseError(envoke::ParseError::Failed { field, .. }) if field == "APP_CONFIG_JSON"
+            ));
+        });
+    }
+
+    #[test]
+    fn test_load_config_json_prefix() {
+        use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+        #[derive(Fill, serde::Deserialize)]
+        #[fill(prefix = "SVC_", config_json = "CONFIG_JSON")]
+        struct Test {
+            #[fill(env = "HOST")]
+            host: String,
+        }
+
+        temp_env::with_vars(
+            [
+                ("SVC_CONFIG_JSON", Some(r#"{ "host": "example.com" }"#)),
+                ("CONFIG_JSON", Some(r#"{ "host": "unprefixed" }"#)),
+                ("SVC_HOST", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.host, "example.com");
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                (
+                    "SVC_CONFIG_JSON",
+                    Some(OsString::from_vec(vec![0xff, 0xfe])),
+                ),
+                ("SVC_HOST", Some(OsString::from("localhost"))),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(matches!(
+                    err,
+                    envoke::Error::RetrieveError(envoke::RetrieveError::InvalidUnicode { ref key }) if key == "SVC_CONFIG_JSON"
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_config_json_deny_unknown() {
+        #[derive(Debug, Fill, serde::Deserialize)]
+        #[fill(prefix = "SVC_", config_json = "CONFIG_JSON", deny_unknown)]
+        struct Test {
+            #[fill(env = "HOST")]
+            host: String,
+        }
+
+        assert_eq!(Test::env_keys(), ["SVC_CONFIG_JSON", "SVC_HOST"]);
+
+        temp_env::with_vars(
+            [
+                ("SVC_CONFIG_JSON", Some(r#"{ "host": "example.com" }"#)),
+                ("SVC_HOST", None),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.host, "example.com");
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_keep_empty() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "COLS", keep_empty)]
+            cols: Vec<String>,
+
+            #[fill(env = "OPT_COLS", keep_empty, delimiter = ";")]
+            opt_cols: Option<Vec<String>>,
+
+            #[fill(env = "STRICT_COLS")]
+            strict_cols: Vec<String>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("COLS", Some(",b,,d,")),
+                ("OPT_COLS", Some("a; ;c")),
+                ("STRICT_COLS", Some("a,b")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.cols, vec!["", "b", "", "d", ""]);
+                assert_eq!(
+                    test.opt_cols,
+                    Some(vec!["a".to_string(), String::new(), "c".to_string()])
+                );
+                assert_eq!(test.strict_cols, vec!["a", "b"]);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("COLS", Some("a")),
+                ("OPT_COLS", None),
+                ("STRICT_COLS", Some("a,,c")),
+            ],
+            || {
+                let err = parse_error_of(Test::try_envoke().err().unwrap(), "strict_cols");
+                assert!(matches!(
+                    err,
+                    envoke::ParseError::InvalidElement { index: 1, err } if err.is_missing_value()
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_interval() {
+        use std::ops::Bound;
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "CLOSED")]
+            closed: (Bound<u32>, Bound<u32>),
+
+            #[fill(env = "OPEN")]
+            open: (Bound<u32>, Bound<u32>),
+
+            #[fill(env = "HALF_OPEN")]
+            half_open: (Bound<i64>, Bound<i64>),
+
+            #[fill(env = "HALF_CLOSED")]
+            half_closed: (Bound<f64>, Bound<f64>),
+
+            #[fill(env = "UNBOUNDED_START")]
+            unbounded_start: (Bound<u32>, Bound<u32>),
+
+            #[fill(env = "UNBOUNDED")]
+            unbounded: Option<(Bound<u32>, Bound<u32>)>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("CLOSED", Some("[0,100]")),
+                ("OPEN", Some("(0, 100)")),
+                ("HALF_OPEN", Some("[-5,5)")),
+                ("HALF_CLOSED", Some("(0.5,1.5]")),
+                ("UNBOUNDED_START", Some("(,100]")),
+                ("UNBOUNDED", Some("(,)")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.closed, (Bound::Included(0), Bound::Included(100)));
+                assert_eq!(test.open, (Bound::Excluded(0), Bound::Excluded(100)));
+                assert_eq!(test.half_open, (Bound::Included(-5), Bound::Excluded(5)));
+                assert_eq!(
+                    test.half_closed,
+                    (Bound::Excluded(0.5), Bound::Included(1.5))
+                );
+                assert_eq!(
+                    test.unbounded_start,
+                    (Bound::Unbounded, Bound::Included(100))
+                );
+                assert_eq!(test.unbounded, Some((Bound::Unbounded, Bound::Unbounded)));
+            },
+        );
+
+        for invalid in ["0,100", "[0;100]", "{0,100]", "[0,a]", "["] {
+            temp_env::with_vars(
+                [
+                    ("CLOSED", Some(invalid)),
+                    ("OPEN", Some("(0,1)")),
+                    ("HALF_OPEN", Some("(0,1)")),
+                    ("HALF_CLOSED", Some("(0,1)")),
+                    ("UNBOUNDED_START", Some("(0,1)")),
+                    ("UNBOUNDED", None),
+                ],
+                || {
+                    let err = Test::try_envoke().err().unwrap();
+                    assert!(matches!(
+                        err,
+                        envoke::Error::ParseError(envoke::ParseError::Failed { field, .. }) if field == "closed"
+                    ));
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_env_duration() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "TIMEOUT", duration)]
+            timeout: Duration,
+
+            #[fill(env = "GRACE_PERIOD", duration, default = Duration::from_secs(5))]
+            grace_period: Duration,
+
+            #[fill(env = "INTERVALS", duration)]
+            intervals: Vec<Duration>,
+
+            #[fill(env = "DEADLINES", duration)]
+            deadlines: Option<HashMap<String, Duration>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("TIMEOUT", Some("1h 30m")),
+                ("GRACE_PERIOD", None),
+                ("INTERVALS", Some("30s,5m,1h,250ms")),
+                ("DEADLINES", Some("read=2s,write=1m30s")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.timeout, Duration::from_secs(90 * 60));
+                assert_eq!(test.grace_period, Duration::from_secs(5));
+                assert_eq!(
+                    test.intervals,
+                    vec![
+                        Duration::from_secs(30),
+                        Duration::from_secs(5 * 60),
+                        Duration::from_secs(60 * 60),
+                        Duration::from_millis(250),
+                    ]
+                );
+                assert_eq!(
+                    test.deadlines,
+                    Some(HashMap::from([
+                        ("read".to_string(), Duration::from_secs(2)),
+                        ("write".to_string(), Duration::from_secs(90)),
+                    ]))
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("TIMEOUT", Some("10s")),
+                ("INTERVALS", Some("30s,5x,1h")),
+                ("DEADLINES", None),
+            ],
+            || {
+                let err = parse_error_of(Test::try_envoke().err().unwrap(), "intervals");
+                assert!(matches!(
+                    err,
+                    envoke::ParseError::InvalidElement { index: 1, err } if err.is_invalid_duration()
+                ));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("TIMEOUT", Some("10")),
+                ("INTERVALS", Some("1s")),
+                ("DEADLINES", None),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ParseError(envoke::ParseError::Failed { field, .. }) if field == "timeout"
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_env_keys() {
+        #[derive(Fill)]
+        #[fill(prefix = "APP_", rename_all = "UPPERCASE")]
+        struct Database {
+            #[fill(env, env = "DB_URL")]
+            url: String,
+        }
+
+        #[derive(Fill)]
+        #[fill(prefix = "APP_", rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, deprecated_env = "HOSTNAME")]
+            host: String,
+
+            #[fill(env, no_prefix)]
+            port: u16,
+
+            #[fill(nested)]
+            database: Database,
+
+            #[fill(default = 4)]
+            workers: u32,
+
+            #[fill(ignore)]
+            ignored: Option<String>,
+        }
+
+        assert_eq!(
+            Test::env_keys(),
+            vec!["APP_HOST", "APP_HOSTNAME", "PORT", "APP_URL", "APP_DB_URL"]
+        );
+    }
+
+    #[test]
+    fn test_load_from_map() {
+        #[derive(Fill)]
+        struct Database {
+            #[fill(env = "FROM_MAP_DB_URL")]
+            url: String,
+        }
+
+        #[derive(Fill)]
+        #[fill(from_map)]
+        struct Test {
+            #[fill(env = "FROM_MAP_HOST")]
+            host: String,
+
+            #[fill(env = "FROM_MAP_PORT", default = 8080)]
+            port: u16,
+
+            #[fill(nested)]
+            database: Database,
+        }
+
+        let source = HashMap::from([
+            ("FROM_MAP_HOST".to_string(), "localhost".to_string()),
+            ("FROM_MAP_DB_URL".to_string(), "postgres://db".to_string()),
+        ]);
+
+        temp_env::with_vars(
+            [
+                ("FROM_MAP_HOST", Some("example.com")),
+                ("FROM_MAP_PORT", Some("9090")),
+                ("FROM_MAP_DB_URL", Some("sqlite://")),
+            ],
+            || {
+                // Only the map is read, not the process environment
+                let test = Test::try_from(source).unwrap();
+                assert_eq!(test.host, "localhost");
+                assert_eq!(test.port, 8080);
+                assert_eq!(test.database.url, "postgres://db");
+
+                assert!(Test::try_from(HashMap::new()).is_err());
+
+                // The process environment is used again afterwards
+                let test = Test::envoke();
+                assert_eq!(test.host, "example.com");
+                assert_eq!(test.port, 9090);
+                assert_eq!(test.database.url, "sqlite://");
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_enum_by_name_or_index() {
+        #[derive(Debug, PartialEq, strum::EnumString, strum::VariantNames)]
+        #[strum(serialize_all = "lowercase")]
+        enum Mode {
+            Development,
+            Staging,
+            Production,
+        }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "ENUM_BY_MODE", enum_by = "name_or_index")]
+            mode: Mode,
+
+            #[fill(env = "ENUM_BY_FALLBACK", enum_by = "name_or_index")]
+            fallback: Option<Mode>,
+
+            #[fill(env = "ENUM_BY_MODES", enum_by = "name_or_index")]
+            modes: Vec<Mode>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("ENUM_BY_MODE", Some("2")),
+                ("ENUM_BY_FALLBACK", Some("staging")),
+                ("ENUM_BY_MODES", Some("0,staging,2")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.mode, Mode::Production);
+                assert_eq!(test.fallback, Some(Mode::Staging));
+                assert_eq!(
+                    test.modes,
+                    vec![Mode::Development, Mode::Staging, Mode::Production]
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ENUM_BY_MODE", Some("3")),
+                ("ENUM_BY_FALLBACK", None),
+                ("ENUM_BY_MODES", Some("")),
+            ],
+            || {
+                let result = Test::try_envoke();
+                assert!(result.is_err());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_field_prefix_suffix() {
+        #[derive(Fill)]
+        #[fill(prefix = "APP", suffix = "VAR", delimiter = "_")]
+        struct Test {
+            #[fill(env = "NAME")]
+            name: String,
+
+            #[fill(env = "URL", prefix = "DB")]
+            database_url: String,
+
+            #[fill(env = "PORT", suffix = "V2")]
+            port: u16,
+
+            #[fill(env = "HOST", prefix = "DB", no_suffix)]
+            database_host: String,
+        }
+
+        temp_env::with_vars(
+            [
+                ("APP_NAME_VAR", Some("app")),
+                ("DB_URL_VAR", Some("postgres://db")),
+                ("APP_PORT_V2", Some("8080")),
+                ("DB_HOST", Some("localhost")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.name, "app");
+                assert_eq!(test.database_url, "postgres://db");
+                assert_eq!(test.port, 8080);
+                assert_eq!(test.database_host, "localhost");
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_prefer_dotenv() {
+        #[derive(Fill)]
+        #[fill(dotenv = "dotenv/prefer_dotenv.env")]
+        struct Test {
+            #[fill(env = "PREFER_DOTENV_HOST", prefer_dotenv)]
+            host: String,
+
+            #[fill(env = "PREFER_DOTENV_PORT")]
+            port: u16,
+        }
+
+        temp_env::with_vars(
+            [
+                ("PREFER_DOTENV_HOST", Some("env.local")),
+                ("PREFER_DOTENV_PORT", Some("8000")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.host, "dotenv.local");
+                assert_eq!(test.port, 8000);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("PREFER_DOTENV_HOST", None::<&str>),
+                ("PREFER_DOTENV_PORT", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.host, "dotenv.local");
+                assert_eq!(test.port, 9000);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_dotenv_collections() {
+        use std::collections::HashMap;
+
+        #[derive(Fill)]
+        #[fill(dotenv = "dotenv/collections.env")]
+        struct Test {
+            #[fill(env = "COLLECTIONS_TAGS")]
+            tags: Vec<String>,
+
+            #[fill(env = "COLLECTIONS_LIMITS")]
+            limits: HashMap<String, u32>,
+        }
+
+        // Values from the dotenv file are trimmed and parsed like those from
+        // the environment
+        temp_env::with_vars(
+            [
+                ("COLLECTIONS_TAGS", None::<&str>),
+                ("COLLECTIONS_LIMITS", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.tags, ["a", "b", "c"]);
+                assert_eq!(
+                    test.limits,
+                    HashMap::from([("x".to_string(), 1), ("y".to_string(), 2)])
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("COLLECTIONS_TAGS", Some("  d,e  ")),
+                ("COLLECTIONS_LIMITS", Some(" z = 3 ")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.tags, ["d", "e"]);
+                assert_eq!(test.limits, HashMap::from([("z".to_string(), 3)]));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_radix_auto() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "RADIX_FLAGS", radix_auto)]
+            flags: u8,
+
+            #[fill(env = "RADIX_MODE", radix_auto)]
+            mode: u32,
+
+            #[fill(env = "RADIX_MASK", radix_auto)]
+            mask: Option<u16>,
+
+            #[fill(env = "RADIX_OFFSET", radix_auto)]
+            offset: i64,
+        }
+
+        temp_env::with_vars(
+            [
+                ("RADIX_FLAGS", Some("0xFF")),
+                ("RADIX_MODE", Some("0o755")),
+                ("RADIX_MASK", Some("0b1010_1010")),
+                ("RADIX_OFFSET", Some("-1_000_000")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.flags, 0xFF);
+                assert_eq!(test.mode, 0o755);
+                assert_eq!(test.mask, Some(0b1010_1010));
+                assert_eq!(test.offset, -1_000_000);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("RADIX_FLAGS", Some("0x100")),
+                ("RADIX_MODE", Some("0")),
+                ("RADIX_MASK", None),
+                ("RADIX_OFFSET", Some("0")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                let envoke::Error::ParseError(envoke::ParseError::Failed { err, .. }) = err else {
+                    panic!("unexpected error: {err}");
+                };
+                let err = err.downcast::<envoke::ParseError>().unwrap();
+                assert!(err.is_integer_overflow());
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("RADIX_FLAGS", Some("0xFG")),
+                ("RADIX_MODE", Some("0")),
+                ("RADIX_MASK", None),
+                ("RADIX_OFFSET", Some("0")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                let envoke::Error::ParseError(envoke::ParseError::Failed { err, .. }) = err else {
+                    panic!("unexpected error: {err}");
+                };
+                let err = err.downcast::<envoke::ParseError>().unwrap();
+                assert!(err.is_unexpected_value_type());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_array_vec() {
+        use arrayvec::ArrayVec;
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "ARRAY_VEC_HOSTS")]
+            hosts: ArrayVec<String, 3>,
+
+            #[fill(env = "ARRAY_VEC_PORTS")]
+            ports: Option<ArrayVec<u16, 2>>,
+        }
+
+        temp_env::with_vars(
+            [("ARRAY_VEC_HOSTS", Some("a,b")), ("ARRAY_VEC_PORTS", None)],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.hosts.as_slice(), ["a", "b"]);
+                assert!(test.ports.is_none());
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ARRAY_VEC_HOSTS", Some("a,b,c")),
+                ("ARRAY_VEC_PORTS", Some("80,443")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.hosts.as_slice(), ["a", "b", "c"]);
+                assert_eq!(test.ports.unwrap().as_slice(), [80, 443]);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ARRAY_VEC_HOSTS", Some("a,b,c,d")),
+                ("ARRAY_VEC_PORTS", None),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                let envoke::Error::ParseError(envoke::ParseError::Failed { err, .. }) = err else {
+                    panic!("unexpected error: {err}");
+                };
+                let err = err.downcast::<envoke::ParseError>().unwrap();
+                assert!(matches!(
+                    *err,
+                    envoke::ParseError::CapacityExceeded {
+                        len: 4,
+                        capacity: 3
+                    }
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_env_line() {
+        #[derive(Fill)]
+        #[fill(prefix = "APP_", rename_all = "UPPERCASE", env_line)]
+        struct Database {
+            #[fill(env)]
+            url: String,
+
+            #[fill(env, sensitive)]
+            password: String,
+        }
+
+        #[derive(Fill)]
+        #[fill(prefix = "APP_", rename_all = "UPPERCASE", env_line)]
+        struct Test {
+            #[fill(env)]
+            host: String,
+
+            #[fill(env, delimiter = ";")]
+            ports: Vec<u16>,
+
+            #[fill(env)]
+            labels: BTreeMap<String, String>,
+
+            #[fill(env)]
+            timeout: Option<u64>,
+
+            #[fill(nested)]
+            database: Database,
+        }
+
+        temp_env::with_vars(
+            [
+                ("APP_HOST", Some("localhost")),
+                ("APP_PORTS", Some("80;443")),
+                ("APP_LABELS", Some("a=1,b=2")),
+                ("APP_TIMEOUT", None),
+                ("APP_URL", Some("postgres://db")),
+                ("APP_PASSWORD", Some("hunter2")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.env_line("host").as_deref(), Some("APP_HOST=localhost"));
+                assert_eq!(test.env_line("ports").as_deref(), Some("APP_PORTS=80;443"));
+                assert_eq!(
+                    test.env_line("labels").as_deref(),
+                    Some("APP_LABELS=a=1,b=2")
+                );
+                assert_eq!(test.env_line("timeout"), None);
+                assert_eq!(
+                    test.env_line("url").as_deref(),
+                    Some("APP_URL=postgres://db")
+                );
+                assert_eq!(
+                    test.env_line("password").as_deref(),
+                    Some("APP_PASSWORD=***")
+                );
+                assert_eq!(test.env_line("unknown"), None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_nested_optional() {
+        #[derive(Fill)]
+        struct Tls {
+            #[fill(env = "OPTIONAL_TLS_CERT")]
+            cert: String,
+
+            #[fill(env = "OPTIONAL_TLS_PORT")]
+            port: u16,
+
+            #[fill(env = "OPTIONAL_TLS_VERIFY", default = true)]
+            verify: bool,
+        }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(nested)]
+            tls: Option<Tls>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("OPTIONAL_TLS_CERT", None::<&str>),
+                ("OPTIONAL_TLS_PORT", None),
+                ("OPTIONAL_TLS_VERIFY", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert!(test.tls.is_none());
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("OPTIONAL_TLS_CERT", Some("cert.pem")),
+                ("OPTIONAL_TLS_PORT", Some("8443")),
+                ("OPTIONAL_TLS_VERIFY", None),
+            ],
+            || {
+                let tls = Test::envoke().tls.unwrap();
+                assert_eq!(tls.cert, "cert.pem");
+                assert_eq!(tls.port, 8443);
+                assert!(tls.verify);
+            },
+        );
+
+        // Setting any variable requires all required ones to be set
+        temp_env::with_vars(
+            [
+                ("OPTIONAL_TLS_CERT", None),
+                ("OPTIONAL_TLS_PORT", None),
+                ("OPTIONAL_TLS_VERIFY", Some("false")),
+            ],
+            || {
+                let err = retrieve_error_of(Test::try_envoke().err().unwrap(), "cert");
+                assert!(err.is_not_found());
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("OPTIONAL_TLS_CERT", Some("cert.pem")),
+                ("OPTIONAL_TLS_PORT", Some("https")),
+                ("OPTIONAL_TLS_VERIFY", None),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                assert!(err.is_parse_error());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_kv_delimiter() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "KV_LABELS", delimiter = ";", kv_delimiter = ":")]
+            labels: HashMap<String, String>,
+
+            #[fill(env = "KV_WEIGHTS", kv_delimiter = ":")]
+            weights: Option<BTreeMap<String, u32>>,
+
+            #[fill(env = "KV_HOSTS", delimiter = "=")]
+            hosts: Vec<String>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("KV_LABELS", Some("env:prod;region:eu-west-1")),
+                ("KV_WEIGHTS", Some("a:1,b:2")),
+                ("KV_HOSTS", Some("a=b")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(
+                    test.labels,
+                    HashMap::from([
+                        ("env".to_string(), "prod".to_string()),
+                        ("region".to_string(), "eu-west-1".to_string()),
+                    ])
+                );
+                assert_eq!(
+                    test.weights,
+                    Some(BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]))
+                );
+                assert_eq!(test.hosts, vec!["a", "b"]);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("KV_LABELS", Some("env=prod")),
+                ("KV_WEIGHTS", None),
+                ("KV_HOSTS", Some("a")),
+            ],
+            || {
+                let err = parse_error_of(Test::try_envoke().err().unwrap(), "labels");
+                assert!(matches!(err, envoke::ParseError::MissingValue));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_enum_env_affixes() {
+        #[derive(Debug, PartialEq, Fill)]
+        #[fill(prefix = "APP_", suffix = "_V2", env = "MODE")]
+        #[fill(env(name = "LEGACY_MODE", no_prefix))]
+        #[fill(env(name = "DEPLOY_MODE", no_suffix))]
+        enum Mode {
+            #[fill(rename = "dev", no_prefix, no_suffix)]
+            Development,
+
+            #[fill(rename = "prod", no_prefix, no_suffix)]
+            Production,
+        }
+
+        temp_env::with_vars(
+            [
+                ("APP_MODE_V2", Some("prod")),
+                ("LEGACY_MODE_V2", Some("dev")),
+                ("APP_DEPLOY_MODE", None),
+            ],
+            || {
+                assert_eq!(Mode::envoke(), Mode::Production);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("APP_MODE_V2", None),
+                ("LEGACY_MODE_V2", Some("dev")),
+                ("APP_DEPLOY_MODE", Some("prod")),
+            ],
+            || {
+                assert_eq!(Mode::envoke(), Mode::Development);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("APP_MODE_V2", None),
+                ("LEGACY_MODE_V2", None),
+                ("APP_DEPLOY_MODE", Some("prod")),
+            ],
+            || {
+                assert_eq!(Mode::envoke(), Mode::Production);
+            },
+        );
+
+        assert_eq!(
+            Mode::env_keys(),
+            vec!["APP_MODE_V2", "LEGACY_MODE_V2", "APP_DEPLOY_MODE"]
+        );
+    }
+
+    #[test]
+    fn test_load_env_sensitive_error() {
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "PIN", sensitive)]
+            pin: u32,
+
+            #[fill(env = "TIMEOUT", duration, sensitive)]
+            timeout: std::time::Duration,
+
+            #[fill(env = "RETRIES")]
+            retries: u32,
+        }
+
+        temp_env::with_vars(
+            [
+                ("PIN", Some("hunter2")),
+                ("TIMEOUT", Some("1s")),
+                ("RETRIES", Some("3")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap().to_string();
+                assert!(err.contains("[redacted]"));
+                assert!(!err.contains("hunter2"));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("PIN", Some("1234")),
+                ("TIMEOUT", Some("soon")),
+                ("RETRIES", Some("3")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                let envoke::Error::ParseError(envoke::ParseError::Failed { err, .. }) = err else {
+                    panic!("expected a parse error");
+                };
+                assert!(err.to_string().contains("[redacted]"));
+                assert!(!err.to_string().contains("soon"));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("PIN", Some("1234")),
+                ("TIMEOUT", Some("1s")),
+                ("RETRIES", Some("many")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap().to_string();
+                assert!(err.contains("many"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_optional_chain() {
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "PRIMARY_PORT", env = "FALLBACK_PORT")]
+            port: Option<u16>,
+
+            #[fill(env = "PRIMARY_HOSTS", env = "FALLBACK_HOSTS")]
+            hosts: Option<Vec<String>>,
+        }
+
+        temp_env::with_vars_unset(
+            [
+                "PRIMARY_PORT",
+                "FALLBACK_PORT",
+                "PRIMARY_HOSTS",
+                "FALLBACK_HOSTS",
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.port, None);
+                assert_eq!(test.hosts, None);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("PRIMARY_PORT", None),
+                ("FALLBACK_PORT", Some("8080")),
+                ("FALLBACK_HOSTS", Some("a,b")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.port, Some(8080));
+                assert_eq!(test.hosts, Some(vec!["a".to_string(), "b".to_string()]));
+            },
+        );
+
+        // A set but invalid value is an error even if a fallback is valid
+        temp_env::with_vars(
+            [
+                ("PRIMARY_PORT", Some("not a port")),
+                ("FALLBACK_PORT", Some("8080")),
+            ],
+            || {
+                let err = parse_error_of(Test::try_envoke().err().unwrap(), "port");
+                assert!(err.is_unexpected_value_type());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_utf8_path() {
+        use camino::Utf8PathBuf;
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "CONFIG_DIR")]
+            config_dir: Utf8PathBuf,
+
+            #[fill(env = "CACHE_DIR", expand_path)]
+            cache_dir: Option<Utf8PathBuf>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("CONFIG_DIR", Some("/etc/app")),
+                ("CACHE_DIR", Some("$BASE_DIR/cache")),
+                ("BASE_DIR", Some("/var/lib/app")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.config_dir, Utf8PathBuf::from("/etc/app"));
+                assert_eq!(
+                    test.cache_dir,
+                    Some(Utf8PathBuf::from("/var/lib/app/cache"))
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_duration_crates() {
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "TIMEOUT", duration)]
+            timeout: time::Duration,
+
+            #[fill(env = "INTERVALS", duration)]
+            intervals: Vec<time::Duration>,
+
+            #[fill(env = "TTL", duration)]
+            ttl: chrono::Duration,
+
+            #[fill(env = "GRACE", duration)]
+            grace: Option<chrono::TimeDelta>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("TIMEOUT", Some("1m 30s")),
+                ("INTERVALS", Some("500ms,2s")),
+                ("TTL", Some("1d")),
+                ("GRACE", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.timeout, time::Duration::seconds(90));
+                assert_eq!(
+                    test.intervals,
+                    vec![
+                        time::Duration::milliseconds(500),
+                        time::Duration::seconds(2)
+                    ]
+                );
+                assert_eq!(test.ttl, chrono::Duration::days(1));
+                assert_eq!(test.grace, None);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("TIMEOUT", Some("1m 30s")),
+                ("INTERVALS", Some("500ms,2s")),
+                ("TTL", Some("1 fortnight")),
+            ],
+            || {
+                let err = Test::try_envoke().err().unwrap();
+                let envoke::Error::ParseError(envoke::ParseError::Failed { field, .. }) = err
+                else {
+                    panic!("expected a parse error");
+                };
+                assert_eq!(field, "ttl");
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_tuple_struct() {
+        #[derive(Debug, Fill)]
+        struct Port(#[fill(env = "PORT")] u16);
+
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "APP_")]
+        struct Test(
+            #[fill(env = "HOST")] String,
+            #[fill(nested)] Port,
+            #[fill(default = 3)] u8,
+            #[fill(env = "TAGS")] Option<Vec<String>>,
+        );
+
+        temp_env::with_vars(
+            [
+                ("APP_HOST", Some("localhost")),
+                ("PORT", Some("8080")),
+                ("APP_TAGS", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.0, "localhost");
+                assert_eq!(test.1 .0, 8080);
+                assert_eq!(test.2, 3);
+                assert_eq!(test.3, None);
+                assert_eq!(Test::env_keys(), vec!["APP_HOST", "PORT", "APP_TAGS"]);
+            },
+        );
+
+        temp_env::with_var("PORT", Some("not a port"), || {
+            // Fields of tuple structs are named by their index
+            let err = parse_error_of(Port::try_envoke().err().unwrap(), "0");
+            assert!(err.is_unexpected_value_type());
+        });
+    }
+
+    #[test]
+    fn test_load_env_default_env_name() {
+        fn describe(env: &str) -> String {
+            format!("{env} is not set")
+        }
+
+        fn count(envs: &[&str], extra: usize) -> usize {
+            envs.len() + extra
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "APP_", suffix = "_V1")]
+        struct Test {
+            #[fill(env = "MODE", default = describe(env_name))]
+            mode: String,
+
+            #[fill(env = "WORKERS", env = "THREADS", no_suffix, default = count(env_names, 1))]
+            workers: usize,
+        }
+
+        temp_env::with_vars_unset(["APP_MODE_V1", "APP_WORKERS", "APP_THREADS"], || {
+            let test = Test::envoke();
+            assert_eq!(test.mode, "APP_MODE_V1 is not set");
+            assert_eq!(test.workers, 3);
+        });
+    }
+
+    #[test]
+    fn test_parse_error_source() {
+        use std::error::Error as _;
+
+        fn parse_level(value: String) -> Result<u8, std::num::ParseIntError> {
+            value.parse()
+        }
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "LEVEL", try_parse_fn = parse_level, arg_type = String)]
+            level: u8,
+
+            #[fill(env = "PORTS")]
+            ports: Vec<u16>,
+        }
+
+        temp_env::with_vars([("LEVEL", Some("high")), ("PORTS", Some("80"))], || {
+            let err = Test::try_envoke().err().unwrap();
+            let envoke::Error::ParseError(err) = err else {
+                panic!("expected a parse error");
+            };
+            assert!(matches!(err, envoke::ParseError::Failed { .. }));
+
+            let source = err.source().unwrap();
+            assert!(source.is::<std::num::ParseIntError>());
+        });
+
+        temp_env::with_vars([("LEVEL", Some("1")), ("PORTS", Some("80,http"))], || {
+            let err = parse_error_of(Test::try_envoke().err().unwrap(), "ports");
+            assert!(matches!(
+                err,
+                envoke::ParseError::InvalidElement { index: 1, .. }
+            ));
+
+            let source = err.source().unwrap();
+            assert!(source.to_string().contains("http"));
+        });
+
+        let err = envoke::ParseError::failed("level", "out of range");
+        assert_eq!(err.source().unwrap().to_string(), "out of range");
+    }
+
+    #[test]
+    fn test_load_nested_block() {
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "DB_")]
+        struct Database {
+            #[fill(env = "HOST")]
+            host: String,
+
+            #[fill(env = "PORT", default = 5432)]
+            port: u16,
+        }
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(nested, block, env = "DATABASE")]
+            db: Database,
+        }
+
+        let block = "# database settings\nDB_HOST=localhost\n\nDB_PORT=\"6543\"\n";
+        temp_env::with_vars(
+            [("DATABASE", Some(block)), ("DB_HOST", Some("remote"))],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.db.host, "localhost");
+                assert_eq!(test.db.port, 6543);
+                assert_eq!(Test::env_keys(), vec!["DATABASE"]);
+            },
+        );
+
+        temp_env::with_var("DATABASE", Some("DB_HOST=localhost\nDB_USER=admin"), || {
+            let err = parse_error_of(Test::try_envoke().err().unwrap(), "db");
+            assert!(matches!(
+                err,
+                envoke::ParseError::UnknownKey { key } if key == "DB_USER"
+            ));
+        });
+
+        // Errors of the nested struct name its own field
+        temp_env::with_var("DATABASE", Some("DB_PORT=1234"), || {
+            let err = retrieve_error_of(Test::try_envoke().err().unwrap(), "host");
+            assert!(err.is_not_found());
+        });
+    }
+
+    #[test]
+    fn test_load_env_lazy_parse() {
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "PORT")]
+            port: u16,
+
+            #[fill(env = "WORKERS", lazy_parse)]
+            workers: envoke::Lazy<usize>,
+
+            #[fill(env = "TOKEN", lazy_parse, sensitive)]
+            token: Option<envoke::Lazy<u64>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("PORT", Some("8080")),
+                ("WORKERS", Some("many")),
+                ("TOKEN", Some("secret")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.port, 8080);
+                assert_eq!(test.workers.raw(), "many");
+
+                let err = test.workers.get().err().unwrap();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ParseError(envoke::ParseError::Failed { field, .. }) if field == "workers"
+                ));
+
+                let token = test.token.as_ref().unwrap();
+                let err = token.get().err().unwrap().to_string();
+                assert!(!err.contains("secret"));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("PORT", Some("8080")),
+                ("WORKERS", Some("4")),
+                ("TOKEN", None),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(*test.workers.get().unwrap(), 4);
+                assert!(test.token.is_none());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_nested_map() {
+        #[derive(Debug, PartialEq, Fill)]
+        struct Database {
+            #[fill(env = "HOST")]
+            host: String,
+
+            #[fill(env = "PORT", default = 5432)]
+            port: u16,
+        }
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(nested, map_prefix = "MAPTEST_DB_")]
+            databases: BTreeMap<String, Database>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("MAPTEST_DB_main_HOST", Some("primary")),
+                ("MAPTEST_DB_main_PORT", Some("6543")),
+                ("MAPTEST_DB_read_only_HOST", Some("replica")),
+                ("MAPTEST_DB_HOST", Some("ignored")),
+            ],
+            || {
+                let test = Test::envoke();
+                assert_eq!(
+                    test.databases,
+                    BTreeMap::from([
+                        (
+                            "main".to_string(),
+                            Database {
+                                host: "primary".to_string(),
+                                port: 6543
+                            }
+                        ),
+                        (
+                            "read_only".to_string(),
+                            Database {
+                                host: "replica".to_string(),
+                                port: 5432
+                            }
+                        ),
+                    ])
+                );
+            },
+        );
+
+        temp_env::with_var("MAPTEST_DB_main_PORT", Some("6543"), || {
+            let err = retrieve_error_of(Test::try_envoke().err().unwrap(), "host");
+            assert!(err.is_not_found());
+        });
+    }
+
+    #[test]
+    fn test_load_env_deprecated_hint() {
+        #[derive(Fill)]
+        struct Test {
+            #[fill(
+                env = "DATABASE_URL",
+                deprecated_env = "DB_URL",
+                deprecated = "set DATABASE_URL instead, DB_URL is removed in 1.0"
+            )]
+            url: String,
+        }
+
+        envoke::set_warning_handler(record_warning);
+        take_warnings();
+
+        temp_env::with_vars(
+            [("DATABASE_URL", None), ("DB_URL", Some("postgres://"))],
+            || {
+                let test = Test::envoke();
+                assert_eq!(test.url, "postgres://");
+
+                let warnings = take_warnings();
+                assert_eq!(
+                    warnings,
+                    vec![
+                        "environment variable `DB_URL` used by `url` is deprecated, set \
+                         DATABASE_URL instead, DB_URL is removed in 1.0"
+                    ]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_bytes() {
+        use bytes::Bytes;
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "PAYLOAD")]
+            payload: bytes::Bytes,
+
+            #[fill(env = "BANNER")]
+            banner: Option<bytes::Bytes>,
+        }
+
+        temp_env::with_vars([("PAYLOAD", Some("héllo")), ("BANNER", None)], || {
+            let test = Test::envoke();
+            assert_eq!(test.payload, Bytes::from("héllo"));
+            assert_eq!(test.banner, None);
+        });
+    }
+
+    #[test]
+    fn test_load_user_bytes_type() {
+        #[derive(Debug, PartialEq)]
+        struct Bytes(usize);
+
+        impl std::str::FromStr for Bytes {
+            type Err = std::num::ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Bytes(s.trim_end_matches("KiB").parse::<usize>()? * 1024))
+            }
+        }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "CACHE_SIZE")]
+            cache_size: Bytes,
+        }
+
+        temp_env::with_var("CACHE_SIZE", Some("4KiB"), || {
+            let test = Test::envoke();
+            assert_eq!(test.cache_size, Bytes(4096));
+        });
+    }
+
+    #[test]
+    fn test_diff() {
+        #[derive(Fill)]
+        #[fill(prefix = "DIFF_", rename_all = "UPPERCASE", diff)]
+        struct Database {
+            #[fill(env)]
+            url: String,
+
+            #[fill(env, sensitive)]
+            password: String,
+        }
+
+        #[derive(Fill)]
+        #[fill(prefix = "DIFF_", rename_all = "UPPERCASE", diff)]
+        struct Test {
+            #[fill(env)]
+            host: String,
+
+            #[fill(env)]
+            ports: Vec<u16>,
+
+            #[fill(env)]
+            timeout: Option<u64>,
+
+            #[fill(nested)]
+            database: Database,
+
+            #[fill(ignore)]
+            _cache: Option<Vec<String>>,
+        }
+
+        let load = |host, ports, timeout, url, password| {
+            temp_env::with_vars(
+                [
+                    ("DIFF_HOST", Some(host)),
+                    ("DIFF_PORTS", Some(ports)),
+                    ("DIFF_TIMEOUT", timeout),
+                    ("DIFF_URL", Some(url)),
+                    ("DIFF_PASSWORD", Some(password)),
+                ],
+                Test::envoke,
+            )
+        };
+
+        let old = load("localhost", "80,443", None, "postgres://a", "hunter2");
+        let new = load(
+            "localhost",
+            "80,8080",
+            Some("30"),
+            "postgres://b",
+            "hunter3",
+        );
+
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                envoke::FieldDiff {
+                    field: "ports".to_string(),
+                    old: Some("80,443".to_string()),
+                    new: Some("80,8080".to_string()),
+                },
+                envoke::FieldDiff {
+                    field: "timeout".to_string(),
+                    old: None,
+                    new: Some("30".to_string()),
+                },
+                envoke::FieldDiff {
+                    field: "database.url".to_string(),
+                    old: Some("postgres://a".to_string()),
+                    new: Some("postgres://b".to_string()),
+                },
+                envoke::FieldDiff {
+                    field: "database.password".to_string(),
+                    old: Some("***".to_string()),
+                    new: Some("***".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_env_net_addr() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "NET_")]
+        struct Test {
+            #[fill(env)]
+            ip: IpAddr,
+
+            #[fill(env)]
+            ipv4: Option<Ipv4Addr>,
+
+            #[fill(env)]
+            socket: SocketAddr,
+
+            #[fill(env)]
+            allowed_ips: Vec<IpAddr>,
+
+            #[fill(env, delimiter = ";")]
+            listeners: HashSet<SocketAddr>,
+
+            #[fill(env)]
+            upstreams: Option<Vec<std::net::Ipv4Addr>>,
+        }
+
+        let vars = |ips: &'static str, ipv4: Option<&'static str>| {
+            [
+                ("NET_IP", Some("::1")),
+                ("NET_IPV4", ipv4),
+                ("NET_SOCKET", Some("127.0.0.1:8080")),
+                ("NET_ALLOWED_IPS", Some(ips)),
+                ("NET_LISTENERS", Some("0.0.0.0:80;[::]:443")),
+                ("NET_UPSTREAMS", None),
+            ]
+        };
+
+        temp_env::with_vars(vars("10.0.0.1, 10.0.0.2", Some("10.0.0.3")), || {
+            let test = Test::try_envoke().unwrap();
+            assert_eq!(test.ip, IpAddr::from(Ipv6Addr::LOCALHOST));
+            assert_eq!(test.ipv4, Some(Ipv4Addr::new(10, 0, 0, 3)));
+            assert_eq!(test.socket, SocketAddr::from(([127, 0, 0, 1], 8080)));
+            assert_eq!(
+                test.allowed_ips,
+                vec![IpAddr::from([10, 0, 0, 1]), IpAddr::from([10, 0, 0, 2])]
+            );
+            assert_eq!(
+                test.listeners,
+                HashSet::from([
+                    SocketAddr::from(([0, 0, 0, 0], 80)),
+                    SocketAddr::from((Ipv6Addr::UNSPECIFIED, 443)),
+                ])
+            );
+            assert_eq!(test.upstreams, None);
+        });
+
+        temp_env::with_vars(vars("10.0.0.1,bad,10.0.0.2", None), || {
+            let err = Test::try_envoke().unwrap_err().to_string();
+            assert!(err.contains("element at index 1 is invalid"), "{err}");
+            assert!(
+                err.contains("`bad` is not a valid network address"),
+                "{err}"
+            );
+        });
+
+        temp_env::with_vars(vars("10.0.0.1", Some("10.0.0.256")), || {
+            let err = Test::try_envoke().unwrap_err().to_string();
+            assert!(
+                err.contains("`10.0.0.256` is not a valid network address"),
+                "{err}"
+            );
+        });
+
+        temp_env::with_vars(vars("10.0.0.1,,10.0.0.2", None), || {
+            let err = Test::try_envoke().unwrap_err();
+            assert!(err.to_string().contains("element at index 1"), "{err}");
+        });
+    }
+
+    #[test]
+    fn test_load_env_into() {
+        #[derive(Debug, PartialEq)]
+        struct Port(u16);
+
+        impl From<u16> for Port {
+            fn from(port: u16) -> Self {
+                Self(port)
+            }
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Endpoint {
+            port: Port,
+        }
+
+        // Composes `u16 -> Port -> Endpoint` in a single `into` call
+        impl<T: Into<Port>> From<T> for Endpoint {
+            fn from(port: T) -> Self {
+                Self { port: port.into() }
+            }
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "INTO_")]
+        struct Test {
+            #[fill(env, arg_type = u16, into)]
+            port: Port,
+
+            #[fill(env, arg_type = u16, into)]
+            endpoint: Endpoint,
+
+            #[fill(env, arg_type = Option<u16>, into)]
+            admin: Option<Endpoint>,
+
+            #[fill(env, arg_type = u16, into, default = Port(8080))]
+            metrics: Port,
+        }
+
+        temp_env::with_vars(
+            [
+                ("INTO_PORT", Some("80")),
+                ("INTO_ENDPOINT", Some("443")),
+                ("INTO_ADMIN", None),
+                ("INTO_METRICS", None),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.port, Port(80));
+                assert_eq!(test.endpoint, Endpoint { port: Port(443) });
+                assert_eq!(test.admin, None);
+                assert_eq!(test.metrics, Port(8080));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("INTO_PORT", Some("80")),
+                ("INTO_ENDPOINT", Some("443")),
+                ("INTO_ADMIN", Some("9000")),
+                ("INTO_METRICS", Some("9100")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.admin, Some(Endpoint { port: Port(9000) }));
+                assert_eq!(test.metrics, Port(9100));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("INTO_PORT", Some("not a port")),
+                ("INTO_ENDPOINT", Some("443")),
+            ],
+            || {
+                assert!(Test::try_envoke().is_err());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_default_lazy() {
+        fn unreachable_default<T>() -> T {
+            panic!("default evaluated although the variable is set")
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "LAZY_DEFAULT_")]
+        struct Test {
+            #[fill(env, default = unreachable_default())]
+            port: u16,
+
+            #[fill(env, default = unreachable_default())]
+            timeout: Option<u64>,
+
+            #[fill(env, default = unreachable_default())]
+            labels: HashMap<String, String>,
+
+            #[fill(env, default = unreachable_default())]
+            hosts: Vec<String>,
+
+            #[fill(env, default = unreachable_default())]
+            tags: Option<HashSet<String>>,
+
+            #[fill(env, default = unreachable_default())]
+            limits: Option<BTreeMap<String, u32>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("LAZY_DEFAULT_PORT", Some("80")),
+                ("LAZY_DEFAULT_TIMEOUT", Some("30")),
+                ("LAZY_DEFAULT_LABELS", Some("a=1")),
+                ("LAZY_DEFAULT_HOSTS", Some("a,b")),
+                ("LAZY_DEFAULT_TAGS", Some("x")),
+                ("LAZY_DEFAULT_LIMITS", Some("cpu=2")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.port, 80);
+                assert_eq!(test.timeout, Some(30));
+                assert_eq!(test.hosts, vec!["a", "b"]);
+                assert_eq!(test.limits, Some(BTreeMap::from([("cpu".to_string(), 2)])));
+            },
+        );
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "LAZY_DEFAULT_")]
+        struct Unset {
+            #[fill(env, default = 60)]
+            timeout: Option<u64>,
+
+            #[fill(env, default = HashSet::from(["x".to_string()]))]
+            tags: Option<HashSet<String>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("LAZY_DEFAULT_TIMEOUT", None::<&str>),
+                ("LAZY_DEFAULT_TAGS", None),
+            ],
+            || {
+                let test = Unset::try_envoke().unwrap();
+                assert_eq!(test.timeout, Some(60));
+                assert_eq!(test.tags, Some(HashSet::from(["x".to_string()])));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_rename_keys() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "KEYS_")]
+        struct Test {
+            #[fill(env, rename_keys = "kebab-case")]
+            headers: HashMap<String, String>,
+
+            #[fill(env, rename_keys = "SCREAMING_SNAKE_CASE")]
+            limits: Option<BTreeMap<String, u32>>,
+
+            #[fill(env, rename_keys = "lowercase")]
+            regions: BTreeMap<String, String>,
+
+            #[fill(env, rename_keys = "camelCase")]
+            missing: Option<HashMap<String, String>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("KEYS_HEADERS", Some("Content_Type=json,XRequestId=abc")),
+                ("KEYS_LIMITS", Some("maxConnections=10,idle-timeout=30")),
+                ("KEYS_REGIONS", Some("EU=eu-west-1,US=us-east-1")),
+                ("KEYS_MISSING", None),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(
+                    test.headers,
+                    HashMap::from([
+                        ("content-type".to_string(), "json".to_string()),
+                        ("x-request-id".to_string(), "abc".to_string()),
+                    ])
+                );
+                assert_eq!(
+                    test.limits,
+                    Some(BTreeMap::from([
+                        ("IDLE_TIMEOUT".to_string(), 30),
+                        ("MAX_CONNECTIONS".to_string(), 10),
+                    ]))
+                );
+                assert_eq!(
+                    test.regions,
+                    BTreeMap::from([
+                        ("eu".to_string(), "eu-west-1".to_string()),
+                        ("us".to_string(), "us-east-1".to_string()),
+                    ])
+                );
+                assert_eq!(test.missing, None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_priority_last() {
+        #[derive(Debug, Fill)]
+        #[fill(env_priority = "last")]
+        struct Test {
+            #[fill(env = "PRIORITY_PORT", env = "PRIORITY_APP_PORT")]
+            port: u16,
+
+            #[fill(env = "PRIORITY_HOSTS", env = "PRIORITY_APP_HOSTS")]
+            hosts: Option<Vec<String>>,
+
+            #[fill(env = "PRIORITY_WORKERS", deprecated_env = "PRIORITY_THREADS")]
+            workers: u8,
+        }
+
+        temp_env::with_vars(
+            [
+                ("PRIORITY_PORT", Some("80")),
+                ("PRIORITY_APP_PORT", Some("8080")),
+                ("PRIORITY_HOSTS", Some("a,b")),
+                ("PRIORITY_APP_HOSTS", None),
+                ("PRIORITY_WORKERS", Some("4")),
+                ("PRIORITY_THREADS", Some("8")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.port, 8080);
+                assert_eq!(test.hosts, Some(vec!["a".to_string(), "b".to_string()]));
+                assert_eq!(test.workers, 4);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_enum_parse_fn() {
+        fn normalize(value: String) -> String {
+            value.trim().to_uppercase()
+        }
+
+        fn legacy_code(value: String) -> Result<String, String> {
+            match value.as_str() {
+                "1" => Ok("DEVELOPMENT".to_string()),
+                "2" => Ok("PRODUCTION".to_string()),
+                _ => Err(format!("unknown legacy code `{value}`")),
+            }
+        }
+
+        #[derive(Debug, PartialEq, Fill)]
+        #[fill(rename_all = "UPPERCASE", env = "NORMALIZED_MODE", parse_fn = normalize)]
+        enum Mode {
+            Development,
+            Production,
+        }
+
+        #[derive(Debug, PartialEq, Fill)]
+        #[fill(
+            rename_all = "UPPERCASE",
+            env = "LEGACY_CODE",
+            try_parse_fn = legacy_code
+        )]
+        enum Legacy {
+            Development,
+            #[fill(default)]
+            Production,
+        }
+
+        temp_env::with_vars(
+            [
+                ("NORMALIZED_MODE", Some(" production ")),
+                ("LEGACY_CODE", Some("1")),
+            ],
+            || {
+                assert_eq!(Mode::try_envoke().unwrap(), Mode::Production);
+                assert_eq!(Legacy::try_envoke().unwrap(), Legacy::Development);
+            },
+        );
+
+        temp_env::with_var("LEGACY_CODE", Some("9"), || {
+            let err = Legacy::try_envoke().unwrap_err();
+            assert!(err.to_string().contains("unknown legacy code `9`"), "{err}");
+        });
+
+        temp_env::with_var("LEGACY_CODE", None::<&str>, || {
+            assert_eq!(Legacy::try_envoke().unwrap(), Legacy::Production);
+        });
+    }
+
+    #[test]
+    fn test_load_nested_map_global() {
+        #[derive(Debug, PartialEq, Fill)]
+        struct Database {
+            #[fill(env = "HOST")]
+            host: String,
+
+            #[fill(env = "GLOBALTEST_REGION", global)]
+            region: String,
+
+            #[fill(env = "GLOBALTEST_ZONE", global)]
+            zone: Option<String>,
+        }
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(nested, map_prefix = "GLOBALTEST_DB_")]
+            databases: BTreeMap<String, Database>,
+
+            #[fill(nested)]
+            fallback: Database,
+        }
+
+        temp_env::with_vars(
+            [
+                ("GLOBALTEST_DB_main_HOST", Some("primary")),
+                ("GLOBALTEST_DB_main_GLOBALTEST_REGION", Some("scoped")),
+                ("GLOBALTEST_REGION", Some("eu-west-1")),
+                ("GLOBALTEST_ZONE", None),
+                ("HOST", Some("localhost")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(
+                    test.databases,
+                    BTreeMap::from([(
+                        "main".to_string(),
+                        Database {
+                            host: "primary".to_string(),
+                            region: "eu-west-1".to_string(),
+                            zone: None,
+                        }
+                    )])
+                );
+
+                // Outside a prefix scope the field is loaded as usual
+                assert_eq!(test.fallback.region, "eu-west-1");
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_alias() {
+        #[derive(Debug, Fill)]
+        #[fill(env_line)]
+        struct Test {
+            #[fill(env = "ALIAS_DATABASE_URL", alias = "ALIAS_DB_URL")]
+            url: String,
+
+            #[fill(alias = "ALIAS_WORKERS", default = 1)]
+            alias_threads: u8,
+        }
+
+        temp_env::with_vars(
+            [
+                ("ALIAS_DATABASE_URL", None),
+                ("ALIAS_DB_URL", Some("postgres://db")),
+                ("alias_threads", None),
+                ("ALIAS_WORKERS", Some("4")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.url, "postgres://db");
+                assert_eq!(test.alias_threads, 4);
+                assert_eq!(
+                    test.env_line("url").as_deref(),
+                    Some("ALIAS_DATABASE_URL=postgres://db")
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ALIAS_DATABASE_URL", Some("postgres://primary")),
+                ("ALIAS_DB_URL", Some("postgres://db")),
+                ("alias_threads", None),
+                ("ALIAS_WORKERS", None),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.url, "postgres://primary");
+                assert_eq!(test.alias_threads, 1);
+            },
+        );
+
+        assert_eq!(
+            Test::env_keys(),
+            vec![
+                "ALIAS_DATABASE_URL",
+                "ALIAS_DB_URL",
+                "alias_threads",
+                "ALIAS_WORKERS"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_global() {
+        #[derive(Debug, Fill)]
+        #[fill(global)]
+        struct Config {
+            #[fill(env = "GLOBAL_CONFIG_PORT")]
+            port: u16,
+        }
+
+        temp_env::with_var("GLOBAL_CONFIG_PORT", Some("8080"), || {
+            assert_eq!(Config::global().port, 8080);
+        });
+
+        // Loaded once, later changes to the environment are not picked up
+        temp_env::with_var("GLOBAL_CONFIG_PORT", Some("9090"), || {
+            assert_eq!(Config::global().port, 8080);
+            assert!(std::ptr::eq(Config::global(), Config::global()));
+        });
+    }
+
+    #[test]
+    fn test_load_env_split_into() {
+        fn parse_hostport(value: String) -> (String, u16) {
+            let (host, port) = value.split_once(':').unwrap_or((&value, "80"));
+            (host.to_string(), port.parse().unwrap_or(80))
+        }
+
+        fn try_parse_range(value: String) -> Result<(u16, u16), String> {
+            let (min, max) = value
+                .split_once('-')
+                .ok_or_else(|| format!("`{value}` is not a range"))?;
+            Ok((
+                min.parse().map_err(|_| "invalid minimum")?,
+                max.parse().map_err(|_| "invalid maximum")?,
+            ))
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(env_line)]
+        struct Test {
+            #[fill(
+                env = "SPLIT_HOSTPORT",
+                split_into(host, port),
+                parse_fn = parse_hostport,
+                arg_type = String
+            )]
+            host: String,
+
+            port: u16,
+
+            #[fill(env = "SPLIT_NAME")]
+            name: String,
+
+            min_port: u16,
+
+            #[fill(
+                env = "SPLIT_RANGE",
+                split_into(min_port, max_port),
+                try_parse_fn = try_parse_range,
+                arg_type = String
+            )]
+            max_port: u16,
+        }
+
+        temp_env::with_vars(
+            [
+                ("SPLIT_HOSTPORT", Some("localhost:8080")),
+                ("SPLIT_NAME", Some("api")),
+                ("SPLIT_RANGE", Some("1000-2000")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.host, "localhost");
+                assert_eq!(test.port, 8080);
+                assert_eq!(test.name, "api");
+                assert_eq!((test.min_port, test.max_port), (1000, 2000));
+                assert_eq!(test.env_line("host"), None);
+                assert_eq!(test.env_line("port"), None);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("SPLIT_HOSTPORT", Some("localhost")),
+                ("SPLIT_NAME", Some("api")),
+                ("SPLIT_RANGE", Some("1000")),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                assert!(err.to_string().contains("`1000` is not a range"), "{err}");
+            },
+        );
+
+        assert_eq!(
+            Test::env_keys(),
+            vec!["SPLIT_HOSTPORT", "SPLIT_NAME", "SPLIT_RANGE"]
+        );
+    }
+
+    #[test]
+    fn test_load_env_nested_generics() {
+        fn parse_opts(value: Vec<String>) -> Vec<Option<u16>> {
+            value.iter().map(|v| v.parse().ok()).collect()
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "GENERICS_")]
+        struct Test {
+            #[fill(env)]
+            port: std::option::Option<u16>,
+
+            #[fill(env)]
+            hosts: Option<Vec<String>>,
+
+            #[fill(env)]
+            weights: Option<HashMap<String, u16>>,
+
+            #[fill(env, arg_type = Vec<String>, parse_fn = parse_opts)]
+            opts: Vec<Option<u16>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("GENERICS_PORT", Some("8080")),
+                ("GENERICS_HOSTS", Some("a,b")),
+                ("GENERICS_WEIGHTS", Some("a=1")),
+                ("GENERICS_OPTS", Some("1,x,3")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.port, Some(8080));
+                assert_eq!(test.hosts, Some(vec!["a".to_string(), "b".to_string()]));
+                assert_eq!(test.weights, Some(HashMap::from([("a".to_string(), 1)])));
+                assert_eq!(test.opts, vec![Some(1), None, Some(3)]);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("GENERICS_PORT", None::<&str>),
+                ("GENERICS_HOSTS", None),
+                ("GENERICS_WEIGHTS", None),
+                ("GENERICS_OPTS", Some("1")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.port, None);
+                assert_eq!(test.hosts, None);
+                assert_eq!(test.weights, None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_one_of() {
+        #[derive(Debug, PartialEq, strum::EnumString)]
+        #[strum(serialize_all = "lowercase")]
+        enum Mode {
+            Dev,
+            Prod,
+            Test,
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "ONE_OF_")]
+        struct Test {
+            #[fill(env, one_of = ["dev", "staging", "prod"])]
+            stage: String,
+
+            #[fill(env, one_of = [80, 443])]
+            port: u16,
+
+            #[fill(env, one_of = [Mode::Dev, Mode::Prod])]
+            mode: Option<Mode>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("ONE_OF_STAGE", Some("staging")),
+                ("ONE_OF_PORT", Some("443")),
+                ("ONE_OF_MODE", None::<&str>),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.stage, "staging");
+                assert_eq!(test.port, 443);
+                assert_eq!(test.mode, None);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ONE_OF_STAGE", Some("qa")),
+                ("ONE_OF_PORT", Some("443")),
+                ("ONE_OF_MODE", Some("dev")),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ValidationError(envoke::ValidationError::NotOneOf { .. })
+                ));
+                assert_eq!(
+                    err.to_string(),
+                    "Validation error occurred: `stage` must be one of `dev`, `staging`, `prod`"
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ONE_OF_STAGE", Some("dev")),
+                ("ONE_OF_PORT", Some("8080")),
+                ("ONE_OF_MODE", Some("test")),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                assert!(
+                    err.to_string()
+                        .contains("`port` must be one of `80`, `443`"),
+                    "{err}"
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ONE_OF_STAGE", Some("dev")),
+                ("ONE_OF_PORT", Some("80")),
+                ("ONE_OF_MODE", Some("test")),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                assert!(
+                    err.to_string()
+                        .contains("`mode` must be one of `Mode::Dev`, `Mode::Prod`"),
+                    "{err}"
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_file() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "FILE_")]
+        struct Test {
+            #[fill(env, file = "/tmp/envoke_test_file_password", sensitive)]
+            password: String,
+
+            #[fill(env, file = "/tmp/envoke_test_file_port", default = 5432)]
+            port: u16,
+
+            #[fill(env, file = "/tmp/envoke_test_file_user")]
+            user: Option<String>,
+
+            #[fill(env, file = "/tmp/envoke_test_file_pin", sensitive)]
+            pin: Option<u32>,
+        }
+
+        let _ = std::fs::remove_file("/tmp/envoke_test_file_port");
+        let _ = std::fs::remove_file("/tmp/envoke_test_file_user");
+        let _ = std::fs::remove_file("/tmp/envoke_test_file_pin");
+        std::fs::write("/tmp/envoke_test_file_password", "hunter2\n").unwrap();
+
+        // The file is ignored if the variable is set
+        temp_env::with_vars(
+            [
+                ("FILE_PASSWORD", Some("secret")),
+                ("FILE_PORT", Some("6543")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.password, "secret");
+                assert_eq!(test.port, 6543);
+                assert_eq!(test.user, None);
+            },
+        );
+
+        // The file is read if the variable is not set
+        temp_env::with_vars(
+            [
+                ("FILE_PASSWORD", None::<&str>),
+                ("FILE_PORT", None),
+                ("FILE_USER", None),
+            ],
+            || {
+                std::fs::write("/tmp/envoke_test_file_user", "admin").unwrap();
+
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.password, "hunter2");
+                assert_eq!(test.port, 5432);
+                assert_eq!(test.user, Some("admin".to_string()));
+            },
+        );
+
+        // A value read from a file is redacted like any other
+        temp_env::with_vars([("FILE_PIN", None::<&str>)], || {
+            std::fs::write("/tmp/envoke_test_file_pin", "12ab").unwrap();
+
+            let err = Test::try_envoke().unwrap_err();
+            assert!(err.to_string().contains("[redacted]"), "{err}");
+            assert!(!err.to_string().contains("12ab"), "{err}");
+
+            std::fs::remove_file("/tmp/envoke_test_file_pin").unwrap();
+        });
+
+        // Without the variable or the file the field is missing
+        temp_env::with_vars([("FILE_PASSWORD", None::<&str>)], || {
+            std::fs::remove_file("/tmp/envoke_test_file_password").unwrap();
+
+            let err = retrieve_error_of(Test::try_envoke().unwrap_err(), "password");
+            assert!(err.is_not_found());
+        });
+
+        let _ = std::fs::remove_file("/tmp/envoke_test_file_user");
+    }
+
+    #[test]
+    fn test_enum_env_line_canonical_name() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "SCREAMING_SNAKE_CASE", env_line)]
+        struct Remote {
+            #[fill(env = "CANONICAL_REMOTE_URL")]
+            url: String,
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(env = "CANONICAL_MODE", env_line)]
+        enum Mode {
+            #[fill(rename = "development", alias = "dev", alias = "local")]
+            Development,
+            #[fill(rename = "prod", alias = "live")]
+            Production,
+            #[fill(rename = "remote", alias = "cloud")]
+            Remote(Remote),
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(env_line)]
+        struct Config {
+            #[fill(nested)]
+            mode: Mode,
+        }
+
+        temp_env::with_vars(
+            [
+                ("CANONICAL_MODE", Some("local")),
+                ("CANONICAL_REMOTE_URL", None),
+            ],
+            || {
+                let config = Config::try_envoke().unwrap();
+                assert_eq!(config.mode.env_value(), "development");
+                assert_eq!(
+                    config.env_line("CANONICAL_MODE").as_deref(),
+                    Some("CANONICAL_MODE=development")
+                );
+                assert_eq!(config.env_line("url"), None);
+            },
+        );
+
+        temp_env::with_vars([("CANONICAL_MODE", Some("live"))], || {
+            let mode = Mode::try_envoke().unwrap();
+            assert_eq!(mode.env_value(), "prod");
+            assert_eq!(
+                mode.env_line("CANONICAL_MODE").as_deref(),
+                Some("CANONICAL_MODE=prod")
+            );
+        });
+
+        temp_env::with_vars(
+            [
+                ("CANONICAL_MODE", Some("cloud")),
+                ("CANONICAL_REMOTE_URL", Some("https://example.com")),
+            ],
+            || {
+                let config = Config::try_envoke().unwrap();
+                assert_eq!(config.mode.env_value(), "remote");
+                assert_eq!(
+                    config.env_line("CANONICAL_MODE").as_deref(),
+                    Some("CANONICAL_MODE=remote")
+                );
+                assert_eq!(
+                    config.env_line("url").as_deref(),
+                    Some("CANONICAL_REMOTE_URL=https://example.com")
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_with_overrides() {
+        use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+        #[derive(Debug, Fill)]
+        #[fill(
+            rename_all = "UPPERCASE",
+            prefix = "OVERRIDES_",
+            dotenv = "dotenv/overrides.env"
+        )]
+        struct Test {
+            #[fill(env)]
+            host: String,
+
+            #[fill(env)]
+            port: u16,
+
+            #[fill(env)]
+            name: String,
+        }
+
+        temp_env::with_vars(
+            [
+                ("OVERRIDES_HOST", Some("env.local")),
+                ("OVERRIDES_PORT", Some("8000")),
+                ("OVERRIDES_NAME", None),
+            ],
+            || {
+                // Overrides > process environment > dotenv file
+                let test = Test::try_envoke_with_overrides(&["--OVERRIDES_PORT=7000"]).unwrap();
+                assert_eq!(test.host, "env.local");
+                assert_eq!(test.port, 7000);
+                assert_eq!(test.name, "dotenv");
+
+                let args = vec!["OVERRIDES_NAME=cli".to_string()];
+                let test = Test::try_envoke_with_overrides(&args).unwrap();
+                assert_eq!(test.port, 8000);
+                assert_eq!(test.name, "cli");
+
+                // The process environment is left untouched
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.port, 8000);
+                assert_eq!(test.name, "dotenv");
+
+                let err = Test::try_envoke_with_overrides(&["--verbose"]).unwrap_err();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ParseError(envoke::ParseError::InvalidOverride { .. })
+                ));
+                assert!(Test::try_envoke_with_overrides(&["=7000"]).is_err());
+            },
+        );
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "OVERRIDES_")]
+        struct Unicode {
+            #[fill(env)]
+            label: Option<String>,
+
+            #[fill(env)]
+            retries: u8,
+        }
+
+        // Invalid Unicode in the process environment is reported as it would
+        // be without overrides instead of loading the field as unset
+        temp_env::with_var(
+            "OVERRIDES_LABEL",
+            Some(OsString::from_vec(vec![0xff, 0xfe])),
+            || {
+                let err = Unicode::try_envoke_with_overrides(&["OVERRIDES_RETRIES=3"]).unwrap_err();
+                assert!(matches!(
+                    retrieve_error_of(err, "label"),
+                    envoke::RetrieveError::InvalidUnicode { ref key } if key == "OVERRIDES_LABEL"
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_map_misplaced_equalsign() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "EQUALSIGN_")]
+        struct Test {
+            #[fill(env)]
+            labels: HashMap<String, String>,
+        }
+
+        let cases = [
+            ("=a", "MissingKey"),
+            ("a=", "MissingValue"),
+            ("a", "MissingValue"),
+            ("=", "UnexpectedEqualsign"),
+            ("==", "UnexpectedEqualsign"),
+            ("a=b, = ", "UnexpectedEqualsign"),
+        ];
+
+        for (value, expected) in cases {
+            temp_env::with_var("EQUALSIGN_LABELS", Some(value), || {
+                let err = parse_error_of(Test::try_envoke().unwrap_err(), "labels");
+                assert_eq!(format!("{err:?}"), expected, "{value}");
+            });
+        }
+    }
+
+    #[test]
+    fn test_load_env_wrapped_elements() {
+        use std::num::{Saturating, Wrapping};
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "WRAPPED_")]
+        struct Test {
+            #[fill(env)]
+            counters: Vec<Saturating<u32>>,
+
+            #[fill(env)]
+            limits: Option<HashMap<String, Wrapping<u8>>>,
+
+            #[fill(env)]
+            total: std::num::Saturating<u16>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("WRAPPED_COUNTERS", Some("1,4294967295")),
+                ("WRAPPED_LIMITS", Some("a=255")),
+                ("WRAPPED_TOTAL", Some("65535")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.counters, vec![Saturating(1), Saturating(u32::MAX)]);
+                assert_eq!(test.counters[1] + Saturating(1), Saturating(u32::MAX));
+
+                let limits = test.limits.unwrap();
+                assert_eq!(limits["a"] + Wrapping(1), Wrapping(0));
+                assert_eq!(test.total + Saturating(1), Saturating(u16::MAX));
+            },
+        );
+
+        // Values are still parsed as the wrapped type, so they must fit in it
+        temp_env::with_vars(
+            [
+                ("WRAPPED_COUNTERS", Some("1,4294967296")),
+                ("WRAPPED_LIMITS", None),
+                ("WRAPPED_TOTAL", Some("1")),
+            ],
+            || {
+                let err = parse_error_of(Test::try_envoke().unwrap_err(), "counters");
+                assert!(matches!(
+                    err,
+                    envoke::ParseError::InvalidElement { index: 1, .. }
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_deserialize_with() {
+        use serde::{Deserialize, Deserializer};
+
+        fn comma_or_space<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {
+            let value = String::deserialize(d)?;
+            Ok(value
+                .split([',', ' '])
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect())
+        }
+
+        fn percent<'de, D: Deserializer<'de>>(d: D) -> Result<u8, D::Error> {
+            let value = String::deserialize(d)?;
+            let value = value.trim_end_matches('%');
+            value.parse().map_err(serde::de::Error::custom)
+        }
+
+        // The same functions are used when deserializing from a file
+        #[derive(Deserialize)]
+        struct FileConfig {
+            #[serde(deserialize_with = "comma_or_space")]
+            hosts: Vec<String>,
+        }
+
+        let file: FileConfig = serde_json::from_str(r#"{ "hosts": "a b,c" }"#).unwrap();
+        assert_eq!(file.hosts, vec!["a", "b", "c"]);
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "DESERIALIZE_")]
+        struct Test {
+            #[fill(env, deserialize_with = comma_or_space)]
+            hosts: Vec<String>,
+
+            #[fill(env, deserialize_with = percent)]
+            ratio: Option<u8>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("DESERIALIZE_HOSTS", Some("a b,c")),
+                ("DESERIALIZE_RATIO", Some("75%")),
+            ],
+            || {
+                let test = Test::try_envoke().unwrap();
+                assert_eq!(test.hosts, vec!["a", "b", "c"]);
+                assert_eq!(test.ratio, Some(75));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("DESERIALIZE_HOSTS", Some("a")),
+                ("DESERIALIZE_RATIO", Some("many%")),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                assert!(
+                    err.to_string().contains("parsing failed for `ratio`"),
+                    "{err}"
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_rename_all_additional_cases() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "Train-Case", prefix = "train_")]
+        struct Train {
+            #[fill(env)]
+            api_port: u16,
+
+            #[fill(env, rename_keys = "dot.case")]
+            labels: HashMap<String, String>,
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "flatcase", prefix = "flat_")]
+        struct Flat {
+            #[fill(env)]
+            api_port: u16,
+        }
+
+        temp_env::with_vars(
+            [
+                ("Train-Api-Port", Some("8080")),
+                ("Train-Labels", Some("someLabel=a,other_label=b")),
+                ("flatapiport", Some("9090")),
+            ],
+            || {
+                let train = Train::try_envoke().unwrap();
+                assert_eq!(train.api_port, 8080);
+                assert_eq!(
+                    train.labels,
+                    HashMap::from([
+                        ("some.label".to_string(), "a".to_string()),
+                        ("other.label".to_string(), "b".to_string()),
+                    ])
+                );
+
+                assert_eq!(Flat::try_envoke().unwrap().api_port, 9090);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_optional_default_none() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(default)]
+            bare: Option<u16>,
+
+            #[fill(default = None)]
+            explicit: Option<u16>,
+
+            #[fill(default = 8080)]
+            port: Option<u16>,
+
+            no_attribute: Option<u16>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("BARE", Some("1")),
+                ("EXPLICIT", Some("2")),
+                ("PORT", Some("3")),
+                ("NO_ATTRIBUTE", Some("4")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.bare, None);
+                assert_eq!(test.explicit, None);
+                assert_eq!(test.port, Some(8080));
+                assert_eq!(test.no_attribute, Some(4));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_dispatch() {
+        trait Storage {
+            fn describe(&self) -> String;
+        }
+
+        #[derive(Debug, Fill)]
+        struct Disk {
+            #[fill(env = "DISPATCH_DISK_PATH")]
+            path: String,
+        }
+
+        impl Storage for Disk {
+            fn describe(&self) -> String {
+                format!("disk at {}", self.path)
+            }
+        }
+
+        #[derive(Debug, Fill)]
+        struct Memory {
+            #[fill(env = "DISPATCH_MEMORY_SIZE", default = 64)]
+            size: u32,
+        }
+
+        impl Storage for Memory {
+            fn describe(&self) -> String {
+                format!("memory of {}", self.size)
+            }
+        }
+
+        #[derive(Fill)]
+        struct Test {
+            #[fill(env = "DISPATCH_STORAGE", dispatch(disk = Disk, memory = Memory))]
+            storage: Box<dyn Storage>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("DISPATCH_STORAGE", Some("disk")),
+                ("DISPATCH_DISK_PATH", Some("/var/data")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.storage.describe(), "disk at /var/data");
+            },
+        );
+
+        temp_env::with_vars([("DISPATCH_STORAGE", Some("memory"))], || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.storage.describe(), "memory of 64");
+        });
+
+        temp_env::with_vars([("DISPATCH_STORAGE", Some("tape"))], || {
+            let result = Test::try_envoke();
+            assert!(matches!(
+                result,
+                Err(envoke::Error::ValidationError(
+                    envoke::ValidationError::NotOneOf { .. }
+                ))
+            ));
+        });
+
+        let keys = Test::env_keys();
+        assert!(keys.contains(&"DISPATCH_STORAGE".to_string()));
+        assert!(keys.contains(&"DISPATCH_DISK_PATH".to_string()));
+        assert!(keys.contains(&"DISPATCH_MEMORY_SIZE".to_string()));
+    }
+
+    #[test]
+    fn test_load_env_nested_validate_fn() {
+        #[derive(Debug, Fill)]
+        struct Server {
+            #[fill(env = "NESTED_VALIDATE_HOST")]
+            host: String,
+
+            #[fill(env = "NESTED_VALIDATE_PORT")]
+            port: u16,
+        }
+
+        fn check_server(server: &Server) -> anyhow::Result<()> {
+            if server.host == "localhost" && server.port < 1024 {
+                anyhow::bail!("privileged port on localhost");
+            }
+
+            Ok(())
+        }
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(nested, validate_fn(after = check_server))]
+            server: Server,
+        }
+
+        temp_env::with_vars(
+            [
+                ("NESTED_VALIDATE_HOST", Some("localhost")),
+                ("NESTED_VALIDATE_PORT", Some("8080")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.server.port, 8080);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("NESTED_VALIDATE_HOST", Some("localhost")),
+                ("NESTED_VALIDATE_PORT", Some("80")),
+            ],
+            || {
+                let result = Test::try_envoke();
+                assert!(matches!(
+                    result,
+                    Err(envoke::Error::ValidationError(envoke::ValidationError::Failed { field, .. }))
+                        if field == "server"
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_multiple_of() {
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "MULTIPLE_OF_BUFFER", multiple_of = 4096)]
+            buffer: usize,
+
+            #[fill(env = "MULTIPLE_OF_ALIGN", multiple_of = 8)]
+            align: Option<i32>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("MULTIPLE_OF_BUFFER", Some("8192")),
+                ("MULTIPLE_OF_ALIGN", Some("-16")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.buffer, 8192);
+                assert_eq!(test.align, Some(-16));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("MULTIPLE_OF_BUFFER", Some("4096")),
+                ("MULTIPLE_OF_ALIGN", None),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.align, None);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("MULTIPLE_OF_BUFFER", Some("5000")),
+                ("MULTIPLE_OF_ALIGN", None),
+            ],
+            || {
+                let result = Test::try_envoke();
+                assert!(matches!(
+                    result,
+                    Err(envoke::Error::ValidationError(envoke::ValidationError::NotMultipleOf { field, step }))
+                        if field == "buffer" && step == "4096"
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_enumerate() {
+        #[derive(Debug, Fill)]
+        #[fill(env_line)]
+        struct Test {
+            #[fill(env = "ENUMERATE_SERVERS", enumerate)]
+            servers: HashMap<usize, String>,
+
+            #[fill(env = "ENUMERATE_WEIGHTS", enumerate, delimiter = ";")]
+            weights: Option<BTreeMap<usize, u8>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("ENUMERATE_SERVERS", Some("a,b,c")),
+                ("ENUMERATE_WEIGHTS", Some("10;20")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(
+                    test.servers,
+                    HashMap::from([
+                        (0, "a".to_string()),
+                        (1, "b".to_string()),
+                        (2, "c".to_string())
+                    ])
+                );
+                assert_eq!(test.weights, Some(BTreeMap::from([(0, 10), (1, 20)])));
+                assert_eq!(
+                    test.env_line("servers"),
+                    Some("ENUMERATE_SERVERS=a,b,c".to_string())
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ENUMERATE_SERVERS", Some("a")),
+                ("ENUMERATE_WEIGHTS", Some("10;x")),
+            ],
+            || {
+                let result = Test::try_envoke();
+                assert!(matches!(result, Err(envoke::Error::ParseError(_))));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_map_enum_keys() {
+        #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, strum::EnumString)]
+        #[strum(serialize_all = "kebab-case")]
+        enum Region {
+            UsEast,
+            EuWest,
+            ApSouth,
+        }
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "ENUM_KEYS_LIMITS")]
+            limits: HashMap<Region, u32>,
+
+            #[fill(env = "ENUM_KEYS_ORDERED")]
+            ordered: BTreeMap<Region, u32>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("ENUM_KEYS_LIMITS", Some("us-east=10,eu-west=20")),
+                ("ENUM_KEYS_ORDERED", Some("ap-south=3,us-east=1,eu-west=2")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(
+                    test.limits,
+                    HashMap::from([(Region::UsEast, 10), (Region::EuWest, 20)])
+                );
+
+                // Ordered by the enum's `Ord`, i.e., the declaration order
+                let keys: Vec<&Region> = test.ordered.keys().collect();
+                assert_eq!(keys, [&Region::UsEast, &Region::EuWest, &Region::ApSouth]);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ENUM_KEYS_LIMITS", Some("us-east=10,eu-nowhere=20")),
+                ("ENUM_KEYS_ORDERED", Some("us-east=1")),
+            ],
+            || {
+                let err = Test::try_envoke().expect_err("expected an unknown key error");
+                assert!(err
+                    .to_string()
+                    .contains("key `eu-nowhere` is not a valid `Region`"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_system_defaults() {
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "SYSTEM_DEFAULTS_WORKERS", default = cpus)]
+            workers: usize,
+
+            #[fill(env = "SYSTEM_DEFAULTS_THREADS", default = cpus_times(2))]
+            threads: u16,
+
+            #[fill(env = "SYSTEM_DEFAULTS_MAX", default = cpus)]
+            max: Option<u32>,
+        }
+
+        temp_env::with_vars_unset(
+            [
+                "SYSTEM_DEFAULTS_WORKERS",
+                "SYSTEM_DEFAULTS_THREADS",
+                "SYSTEM_DEFAULTS_MAX",
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert!(test.workers > 0);
+                assert_eq!(usize::from(test.threads), test.workers * 2);
+                assert_eq!(test.max, Some(test.workers as u32));
+            },
+        );
+
+        temp_env::with_var("SYSTEM_DEFAULTS_WORKERS", Some("3"), || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.workers, 3);
+        });
+    }
+
+    #[test]
+    fn test_load_dotenv_io_errors() {
+        #[derive(Debug, Fill)]
+        #[fill(dotenv = "dotenv/does_not_exist.env")]
+        struct Missing {
+            #[fill(env = "DOTENV_IO_VALUE", default)]
+            _value: String,
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(dotenv = "dotenv")]
+        struct Directory {
+            #[fill(env = "DOTENV_IO_VALUE", default)]
+            _value: String,
+        }
+
+        let path = "/tmp/envoke_test_dotenv_invalid_utf8.env";
+        std::fs::write(path, b"DOTENV_IO_VALUE=\xff\xfe\n").expect("failed to write test file");
+
+        #[derive(Debug, Fill)]
+        #[fill(dotenv = "/tmp/envoke_test_dotenv_invalid_utf8.env")]
+        struct InvalidUtf8 {
+            #[fill(env = "DOTENV_IO_VALUE", default)]
+            _value: String,
+        }
+
+        let result = Missing::try_envoke();
+        assert!(matches!(
+            result,
+            Err(envoke::Error::RetrieveError(envoke::RetrieveError::ReadFile { path, err }))
+                if path == "dotenv/does_not_exist.env" && err.kind() == std::io::ErrorKind::NotFound
+        ));
+
+        let result = Directory::try_envoke();
+        assert!(matches!(
+            result,
+            Err(envoke::Error::RetrieveError(
+                envoke::RetrieveError::ReadFile { .. }
+            ))
+        ));
+
+        let result = InvalidUtf8::try_envoke();
+        assert!(matches!(
+            result,
+            Err(envoke::Error::RetrieveError(envoke::RetrieveError::ReadFile { err, .. }))
+                if err.kind() == std::io::ErrorKind::InvalidData
+        ));
+
+        std::fs::remove_file(path).expect("failed to remove test file");
+    }
+
+    #[test]
+    fn test_load_env_hydrate() {
+        fn bootstrap() -> anyhow::Result<()> {
+            std::env::set_var("HYDRATE_TOKEN", "from-bootstrap");
+            Ok(())
+        }
+
+        fn failing_bootstrap() -> anyhow::Result<()> {
+            anyhow::bail!("remote source unavailable")
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(hydrate = bootstrap)]
+        struct Test {
+            #[fill(env = "HYDRATE_TOKEN")]
+            token: String,
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(hydrate = failing_bootstrap)]
+        struct Failing {
+            #[fill(env = "HYDRATE_TOKEN")]
+            _token: String,
+        }
+
+        temp_env::with_var("HYDRATE_TOKEN", Some("from-env"), || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.token, "from-env");
+        });
+
+        temp_env::with_var_unset("HYDRATE_TOKEN", || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.token, "from-bootstrap");
+        });
+
+        temp_env::with_var_unset("HYDRATE_TOKEN", || {
+            let result = Failing::try_envoke();
+            assert!(matches!(
+                result,
+                Err(envoke::Error::RetrieveError(envoke::RetrieveError::Hydrate { err }))
+                    if err.to_string() == "remote source unavailable"
+            ));
+        });
+    }
+
+    #[test]
+    fn test_load_env_default_fn() {
+        fn make_default(ctx: &envoke::DefaultCtx) -> String {
+            format!("{} from {}", ctx.field, ctx.envs.join("|"))
+        }
+
+        fn port_for(ctx: &envoke::DefaultCtx) -> u16 {
+            match ctx.field {
+                "admin_port" => 9000,
+                _ => 8000,
+            }
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "APP_")]
+        struct Test {
+            #[fill(env = "NAME", env = "ALT_NAME", default_fn = make_default)]
+            name: String,
+
+            #[fill(default_fn = port_for)]
+            admin_port: u16,
+
+            #[fill(env = "DEFAULT_FN_PORT", default_fn = port_for)]
+            port: Option<u16>,
+        }
+
+        temp_env::with_vars_unset(["APP_NAME", "APP_ALT_NAME", "APP_DEFAULT_FN_PORT"], || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.name, "name from APP_NAME|APP_ALT_NAME");
+            assert_eq!(test.admin_port, 9000);
+            assert_eq!(test.port, Some(8000));
+        });
+
+        temp_env::with_var("APP_NAME", Some("envoke"), || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.name, "envoke");
+        });
+    }
+
+    #[test]
+    fn test_enum_case_insensitive() {
+        #[derive(Debug, PartialEq, Fill)]
+        #[fill(
+            env = "CASE_INSENSITIVE_MODE",
+            rename_all = "SCREAMING_SNAKE_CASE",
+            case_insensitive
+        )]
+        enum Mode {
+            Production,
+            LocalDev,
+        }
+
+        #[derive(Debug, PartialEq, Fill)]
+        #[fill(env = "CASE_INSENSITIVE_MODE", rename_all = "SCREAMING_SNAKE_CASE")]
+        enum StrictMode {
+            Production,
+        }
+
+        for value in ["PRODUCTION", "production", "Production"] {
+            temp_env::with_var("CASE_INSENSITIVE_MODE", Some(value), || {
+                assert_eq!(Mode::try_envoke().unwrap(), Mode::Production);
+            });
+        }
+
+        temp_env::with_var("CASE_INSENSITIVE_MODE", Some("local_dev"), || {
+            assert_eq!(Mode::try_envoke().unwrap(), Mode::LocalDev);
+        });
+
+        // Only the case is ignored, not how the words are joined
+        temp_env::with_var("CASE_INSENSITIVE_MODE", Some("local-dev"), || {
+            assert!(Mode::try_envoke().is_err());
+        });
+
+        temp_env::with_var("CASE_INSENSITIVE_MODE", Some("production"), || {
+            assert!(StrictMode::try_envoke().is_err());
+        });
+    }
+
+    #[test]
+    fn test_enum_matches_prefix_remainder() {
+        #[derive(Debug, PartialEq)]
+        struct Location {
+            host: String,
+            path: String,
+        }
+
+        impl FromStr for Location {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (host, path) = s.split_once('/').ok_or("expected `host/path`")?;
+                Ok(Location {
+                    host: host.to_string(),
+                    path: path.to_string(),
+                })
+            }
+        }
+
+        #[derive(Debug, PartialEq, Fill)]
+        #[fill(env = "PREFIX_DATABASE")]
+        enum Database {
+            #[fill(matches_prefix = "postgres://", remainder_into_inner)]
+            Postgres(Location),
+            #[fill(matches_prefix = "mysql://", remainder_into_inner)]
+            Mysql(Location),
+            #[fill(rename = "memory", matches_prefix = "memory:")]
+            Memory,
+        }
+
+        temp_env::with_var("PREFIX_DATABASE", Some("postgres://localhost/app"), || {
+            assert_eq!(
+                Database::try_envoke().unwrap(),
+                Database::Postgres(Location {
+                    host: "localhost".to_string(),
+                    path: "app".to_string(),
+                })
+            );
+        });
+
+        temp_env::with_var("PREFIX_DATABASE", Some("mysql://db.internal/users"), || {
+            assert_eq!(
+                Database::try_envoke().unwrap(),
+                Database::Mysql(Location {
+                    host: "db.internal".to_string(),
+                    path: "users".to_string(),
+                })
+            );
+        });
+
+        for value in ["memory", "memory:shared"] {
+            temp_env::with_var("PREFIX_DATABASE", Some(value), || {
+                assert_eq!(Database::try_envoke().unwrap(), Database::Memory);
+            });
+        }
+
+        temp_env::with_var("PREFIX_DATABASE", Some("postgres://localhost"), || {
+            assert!(matches!(
+                Database::try_envoke(),
+                Err(envoke::Error::ParseError(envoke::ParseError::Failed { .. }))
+            ));
+        });
+
+        temp_env::with_var("PREFIX_DATABASE", Some("sqlite://app.db"), || {
+            assert!(matches!(
+                Database::try_envoke(),
+                Err(envoke::Error::EnumError(envoke::EnumError::NotFound))
+            ));
+        });
+    }
+
+    #[test]
+    fn test_load_env_optional_with_default() {
+        fn default_timeout() -> u64 {
+            30
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, default = 8080)]
+            port: Option<u16>,
+
+            #[fill(env, default = default_timeout())]
+            timeout: Option<u64>,
+
+            #[fill(env)]
+            retries: Option<u8>,
+        }
+
+        temp_env::with_vars_unset(["PORT", "TIMEOUT", "RETRIES"], || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.port, Some(8080));
+            assert_eq!(test.timeout, Some(30));
+            assert_eq!(test.retries, None);
+        });
+
+        temp_env::with_vars(
+            [
+                ("PORT", Some("443")),
+                ("TIMEOUT", Some("5")),
+                ("RETRIES", Some("3")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.port, Some(443));
+                assert_eq!(test.timeout, Some(5));
+                assert_eq!(test.retries, Some(3));
+            },
+        );
+
+        // A value which is set but invalid is not replaced by the default
+        temp_env::with_vars(
+            [
+                ("PORT", Some("not a port")),
+                ("TIMEOUT", None),
+                ("RETRIES", None),
+            ],
+            || {
+                let err = parse_error_of(Test::try_envoke().unwrap_err(), "port");
+                assert!(err.is_unexpected_value_type());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_deque_heap_and_list() {
+        use std::collections::{BinaryHeap, LinkedList, VecDeque};
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env)]
+            queue: VecDeque<u16>,
+
+            #[fill(env)]
+            heap: BinaryHeap<u16>,
+
+            #[fill(env)]
+            list: LinkedList<String>,
+
+            #[fill(env)]
+            optional_queue: Option<VecDeque<u16>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("QUEUE", Some("3,1,2")),
+                ("HEAP", Some("3,7,1")),
+                ("LIST", Some("a,b,c")),
+                ("OPTIONAL_QUEUE", None),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.queue, VecDeque::from([3, 1, 2]));
+                assert_eq!(test.heap.into_sorted_vec(), vec![1, 3, 7]);
+                assert_eq!(
+                    test.list,
+                    LinkedList::from(["a".to_string(), "b".to_string(), "c".to_string()])
+                );
+                assert_eq!(test.optional_queue, None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_present() {
+        #[derive(Debug, Fill)]
+        #[fill(env_line)]
+        struct Test {
+            #[fill(env = "PRESENT_VERBOSE", present)]
+            verbose: bool,
+
+            #[fill(env = "PRESENT_QUIET", env = "PRESENT_SILENT", present)]
+            quiet: bool,
+        }
+
+        temp_env::with_vars(
+            [
+                ("PRESENT_VERBOSE", Some("")),
+                ("PRESENT_QUIET", None),
+                ("PRESENT_SILENT", Some("not a bool")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert!(test.verbose);
+                assert!(test.quiet);
+                assert_eq!(
+                    test.env_line("verbose").as_deref(),
+                    Some("PRESENT_VERBOSE=")
+                );
+            },
+        );
+
+        temp_env::with_vars_unset(
+            ["PRESENT_VERBOSE", "PRESENT_QUIET", "PRESENT_SILENT"],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert!(!test.verbose);
+                assert!(!test.quiet);
+                assert_eq!(test.env_line("verbose"), None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_ranges() {
+        use std::ops::RangeInclusive;
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", env_line)]
+        struct Test {
+            #[fill(env, ranges)]
+            ports: Vec<RangeInclusive<u16>>,
+
+            #[fill(env, ranges = "flatten")]
+            ids: Vec<i32>,
+
+            #[fill(env, ranges = "flatten", delimiter = ";")]
+            workers: Option<Vec<u8>>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("PORTS", Some("80, 443,8000-8100")),
+                ("IDS", Some("-3--1,7,10-12")),
+                ("WORKERS", None),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.ports, vec![80..=80, 443..=443, 8000..=8100]);
+                assert_eq!(test.ids, vec![-3, -2, -1, 7, 10, 11, 12]);
+                assert_eq!(test.workers, None);
+                assert_eq!(
+                    test.env_line("ports").as_deref(),
+                    Some("PORTS=80,443,8000-8100")
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("PORTS", Some("1")),
+                ("IDS", Some("5")),
+                ("WORKERS", Some("1-2;4")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.workers, Some(vec![1, 2, 4]));
+            },
+        );
+
+        for (ports, expected) in [
+            (
+                "80,90-",
+                envoke::ParseError::InvalidRange {
+                    value: "90-".to_string(),
+                },
+            ),
+            (
+                "80,x-100",
+                envoke::ParseError::InvalidRange {
+                    value: "x-100".to_string(),
+                },
+            ),
+            (
+                "80,100-90",
+                envoke::ParseError::ReversedRange {
+                    value: "100-90".to_string(),
+                },
+            ),
+        ] {
+            temp_env::with_vars([("PORTS", Some(ports)), ("IDS", Some("1"))], || {
+                let err = parse_error_of(Test::try_envoke().unwrap_err(), "ports");
+                let envoke::ParseError::InvalidElement { index, err } = err else {
+                    panic!("expected an invalid element for `{ports}`");
+                };
+                assert_eq!(index, 1);
+                assert_eq!(err.to_string(), expected.to_string());
+            });
+        }
+    }
+
+    #[test]
+    fn test_error_field() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, one_of = [80, 443])]
+            port: u16,
+
+            #[fill(env)]
+            host: String,
+        }
+
+        temp_env::with_vars(
+            [("PORT", Some("8080")), ("HOST", Some("localhost"))],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                assert_eq!(err.category(), envoke::ErrorCategory::Validation);
+                assert_eq!(err.field(), Some("port"));
+            },
+        );
+
+        temp_env::with_vars([("PORT", Some("abc")), ("HOST", Some("localhost"))], || {
+            let err = Test::try_envoke().unwrap_err();
+            assert_eq!(err.category(), envoke::ErrorCategory::Parse);
+            assert_eq!(err.field(), Some("port"));
+            assert!(parse_error_of(err, "port").is_unexpected_value_type());
+        });
+
+        temp_env::with_vars([("PORT", Some("443")), ("HOST", None)], || {
+            let err = Test::try_envoke().unwrap_err();
+            assert_eq!(err.category(), envoke::ErrorCategory::Retrieve);
+            assert_eq!(err.field(), Some("host"));
+            assert!(retrieve_error_of(err, "host").is_not_found());
+        });
+
+        // Errors of the whole struct are not tied to a field
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", prefix = "FIELDERR_", deny_unknown)]
+        struct Strict {
+            #[fill(env)]
+            host: String,
+        }
+
+        temp_env::with_vars(
+            [
+                ("FIELDERR_HOST", Some("localhost")),
+                ("FIELDERR_PORT", Some("80")),
+            ],
+            || {
+                let err = Strict::try_envoke().unwrap_err();
+                assert_eq!(err.category(), envoke::ErrorCategory::Validation);
+                assert_eq!(err.field(), None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_try_envoke_all() {
+        use envoke::ErrorCategory;
+
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "ALL_DB_")]
+        struct Database {
+            #[fill(env = "HOST")]
+            host: String,
+
+            #[fill(env = "PORT")]
+            port: u16,
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "ALL_")]
+        struct Test {
+            #[fill(env = "PORT", one_of = [80, 443])]
+            port: u16,
+
+            #[fill(env = "WORKERS")]
+            workers: usize,
+
+            #[fill(env = "NAME")]
+            name: String,
+
+            #[fill(nested)]
+            db: Database,
+        }
+
+        temp_env::with_vars(
+            [
+                ("ALL_PORT", Some("8080")),
+                ("ALL_WORKERS", Some("many")),
+                ("ALL_NAME", Some("api")),
+                ("ALL_DB_HOST", None),
+                ("ALL_DB_PORT", Some("-1")),
+            ],
+            || {
+                let errors = Test::try_envoke_all().unwrap_err();
+                let leaves: Vec<_> = errors
+                    .iter()
+                    .map(|err| (err.field(), err.category()))
+                    .collect();
+                assert_eq!(
+                    leaves,
+                    [
+                        (Some("port"), ErrorCategory::Validation),
+                        (Some("workers"), ErrorCategory::Parse),
+                        (Some("host"), ErrorCategory::Retrieve),
+                        (Some("port"), ErrorCategory::Parse),
+                    ]
+                );
+                assert_eq!(errors.to_string().lines().count(), 4);
+
+                // Stops at the first error as before
+                let err = Test::try_envoke().unwrap_err();
+                assert_eq!(err.field(), Some("port"));
+                assert_eq!(err.category(), ErrorCategory::Validation);
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("ALL_PORT", Some("443")),
+                ("ALL_WORKERS", Some("4")),
+                ("ALL_NAME", Some("api")),
+                ("ALL_DB_HOST", Some("localhost")),
+                ("ALL_DB_PORT", Some("5432")),
+            ],
+            || {
+                let test = Test::try_envoke_all().expect("failed to load test struct");
+                assert_eq!(test.workers, 4);
+                assert_eq!(test.db.port, 5432);
+            },
+        );
+
+        // Errors which are not tied to a field end the load
+        #[derive(Debug, Fill)]
+        #[fill(dotenv = "dotenv/missing.env")]
+        struct Dotenv {
+            #[fill(env = "ALL_PORT")]
+            port: u16,
+        }
+
+        let errors = Dotenv::try_envoke_all().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        let err = errors.into_iter().next().unwrap();
+        assert_eq!(err.field(), None);
+        assert_eq!(err.category(), ErrorCategory::Retrieve);
+    }
+
+    #[test]
+    fn test_load_env_parser_registry() {
+        use envoke::ParserRegistry;
+
+        #[derive(Debug, PartialEq)]
+        struct Codec(String);
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env = "REGISTRY_CODEC", parser = "codec")]
+            codec: Codec,
+
+            #[fill(env = "REGISTRY_LEVEL", parser = "level")]
+            level: Option<u8>,
+        }
+
+        let mut registry = ParserRegistry::new();
+        registry
+            .register("codec", |value: &str| match value {
+                "gzip" | "zstd" => Ok(Codec(value.to_string())),
+                _ => Err(format!("unknown codec `{value}`")),
+            })
+            .register("level", |value: &str| {
+                value.len().try_into().map(|len: u8| len * 2)
+            });
+
+        temp_env::with_vars(
+            [
+                ("REGISTRY_CODEC", Some("zstd")),
+                ("REGISTRY_LEVEL", Some("abc")),
+            ],
+            || {
+                let test = Test::try_envoke_with_registry(&registry).unwrap();
+                assert_eq!(test.codec, Codec("zstd".to_string()));
+                assert_eq!(test.level, Some(6));
+
+                // The parsers are only available while loading with the registry
+                let err = Test::try_envoke().unwrap_err();
+                assert_eq!(err.field(), Some("codec"));
+                assert!(err.to_string().contains("no parser returning `Codec`"));
+            },
+        );
+
+        temp_env::with_vars(
+            [("REGISTRY_CODEC", Some("brotli")), ("REGISTRY_LEVEL", None)],
+            || {
+                let err = Test::try_envoke_with_registry(&registry).unwrap_err();
+                assert!(err.to_string().contains("unknown codec `brotli`"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_optional_parse_fn_inner_arg() {
+        use std::time::Duration;
+
+        #[derive(Debug, PartialEq)]
+        struct Port(u16);
+
+        impl From<u16> for Port {
+            fn from(port: u16) -> Self {
+                Port(port)
+            }
+        }
+
+        fn parse_secs(secs: u64) -> Duration {
+            Duration::from_secs(secs)
+        }
+
+        fn parse_millis(millis: u64) -> Result<Duration, String> {
+            match millis {
+                0 => Err("timeout cannot be zero".to_string()),
+                millis => Ok(Duration::from_millis(millis)),
+            }
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, parse_fn = parse_secs, arg_type = u64)]
+            interval: Option<Duration>,
+
+            #[fill(env, try_parse_fn = parse_millis, arg_type = u64)]
+            timeout: Option<Duration>,
+
+            #[fill(env, arg_type = u16, into)]
+            port: Option<Port>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("INTERVAL", Some("5")),
+                ("TIMEOUT", Some("250")),
+                ("PORT", Some("8080")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.interval, Some(Duration::from_secs(5)));
+                assert_eq!(test.timeout, Some(Duration::from_millis(250)));
+                assert_eq!(test.port, Some(Port(8080)));
+            },
+        );
+
+        temp_env::with_vars_unset(["INTERVAL", "TIMEOUT", "PORT"], || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.interval, None);
+            assert_eq!(test.timeout, None);
+            assert_eq!(test.port, None);
+        });
+
+        temp_env::with_vars([("TIMEOUT", Some("0"))], || {
+            let err = Test::try_envoke().unwrap_err();
+            assert_eq!(err.field(), Some("timeout"));
+        });
+    }
+
+    #[test]
+    fn test_load_env_deny_unknown() {
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "STRICT_", rename_all = "UPPERCASE")]
+        struct Database {
+            #[fill(env)]
+            host: String,
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "STRICT_", rename_all = "UPPERCASE", deny_unknown)]
+        struct Test {
+            #[fill(env)]
+            port: u16,
+
+            #[fill(env = "DEBUG", env = "VERBOSE")]
+            debug: Option<bool>,
+
+            #[fill(nested)]
+            database: Database,
+        }
+
+        temp_env::with_vars(
+            [
+                ("STRICT_PORT", Some("80")),
+                ("STRICT_VERBOSE", Some("true")),
+                ("STRICT_HOST", Some("localhost")),
+                ("STRICTLY_UNRELATED", Some("1")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.port, 80);
+                assert_eq!(test.debug, Some(true));
+                assert_eq!(test.database.host, "localhost");
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("STRICT_PORT", Some("80")),
+                ("STRICT_PROT", Some("80")),
+                ("STRICT_HOTS", Some("localhost")),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                let envoke::Error::ValidationError(envoke::ValidationError::UnknownVariables {
+                    keys,
+                }) = &err
+                else {
+                    panic!("expected unknown variables, got {err}");
+                };
+                assert_eq!(keys, &["STRICT_HOTS", "STRICT_PROT"]);
+                assert!(err.to_string().contains("`STRICT_HOTS`, `STRICT_PROT`"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_fuzzy_names() {
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "FUZZY_", rename_all = "UPPERCASE")]
+        struct Database {
+            #[fill(env)]
+            host: String,
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "FUZZY_", rename_all = "UPPERCASE", fuzzy_names)]
+        struct Test {
+            #[fill(env)]
+            port: u16,
+
+            #[fill(env)]
+            log_level: Option<String>,
+
+            #[fill(nested)]
+            database: Database,
+        }
+
+        for (port, log_level, host) in [
+            ("FUZZY_PORT", "FUZZY_LOG_LEVEL", "FUZZY_HOST"),
+            ("fuzzy-port", "Fuzzy.Log.Level", "fuzzy_host"),
+            ("fuzzy.port", "FUZZY-LOG_LEVEL", "Fuzzy-Host"),
+        ] {
+            temp_env::with_vars(
+                [
+                    (port, Some("80")),
+                    (log_level, Some("debug")),
+                    (host, Some("localhost")),
+                ],
+                || {
+                    let test = Test::try_envoke().expect("failed to load test struct");
+                    assert_eq!(test.port, 80);
+                    assert_eq!(test.log_level.as_deref(), Some("debug"));
+                    assert_eq!(test.database.host, "localhost");
+                },
+            );
+        }
+
+        // An exact match takes precedence and other structs only read exact names
+        temp_env::with_vars(
+            [
+                ("FUZZY_PORT", Some("443")),
+                ("fuzzy-port", Some("80")),
+                ("fuzzy-host", Some("localhost")),
+                ("FUZZY_HOST", None),
+            ],
+            || {
+                assert_eq!(Test::try_envoke().unwrap().port, 443);
+                assert!(Database::try_envoke().is_err());
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_rest() {
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "PLUGIN_", rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env)]
+            port: u16,
+
+            #[fill(env = "HOST", no_prefix)]
+            host: Option<String>,
+
+            #[fill(rest)]
+            rest: HashMap<String, String>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("PLUGIN_PORT", Some("80")),
+                ("PLUGIN_COLOR", Some("red")),
+                ("PLUGIN_SIZE", Some("large")),
+                ("PLUGINS_UNRELATED", Some("1")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.port, 80);
+                assert_eq!(
+                    test.rest,
+                    HashMap::from([
+                        ("COLOR".to_string(), "red".to_string()),
+                        ("SIZE".to_string(), "large".to_string()),
+                    ])
+                );
+            },
+        );
+
+        // `PLUGIN_HOST` would be collected as `HOST`, which is read by `host`
+        temp_env::with_vars(
+            [
+                ("PLUGIN_PORT", Some("80")),
+                ("PLUGIN_HOST", Some("localhost")),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                let envoke::Error::ValidationError(envoke::ValidationError::RestOverlap {
+                    name,
+                    key,
+                }) = err
+                else {
+                    panic!("unexpected error: {err}");
+                };
+                assert_eq!(name, "PLUGIN_HOST");
+                assert_eq!(key, "HOST");
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_datetime() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", env_line)]
+        struct Test {
+            #[fill(env, datetime)]
+            start: chrono::DateTime<chrono::Utc>,
+
+            #[fill(env, datetime)]
+            local: Option<chrono::DateTime<chrono::FixedOffset>>,
+
+            #[fill(env, datetime)]
+            end: time::OffsetDateTime,
+
+            #[fill(env, datetime)]
+            deadline: Option<time::UtcDateTime>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("START", Some("2024-05-01T14:00:00+02:00")),
+                ("LOCAL", Some("2024-05-01T14:00:00+02:00")),
+                ("END", Some("2024-05-01T14:00:00.5+02:00")),
+                ("DEADLINE", None),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.start.to_rfc3339(), "2024-05-01T12:00:00+00:00");
+                assert_eq!(
+                    test.local.map(|local| local.offset().local_minus_utc()),
+                    Some(2 * 60 * 60)
+                );
+                assert_eq!(test.end.offset().whole_hours(), 2);
+                assert_eq!(test.end.millisecond(), 500);
+                assert_eq!(test.deadline, None);
+                assert_eq!(
+                    test.env_line("start").as_deref(),
+                    Some("START=2024-05-01T12:00:00+00:00")
+                );
+                assert_eq!(
+                    test.env_line("end").as_deref(),
+                    Some("END=2024-05-01T14:00:00.5+02:00")
+                );
+            },
+        );
+
+        // The offset is required
+        temp_env::with_vars(
+            [
+                ("START", Some("2024-05-01T12:00:00")),
+                ("END", Some("2024-05-01T12:00:00Z")),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                let envoke::Error::ParseError(envoke::ParseError::Failed { field, err }) = err
+                else {
+                    panic!("unexpected error: {err}");
+                };
+                assert_eq!(field, "start");
+                assert!(matches!(
+                    err.downcast_ref::<envoke::ParseError>(),
+                    Some(envoke::ParseError::InvalidDateTime { .. })
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_ignore_with_default() {
+        fn default_retries(_: &envoke::DefaultCtx) -> u32 {
+            3
+        }
+
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env)]
+            field1: String,
+
+            #[fill(ignore, default)]
+            handles: Vec<String>,
+
+            #[fill(ignore, default_fn = default_retries)]
+            retries: u32,
+
+            #[fill(ignore, default = 5)]
+            limit: Option<u32>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("field1", Some("value")),
+                ("handles", Some("a,b")),
+                ("retries", Some("10")),
+                ("limit", Some("10")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.field1, "value");
+                assert!(test.handles.is_empty());
+                assert_eq!(test.retries, 3);
+                assert_eq!(test.limit, Some(5));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_flag() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, flag)]
+            feature: Option<bool>,
+
+            #[fill(env, flag, default)]
+            verbose: bool,
+        }
+
+        let cases = [
+            (None, None),
+            (Some(""), Some(true)),
+            (Some("true"), Some(true)),
+            (Some("false"), Some(false)),
+        ];
+        for (value, expected) in cases {
+            temp_env::with_vars([("FEATURE", value), ("VERBOSE", Some(""))], || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.feature, expected, "{value:?}");
+                assert!(test.verbose);
+            });
+        }
+
+        temp_env::with_vars([("FEATURE", Some("maybe")), ("VERBOSE", None)], || {
+            let err = Test::try_envoke().unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    envoke::Error::ParseError(envoke::ParseError::Failed { ref field, .. })
+                        if field == "feature"
+                ),
+                "unexpected error: {err}"
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_env_removed() {
+        #[derive(Debug, Fill)]
+        #[fill(
+            rename_all = "UPPERCASE",
+            removed(env = "CACHE_TTL", note = "use CACHE_EXPIRY"),
+            removed(env = "LEGACY_MODE")
+        )]
+        struct Test {
+            #[fill(env)]
+            cache_expiry: u32,
+        }
+
+        temp_env::with_vars([("CACHE_EXPIRY", Some("60"))], || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.cache_expiry, 60);
+        });
+
+        temp_env::with_vars(
+            [("CACHE_EXPIRY", Some("60")), ("CACHE_TTL", Some("60"))],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                assert_eq!(
+                    err.to_string(),
+                    "Retrieve error occurred: environment variable `CACHE_TTL` has been removed, \
+                     use CACHE_EXPIRY"
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [("CACHE_EXPIRY", Some("60")), ("LEGACY_MODE", Some(""))],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                let envoke::Error::RetrieveError(envoke::RetrieveError::Removed { key, note }) =
+                    err
+                else {
+                    panic!("unexpected error: {err}");
+                };
+                assert_eq!(key, "LEGACY_MODE");
+                assert_eq!(note, None);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_decimal_comma() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, decimal_comma)]
+            ratio: f64,
+
+            #[fill(env, decimal_comma)]
+            scale: Option<f32>,
+        }
+
+        let cases = [
+            ("1,5", 1.5),
+            ("1.5", 1.5),
+            ("-0,25", -0.25),
+            ("1,5e3", 1500.0),
+            ("2E-2", 0.02),
+            ("inf", f64::INFINITY),
+            ("-infinity", f64::NEG_INFINITY),
+        ];
+        for (value, expected) in cases {
+            temp_env::with_vars([("RATIO", Some(value)), ("SCALE", Some(value))], || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.ratio, expected, "{value}");
+                assert_eq!(test.scale, Some(expected as f32), "{value}");
+            });
+        }
+
+        temp_env::with_vars([("RATIO", Some("NaN")), ("SCALE", None)], || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert!(test.ratio.is_nan());
+            assert_eq!(test.scale, None);
+        });
+
+        for value in ["1.000,5", "1,000,5", "", "one"] {
+            temp_env::with_var("RATIO", Some(value), || {
+                assert!(Test::try_envoke().is_err(), "{value}");
+            });
+        }
+    }
+
+    #[test]
+    fn test_reload() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE", reload)]
+        struct Test {
+            #[fill(env)]
+            port: u16,
+
+            #[fill(ignore)]
+            session: Option<String>,
+
+            #[fill(ignore, default)]
+            connections: Vec<String>,
+        }
+
+        temp_env::with_var("PORT", Some("80"), || {
+            let mut test = Test::try_envoke().expect("failed to load test struct");
+            test.session = Some("abc".to_string());
+            test.connections.push("db".to_string());
+
+            temp_env::with_var("PORT", Some("8080"), || {
+                let reloaded = test.reload().expect("failed to reload test struct");
+                assert_eq!(reloaded.port, 8080);
+                assert_eq!(reloaded.session.as_deref(), Some("abc"));
+                assert_eq!(reloaded.connections, ["db"]);
+            });
+
+            temp_env::with_var("PORT", Some("invalid"), || {
+                assert!(test.reload().is_err());
+            });
+        });
+    }
+
+    #[test]
+    fn test_load_env_phantom_data() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test<T> {
+            #[fill(env)]
+            port: u16,
+
+            marker: PhantomData<T>,
+        }
+
+        #[derive(Debug, Fill)]
+        struct Marker(std::marker::PhantomData<u8>);
+
+        temp_env::with_var("PORT", Some("80"), || {
+            let test = Test::<String>::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.port, 80);
+            assert_eq!(test.marker, PhantomData);
+
+            assert!(Marker::try_envoke().is_ok());
+        });
+    }
+
+    #[test]
+    fn test_load_env_trim_affixes() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, trim_prefix = "Bearer ")]
+            token: String,
+
+            #[fill(env, trim_prefix = "\"", trim_suffix = "\"")]
+            path: Option<std::path::PathBuf>,
+
+            #[fill(env, trim_suffix = "ms")]
+            timeouts: Vec<u64>,
+
+            #[fill(env, trim_prefix = "v", group_separators)]
+            build: u32,
+        }
+
+        temp_env::with_vars(
+            [
+                ("TOKEN", Some("Bearer xyz")),
+                ("PATH", Some("\"/etc/app\"")),
+                ("TIMEOUTS", Some("100ms,200,300ms")),
+                ("BUILD", Some("v1_000")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.token, "xyz");
+                assert_eq!(test.path, Some("/etc/app".into()));
+                assert_eq!(test.timeouts, [100, 200, 300]);
+                assert_eq!(test.build, 1000);
+            },
+        );
+
+        // Values without the prefix or suffix are parsed as is
+        temp_env::with_vars(
+            [
+                ("TOKEN", Some("xyz")),
+                ("PATH", None),
+                ("TIMEOUTS", Some("100")),
+                ("BUILD", Some("1000")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.token, "xyz");
+                assert_eq!(test.path, None);
+                assert_eq!(test.timeouts, [100]);
+                assert_eq!(test.build, 1000);
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_result_field() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env)]
+            port: Result<u16, String>,
+
+            #[fill(env)]
+            workers: std::result::Result<u8, envoke::Error>,
+
+            #[fill(env)]
+            host: String,
+        }
+
+        temp_env::with_vars(
+            [
+                ("PORT", Some("8080")),
+                ("WORKERS", Some("many")),
+                ("HOST", Some("localhost")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.port, Ok(8080));
+                assert!(test.workers.is_err());
+                assert_eq!(test.host, "localhost");
+            },
+        );
+
+        temp_env::with_vars([("PORT", Some("-1")), ("HOST", Some("localhost"))], || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert!(test.port.is_err_and(|e| e.contains("-1")));
+            assert!(matches!(test.workers, Err(envoke::Error::RetrieveError(_))));
+        });
+    }
+
+    #[test]
+    fn test_load_env_tracing() {
+        use std::sync::{Arc, Mutex};
+
+        use tracing::{
+            field::{Field, Visit},
+            span, Event, Metadata, Subscriber,
+        };
+
+        /// Records the fields of every event as `name=value` pairs
+        #[derive(Default)]
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+
+        struct Fields(Vec<String>);
+
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.push(format!("{}={value:?}", field.name()));
+            }
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields(Vec::new());
+                event.record(&mut fields);
+                self.0.lock().unwrap().push(fields.0.join(" "));
+            }
+
+            fn enter(&self, _: &span::Id) {}
+
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, sensitive)]
+            token: String,
+
+            #[fill(env, default = 80)]
+            port: u16,
+        }
+
+        let recorder = Recorder::default();
+        let events = Arc::clone(&recorder.0);
+
+        temp_env::with_var("TOKEN", Some("secret"), || {
+            tracing::subscriber::with_default(recorder, || {
+                Test::try_envoke().expect("failed to load test struct");
+            });
+        });
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            *events,
+            [
+                "message=resolved field field=\"token\" key=\"TOKEN\" default=false",
+                "message=resolved field field=\"port\" key=\"\" default=true",
+            ]
+        );
+        assert!(events.iter().all(|event| !event.contains("secret")));
+    }
+
+    #[test]
+    fn test_load_env_default_collections() {
+        use std::collections::BTreeSet;
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, default)]
+            map: HashMap<String, u16>,
+
+            #[fill(env, default)]
+            set: BTreeSet<u16>,
+
+            #[fill(env, default)]
+            optional_map: Option<HashMap<String, u16>>,
+
+            #[fill(env, default = HashMap::new())]
+            empty_map: Option<HashMap<String, u16>>,
+        }
+
+        temp_env::with_vars_unset(["MAP", "SET", "OPTIONAL_MAP", "EMPTY_MAP"], || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.map, HashMap::new());
+            assert_eq!(test.set, BTreeSet::new());
+            assert_eq!(test.optional_map, None);
+            assert_eq!(test.empty_map, Some(HashMap::new()));
+        });
+
+        temp_env::with_vars(
+            [
+                ("MAP", Some("a=1")),
+                ("SET", Some("2,1")),
+                ("OPTIONAL_MAP", Some("b=2")),
+                ("EMPTY_MAP", Some("c=3")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.map, HashMap::from([("a".to_string(), 1)]));
+                assert_eq!(test.set, BTreeSet::from([1, 2]));
+                assert_eq!(
+                    test.optional_map,
+                    Some(HashMap::from([("b".to_string(), 2)]))
+                );
+                assert_eq!(test.empty_map, Some(HashMap::from([("c".to_string(), 3)])));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_unix_timestamp() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, unix_timestamp)]
+            created: SystemTime,
+
+            #[fill(env, unix_timestamp = "millis")]
+            updated: Option<SystemTime>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("CREATED", Some("1714564800")),
+                ("UPDATED", Some("1714564800500")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.created, UNIX_EPOCH + Duration::from_secs(1714564800));
+                assert_eq!(
+                    test.updated,
+                    Some(UNIX_EPOCH + Duration::from_millis(1714564800500))
+                );
+            },
+        );
+
+        for value in ["-1", "1.5", "99999999999999999999", "18446744073709551615"] {
+            temp_env::with_var("CREATED", Some(value), || {
+                let err = Test::try_envoke().expect_err("expected an invalid timestamp");
+                assert!(err.to_string().contains("unix timestamp"), "{err}");
+            });
+        }
+    }
+
+    #[test]
+    fn test_load_env_field_case() {
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "app", delimiter = "_", rename_all = "SCREAMING_SNAKE_CASE")]
+        struct Test {
+            #[fill(env)]
+            port: u16,
+
+            #[fill(env, case = "kebab-case")]
+            legacy_host: String,
+
+            #[fill(env, case = "kebab-case", no_prefix)]
+            legacy_name: String,
+        }
+
+        temp_env::with_vars(
+            [
+                ("APP_PORT", Some("80")),
+                ("app-legacy-host", Some("localhost")),
+                ("legacy-name", Some("envoke")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.port, 80);
+                assert_eq!(test.legacy_host, "localhost");
+                assert_eq!(test.legacy_name, "envoke");
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_unique() {
+        use std::collections::BTreeSet;
+
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, unique)]
+            hosts: Vec<String>,
+
+            #[fill(env, unique)]
+            ports: Option<BTreeSet<u16>>,
+        }
+
+        temp_env::with_vars([("HOSTS", Some("a,b")), ("PORTS", Some("80,443"))], || {
+            let test = Test::try_envoke().expect("failed to load test struct");
+            assert_eq!(test.hosts, ["a", "b"]);
+            assert_eq!(test.ports, Some(BTreeSet::from([80, 443])));
+        });
+
+        temp_env::with_vars([("HOSTS", Some("a,b,a")), ("PORTS", None)], || {
+            let err = Test::try_envoke().expect_err("expected a duplicate host");
+            assert!(matches!(
+                err,
+                envoke::Error::ValidationError(envoke::ValidationError::Duplicate { index: 2, .. })
+            ));
+            assert_eq!(err.field(), Some("hosts"));
+        });
+
+        // Parsed values are compared, so `080` is the same port as `80`
+        temp_env::with_vars([("HOSTS", Some("a")), ("PORTS", Some("80,080"))], || {
+            let err = Test::try_envoke().expect_err("expected a duplicate port");
+            assert!(matches!(
+                err,
+                envoke::Error::ValidationError(envoke::ValidationError::Duplicate { index: 1, .. })
+            ));
+            assert_eq!(err.field(), Some("ports"));
+        });
+    }
+
+    #[test]
+    fn test_load_env_url_schemes() {
+        #[derive(Debug, Fill)]
+        #[fill(rename_all = "UPPERCASE")]
+        struct Test {
+            #[fill(env, url(schemes = ["http", "https"]))]
+            endpoint: url::Url,
+
+            #[fill(env, url(schemes = ["postgres"]))]
+            database: Option<url::Url>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("ENDPOINT", Some("https://example.com/api")),
+                ("DATABASE", Some("postgres://localhost/db")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.endpoint.as_str(), "https://example.com/api");
+                assert_eq!(
+                    test.database.map(String::from),
+                    Some("postgres://localhost/db".to_string())
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [("ENDPOINT", Some("ftp://example.com")), ("DATABASE", None)],
+            || {
+                let err = Test::try_envoke().expect_err("expected an invalid scheme");
+                assert!(matches!(
+                    err,
+                    envoke::Error::ValidationError(envoke::ValidationError::InvalidScheme { ref scheme, .. })
+                        if scheme == "ftp"
+                ));
+                assert_eq!(err.field(), Some("endpoint"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_templated_env() {
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "app", delimiter = "_", rename_all = "SCREAMING_SNAKE_CASE")]
+        struct Test {
+            // Declared before the field it refers to
+            #[fill(env = "service_{region}_endpoint", default = "localhost")]
+            endpoint: String,
+
+            #[fill(env)]
+            region: String,
+
+            #[fill(env = "{region}_{zone}_replicas")]
+            replicas: u8,
+
+            #[fill(env)]
+            zone: u8,
+        }
+
+        temp_env::with_vars(
+            [
+                ("APP_REGION", Some("EU")),
+                ("APP_ZONE", Some("2")),
+                ("APP_SERVICE_EU_ENDPOINT", Some("eu.example.com")),
+                ("APP_EU_2_REPLICAS", Some("3")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.region, "EU");
+                assert_eq!(test.endpoint, "eu.example.com");
+                assert_eq!(test.replicas, 3);
+            },
+        );
+
+        // The name is formatted with the value as is
+        temp_env::with_vars(
+            [
+                ("APP_REGION", Some("us")),
+                ("APP_ZONE", Some("1")),
+                ("APP_SERVICE_US_ENDPOINT", Some("us.example.com")),
+                ("APP_us_1_REPLICAS", Some("5")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.endpoint, "localhost");
+                assert_eq!(test.replicas, 5);
+            },
+        );
+
+        let keys = Test::env_keys();
+        assert!(keys.contains(&"APP_REGION".to_string()));
+        assert!(!keys.iter().any(|key| key.contains('{')));
+    }
+
+    #[test]
+    fn test_load_env_templated_env_checks() {
+        #[derive(Debug, Fill)]
+        struct Test {
+            #[fill(env = "CHECK_REGION")]
+            region: String,
+
+            #[fill(env = "EP_{region}", conflicts_with = "fallback")]
+            endpoint: Option<String>,
+
+            #[fill(env = "EP_FALLBACK")]
+            fallback: Option<String>,
+
+            #[fill(env = "TOKEN_{region}", required_if(field = "region", equals = "EU"))]
+            token: Option<String>,
+        }
+
+        temp_env::with_vars(
+            [
+                ("CHECK_REGION", Some("EU")),
+                ("EP_EU", Some("eu.example.com")),
+                ("EP_FALLBACK", None),
+                ("TOKEN_EU", Some("secret")),
+            ],
+            || {
+                let test = Test::try_envoke().expect("failed to load test struct");
+                assert_eq!(test.endpoint.as_deref(), Some("eu.example.com"));
+                assert_eq!(test.region, "EU");
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("CHECK_REGION", Some("EU")),
+                ("EP_EU", Some("eu.example.com")),
+                ("EP_FALLBACK", Some("example.com")),
+                ("TOKEN_EU", Some("secret")),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                assert!(matches!(
+                    err,
+                    envoke::Error::ValidationError(envoke::ValidationError::Conflict { ref field, ref other })
+                        if field == "endpoint" && other == "fallback"
+                ));
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("CHECK_REGION", Some("EU")),
+                ("EP_EU", None),
+                ("EP_FALLBACK", None),
+                ("TOKEN_EU", None),
+            ],
+            || {
+                let err = Test::try_envoke().unwrap_err();
+                assert_eq!(err.field(), Some("token"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_load_env_default_delimiter() {
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "APP", suffix = "V2")]
+        struct Joined {
+            #[fill(env)]
+            port: u16,
+        }
+
+        #[derive(Debug, Fill)]
+        #[fill(prefix = "APP_", suffix = "_V2")]
+        struct Separated {
+            #[fill(env)]
+            port: u16,
+        }
+
+        temp_env::with_vars(
+            [("APPportV2", Some("80")), ("APP_port_V2", Some("443"))],
+            || {
+                assert_eq!(Joined::try_envoke().unwrap().port, 80);
+                assert_eq!(Separated::try_envoke().unwrap().port, 443);
+            },
+        );
+    }
 }